@@ -34,3 +34,55 @@ export function metaspaceDecoder(replacement?: string, addPrefixSpace?: boolean)
  * This suffix will be replaced by whitespaces during the decoding
  */
 export function bpeDecoder(suffix?: string): Decoder;
+
+/**
+ * Instantiate a new ByteFallback Decoder
+ * ByteFallback converts tokens looking like `<0x61>` back to their byte, and
+ * attempts to make them into a string. Invalid sequences of bytes are replaced
+ * by the replacement character.
+ */
+export function byteFallbackDecoder(): Decoder;
+
+/**
+ * Instantiate a new Sequence Decoder
+ * @param decoders The decoders to run in order, each of them receiving the tokens
+ * produced by the previous one
+ */
+export function sequenceDecoder(decoders: Decoder[]): Decoder;
+
+/**
+ * Instantiate a new Replace Decoder
+ * @param pattern The pattern to look for in each token
+ * @param content The content used to replace each match
+ */
+export function replaceDecoder(pattern: string, content: string): Decoder;
+
+/**
+ * Instantiate a new Strip Decoder
+ * @param [content=' '] The character to strip. Must be exactly one character.
+ * @param [left=0] The maximum number of characters to strip at the beginning of each token
+ * @param [right=0] The maximum number of characters to strip at the end of each token
+ */
+export function stripDecoder(content?: string, left?: number, right?: number): Decoder;
+
+/**
+ * Instantiate a new Fuse Decoder
+ * Fuse fuses all the tokens into a single one, which lets the following decoders
+ * of a Sequence work on the whole string.
+ */
+export function fuseDecoder(): Decoder;
+
+/**
+ * Instantiate a new CTC Decoder
+ * The consecutive duplicate tokens are collapsed, then the pad token is removed and the
+ * word delimiter token is replaced by a space.
+ * @param [padToken='<pad>'] The pad token used by CTC to delimit a new token
+ * @param [wordDelimiterToken='|'] The word delimiter token. It will be replaced by a space
+ * @param [cleanup=true] Whether to cleanup some tokenization artifacts.
+ * Mainly spaces before punctuation, and some abbreviated english forms.
+ */
+export function ctcDecoder(
+  padToken?: string,
+  wordDelimiterToken?: string,
+  cleanup?: boolean
+): Decoder;
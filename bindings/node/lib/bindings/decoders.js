@@ -5,4 +5,10 @@ module.exports = {
   wordPieceDecoder: native.decoders_WordPiece,
   metaspaceDecoder: native.decoders_Metaspace,
   bpeDecoder: native.decoders_BPEDecoder,
+  byteFallbackDecoder: native.decoders_ByteFallback,
+  sequenceDecoder: native.decoders_Sequence,
+  replaceDecoder: native.decoders_Replace,
+  stripDecoder: native.decoders_Strip,
+  fuseDecoder: native.decoders_Fuse,
+  ctcDecoder: native.decoders_CTC,
 };
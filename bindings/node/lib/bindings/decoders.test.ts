@@ -1,4 +1,14 @@
-import { bpeDecoder, metaspaceDecoder, wordPieceDecoder } from "./decoders";
+import {
+  bpeDecoder,
+  byteFallbackDecoder,
+  ctcDecoder,
+  fuseDecoder,
+  metaspaceDecoder,
+  replaceDecoder,
+  sequenceDecoder,
+  stripDecoder,
+  wordPieceDecoder,
+} from "./decoders";
 
 describe("wordPieceDecoder", () => {
   it("accepts `undefined` as first parameter", () => {
@@ -25,3 +35,41 @@ describe("bpeDecoder", () => {
     expect(bpeDecoder(undefined)).toBeDefined();
   });
 });
+
+describe("byteFallbackDecoder", () => {
+  it("instantiates correctly", () => {
+    expect(byteFallbackDecoder()).toBeDefined();
+  });
+});
+
+describe("sequenceDecoder", () => {
+  it("accepts a list of decoders", () => {
+    expect(
+      sequenceDecoder([replaceDecoder("▁", " "), byteFallbackDecoder(), fuseDecoder()])
+    ).toBeDefined();
+  });
+
+  it("accepts an empty list", () => {
+    expect(sequenceDecoder([])).toBeDefined();
+  });
+});
+
+describe("stripDecoder", () => {
+  it("accepts `undefined` as parameters", () => {
+    expect(stripDecoder(undefined, undefined, undefined)).toBeDefined();
+  });
+
+  it("accepts all the parameters", () => {
+    expect(stripDecoder(" ", 1, 0)).toBeDefined();
+  });
+});
+
+describe("ctcDecoder", () => {
+  it("accepts `undefined` as parameters", () => {
+    expect(ctcDecoder(undefined, undefined, undefined)).toBeDefined();
+  });
+
+  it("accepts all the parameters", () => {
+    expect(ctcDecoder("[PAD]", "_", false)).toBeDefined();
+  });
+});
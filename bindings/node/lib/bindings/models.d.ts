@@ -38,6 +38,12 @@ export interface BPEOptions {
    * The suffix to attach to subword units that represent an end of word
    */
   endOfWordSuffix?: string;
+  /**
+   * Whether to use the `<0xNN>` byte tokens instead of the unknown token
+   * for unknown characters
+   * @default false
+   */
+  byteFallback?: boolean;
 }
 
 export namespace BPE {
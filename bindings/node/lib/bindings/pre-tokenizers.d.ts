@@ -81,3 +81,35 @@ export function charDelimiterSplitPreTokenizer(delimiter: string): PreTokenizer;
  * sequentially.
  */
 export function sequencePreTokenizer(pretokenizers: PreTokenizer[]): PreTokenizer;
+
+/**
+ * Returns a Split PreTokenizer
+ * This versatile pre-tokenizer splits using the provided pattern and
+ * according to the provided behavior.
+ *
+ * @param pattern The string pattern used for splitting
+ * @param behavior The behavior to use when splitting.
+ * Choices: "removed", "isolated", "merged_with_previous", "merged_with_next",
+ * "contiguous"
+ * @param [invert=false] Whether to invert the pattern
+ */
+export function splitPreTokenizer(
+  pattern: string,
+  behavior: string,
+  invert?: boolean
+): PreTokenizer;
+
+/**
+ * Returns a UnicodeScripts PreTokenizer
+ * This pre-tokenizer splits on the boundaries between Unicode scripts.
+ * Chars with the `Any` script never start a new split.
+ *
+ * @param [scriptMappings] The scripts to merge into another one, like `{ Hiragana: "Han" }`.
+ * Defaults to Hiragana and Katakana merged into Han
+ * @param [charMappings] The scripts given to specific chars, like `{ " ": "Any" }`.
+ * Defaults to `ー` as Han, and spaces as Any
+ */
+export function unicodeScriptsPreTokenizer(
+  scriptMappings?: { [script: string]: string },
+  charMappings?: { [char: string]: string }
+): PreTokenizer;
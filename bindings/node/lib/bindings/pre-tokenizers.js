@@ -10,4 +10,6 @@ module.exports = {
   charDelimiterSplitPreTokenizer: native.pre_tokenizers_CharDelimiterSplit,
   punctuationPreTokenizer: native.pre_tokenizers_Punctuation,
   sequencePreTokenizer: native.pre_tokenizers_Sequence,
+  splitPreTokenizer: native.pre_tokenizers_Split,
+  unicodeScriptsPreTokenizer: native.pre_tokenizers_UnicodeScripts,
 };
@@ -3,6 +3,8 @@ import {
   metaspacePreTokenizer,
   punctuationPreTokenizer,
   sequencePreTokenizer,
+  splitPreTokenizer,
+  unicodeScriptsPreTokenizer,
   whitespaceSplitPreTokenizer,
 } from "./pre-tokenizers";
 
@@ -45,3 +47,31 @@ describe("sequencePreTokenizer", () => {
     expect(sequence3.constructor.name).toEqual("PreTokenizer");
   });
 });
+
+describe("splitPreTokenizer", () => {
+  it("instantiates correctly with invert parameter", () => {
+    const processor = splitPreTokenizer(" ", "merged_with_previous", false);
+    expect(processor.constructor.name).toEqual("PreTokenizer");
+  });
+
+  it("instantiates correctly without invert parameter", () => {
+    const processor = splitPreTokenizer(" ", "isolated");
+    expect(processor.constructor.name).toEqual("PreTokenizer");
+  });
+});
+
+describe("unicodeScriptsPreTokenizer", () => {
+  it("instantiates correctly without any parameter", () => {
+    const processor = unicodeScriptsPreTokenizer();
+    expect(processor.constructor.name).toEqual("PreTokenizer");
+  });
+
+  it("instantiates correctly with custom mappings", () => {
+    const processor = unicodeScriptsPreTokenizer({ Hiragana: "Han" }, { " ": "Any" });
+    expect(processor.constructor.name).toEqual("PreTokenizer");
+  });
+
+  it("throws with an unknown script", () => {
+    expect(() => unicodeScriptsPreTokenizer({ Klingon: "Han" })).toThrow();
+  });
+});
@@ -1,4 +1,4 @@
-import { PaddingDirection } from "./enums";
+import { PaddingDirection, TruncationDirection } from "./enums";
 
 /**
  * An Encoding as returned by the Tokenizer
@@ -8,16 +8,18 @@ export interface RawEncoding {
    * Get the encoded tokens corresponding to the word at the given index in the input
    * sequence, with the form [startToken, endToken+1]
    * @param word The position of a word in the input sequence
+   * @param [seqId] The index of the input sequence in which to look
    * @since 0.7.0
    */
-  wordToTokens(word: number): [number, number] | undefined;
+  wordToTokens(word: number, seqId?: number): [number, number] | undefined;
 
   /**
    * Get the offsets of the word at the given index in the input sequence
    * @param word The index of the word in the input sequence
+   * @param [seqId] The index of the input sequence in which to look
    * @since 0.7.0
    */
-  wordToChars(word: number): [number, number] | undefined;
+  wordToChars(word: number, seqId?: number): [number, number] | undefined;
 
   /**
    * Get the offsets of the token at the given index
@@ -26,6 +28,12 @@ export interface RawEncoding {
    */
   tokenToChars(token: number): [number, number] | undefined;
 
+  /**
+   * Get the index of the input sequence that contains the token at the given index
+   * @param token The index of the token in the encoded sequence
+   */
+  tokenToSequence(token: number): number | undefined;
+
   /**
    * Get the word that contains the token at the given index
    * @param token The index of the token  in the encoded sequence
@@ -36,16 +44,18 @@ export interface RawEncoding {
   /**
    * Find the index of the token at the position of the given char
    * @param pos The position of a char in the input string
+   * @param [seqId] The index of the input sequence in which to look
    * @since 0.6.0
    */
-  charToToken(pos: number): number | undefined;
+  charToToken(pos: number, seqId?: number): number | undefined;
 
   /**
    * Get the word that contains the given char
    * @param pos The position of a char in the input string
+   * @param [seqId] The index of the input sequence in which to look
    * @since 0.7.0
    */
-  charToWord(pos: number): number | undefined;
+  charToWord(pos: number, seqId?: number): number | undefined;
 
   /**
    * Returns the attention mask
@@ -62,6 +72,11 @@ export interface RawEncoding {
    */
   getLength(): number;
 
+  /**
+   * Returns the number of input sequences represented by this Encoding
+   */
+  getNSequences(): number;
+
   /**
    * Returns the offsets
    */
@@ -93,6 +108,11 @@ export interface RawEncoding {
    */
   getWords(): (number | undefined)[];
 
+  /**
+   * The index of the input sequence of each token, undefined for the special tokens
+   */
+  getSequenceIds(): (number | undefined)[];
+
   /**
    * Pad the current Encoding at the given length
    *
@@ -107,8 +127,9 @@ export interface RawEncoding {
    * @param length The maximum length to be kept
    * @param [stride=0] The length of the previous first sequence
    * to be included in the overflowing sequence
+   * @param [direction='right'] The side from which the tokens are removed
    */
-  truncate(length: number, stride?: number): void;
+  truncate(length: number, stride?: number, direction?: TruncationDirection): void;
 }
 
 interface PaddingOptions {
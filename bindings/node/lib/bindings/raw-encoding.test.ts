@@ -92,6 +92,7 @@ describe("RawEncoding", () => {
     expect(typeof encoding.getTokens).toBe("function");
     expect(typeof encoding.getTypeIds).toBe("function");
     expect(typeof encoding.getWords).toBe("function");
+    expect(typeof encoding.getSequenceIds).toBe("function");
     expect(typeof encoding.pad).toBe("function");
     expect(typeof encoding.truncate).toBe("function");
   });
@@ -109,6 +110,12 @@ describe("RawEncoding", () => {
     });
   });
 
+  describe("getSequenceIds", () => {
+    it("returns the correct list of indexes", () => {
+      expect(encoding.getSequenceIds()).toEqual([0, 0, 0, 0, 0]);
+    });
+  });
+
   describe("wordToTokens", () => {
     it("returns the correct indexes", () => {
       const indexes = encoding.wordToTokens(3);
@@ -119,6 +126,11 @@ describe("RawEncoding", () => {
       const index = encoding.wordToTokens(100);
       expect(index).toBeUndefined();
     });
+
+    it("returns undefined when out of range sequence", () => {
+      const index = encoding.wordToTokens(3, 1);
+      expect(index).toBeUndefined();
+    });
   });
 
   describe("wordToChars", () => {
@@ -167,6 +179,11 @@ describe("RawEncoding", () => {
       const index = encoding.charToToken(100);
       expect(index).toBeUndefined();
     });
+
+    it("looks in the given sequence", () => {
+      expect(encoding.charToToken(3, 0)).toEqual(1);
+      expect(encoding.charToToken(3, 1)).toBeUndefined();
+    });
   });
 
   describe("charToWord", () => {
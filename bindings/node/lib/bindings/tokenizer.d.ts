@@ -1,5 +1,5 @@
 import { Decoder } from "./decoders";
-import { PaddingDirection, TruncationStrategy } from "./enums";
+import { PaddingDirection, TruncationDirection, TruncationStrategy } from "./enums";
 import { Model } from "./models";
 import { Normalizer } from "./normalizers";
 import { PostProcessor } from "./post-processors";
@@ -19,9 +19,15 @@ export interface TruncationOptions {
    * starting from the longest one at each token (when there is a pair of input sequences).
    * - `TruncationStrategy.OnlyFirst` Only truncate the first sequence.
    * - `TruncationStrategy.OnlySecond` Only truncate the second sequence.
+   * - `{ only_sequence: n }` Only truncate the sequence at index `n`.
    * @default TruncationStrategy.LongestFirst
    */
-  strategy?: TruncationStrategy;
+  strategy?: TruncationStrategy | { only_sequence: number };
+  /**
+   * The side from which the tokens are removed
+   * @default TruncationDirection.Right
+   */
+  direction?: TruncationDirection;
 }
 
 export interface TruncationConfiguration extends Required<TruncationOptions> {
@@ -90,6 +96,18 @@ export interface EncodeOptions {
    * @default true
    */
   addSpecialTokens?: boolean;
+  /**
+   * The unit of the offsets: bytes, chars, or UTF-16 code units like the indices
+   * of a JavaScript string
+   * @default "char"
+   */
+  offsetType?: "byte" | "char" | "utf16";
+  /**
+   * Whether the offsets are relative to the original string, or to the normalized one.
+   * The overflowing encodings use the same offsets.
+   * @default "original"
+   */
+  offsetReferential?: "original" | "normalized";
 }
 
 /**
@@ -3,7 +3,7 @@
 
 import { promisify } from "util";
 
-import { PaddingDirection, TruncationStrategy } from "./enums";
+import { PaddingDirection, TruncationDirection, TruncationStrategy } from "./enums";
 import { BPE } from "./models";
 import { RawEncoding } from "./raw-encoding";
 import {
@@ -220,6 +220,29 @@ describe("Tokenizer", () => {
         tokenizer.setTruncation(2, { strategy: TruncationStrategy.OnlySecond });
         await expect(encode("my name is john", null)).rejects.toThrow();
       });
+
+      it("truncates from the left with direction `left`", async () => {
+        tokenizer.setTruncation(2, { direction: TruncationDirection.Left });
+
+        const singleEncoding = await encode("my name is john", null);
+        expect(singleEncoding.getTokens()).toEqual(["is", "john"]);
+        expect(singleEncoding.getOverflowing()[0].getTokens()).toEqual(["my", "name"]);
+      });
+
+      it("uses the same offsets for the overflowing encodings", async () => {
+        tokenizer.addTokens(["😀"]);
+        tokenizer.setTruncation(2);
+
+        const encoding = await encode("my 😀 is john", null, { offsetType: "utf16" });
+        expect(encoding.getOffsets()).toEqual([
+          [0, 2],
+          [3, 5],
+        ]);
+        expect(encoding.getOverflowing()[0].getOffsets()).toEqual([
+          [6, 8],
+          [9, 13],
+        ]);
+      });
     });
 
     describe("when padding is enabled", () => {
@@ -349,6 +372,7 @@ describe("Tokenizer", () => {
         maxLength: 2,
         strategy: TruncationStrategy.LongestFirst,
         stride: 0,
+        direction: TruncationDirection.Right,
       };
       expect(truncation).toEqual(expectedConfig);
     });
@@ -11,6 +11,7 @@ export class Encoding {
   private _tokens?: string[];
   private _typeIds?: number[];
   private _wordIndexes?: (number | undefined)[];
+  private _sequenceIds?: (number | undefined)[];
 
   constructor(private _rawEncoding: RawEncoding) {}
 
@@ -140,23 +141,36 @@ export class Encoding {
     return (this._wordIndexes = this._rawEncoding.getWords());
   }
 
+  /**
+   * The index of the input sequence of each token, undefined for the special tokens
+   */
+  get sequenceIds(): (number | undefined)[] {
+    if (this._sequenceIds) {
+      return this._sequenceIds;
+    }
+
+    return (this._sequenceIds = this._rawEncoding.getSequenceIds());
+  }
+
   /**
    * Get the encoded tokens corresponding to the word at the given index in the input
    * sequence, with the form [startToken, endToken+1]
    * @param word The position of a word in the input sequence
+   * @param [seqId] The index of the input sequence in which to look
    * @since 0.7.0
    */
-  wordToTokens(word: number): [number, number] | undefined {
-    return this._rawEncoding.wordToTokens(word);
+  wordToTokens(word: number, seqId?: number): [number, number] | undefined {
+    return this._rawEncoding.wordToTokens(word, seqId);
   }
 
   /**
    * Get the offsets of the word at the given index in the input sequence
    * @param word The index of the word in the input sequence
+   * @param [seqId] The index of the input sequence in which to look
    * @since 0.7.0
    */
-  wordToChars(word: number): [number, number] | undefined {
-    return this._rawEncoding.wordToChars(word);
+  wordToChars(word: number, seqId?: number): [number, number] | undefined {
+    return this._rawEncoding.wordToChars(word, seqId);
   }
 
   /**
@@ -180,9 +194,10 @@ export class Encoding {
   /**
    * Find the index of the token at the position of the given char
    * @param pos The position of a char in the input string
+   * @param [seqId] The index of the input sequence in which to look
    */
-  charToToken(pos: number): number | undefined {
-    return this._rawEncoding.charToToken(pos);
+  charToToken(pos: number, seqId?: number): number | undefined {
+    return this._rawEncoding.charToToken(pos, seqId);
   }
 
   /**
@@ -219,6 +234,7 @@ export class Encoding {
       "_tokens",
       "_typeIds",
       "_wordIndexes",
+      "_sequenceIds",
     ]) {
       delete this[prop as keyof this];
     }
@@ -20,6 +20,13 @@ impl tk::Decoder for Decoder {
             .ok_or("Uninitialized Decoder")?
             .decode(tokens)
     }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> tk::Result<Vec<String>> {
+        self.decoder
+            .as_ref()
+            .ok_or("Uninitialized Decoder")?
+            .decode_chain(tokens)
+    }
 }
 
 declare_types! {
@@ -82,11 +89,113 @@ fn bpe_decoder(mut cx: FunctionContext) -> JsResult<JsDecoder> {
     Ok(decoder)
 }
 
+/// byte_fallback()
+fn byte_fallback(mut cx: FunctionContext) -> JsResult<JsDecoder> {
+    let mut decoder = JsDecoder::new::<_, JsDecoder, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    decoder.borrow_mut(&guard).decoder =
+        Some(Arc::new(tk::decoders::byte_fallback::ByteFallback.into()));
+    Ok(decoder)
+}
+
+/// sequence(decoders: Decoder[])
+fn sequence(mut cx: FunctionContext) -> JsResult<JsDecoder> {
+    let decoders = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let mut sequence = Vec::with_capacity(decoders.len());
+
+    decoders
+        .into_iter()
+        .map(
+            |decoder| match decoder.downcast::<JsDecoder>().or_throw(&mut cx) {
+                Ok(decoder) => {
+                    let guard = cx.lock();
+                    let decoder = decoder.borrow(&guard).decoder.clone();
+                    if let Some(decoder) = decoder {
+                        sequence.push((*decoder).clone());
+                        Ok(())
+                    } else {
+                        cx.throw_error("Uninitialized Decoder")
+                    }
+                }
+                Err(e) => Err(e),
+            },
+        )
+        .collect::<NeonResult<_>>()?;
+
+    let mut decoder = JsDecoder::new::<_, JsDecoder, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    decoder.borrow_mut(&guard).decoder = Some(Arc::new(
+        tk::decoders::sequence::Sequence::new(sequence).into(),
+    ));
+    Ok(decoder)
+}
+
+/// replace(pattern: string, content: string)
+fn replace(mut cx: FunctionContext) -> JsResult<JsDecoder> {
+    let pattern: String = cx.extract::<String>(0)?;
+    let content: String = cx.extract::<String>(1)?;
+
+    let mut decoder = JsDecoder::new::<_, JsDecoder, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    decoder.borrow_mut(&guard).decoder = Some(Arc::new(
+        tk::decoders::replace::Replace::new(pattern, content)
+            .map_err(|e| Error(e.to_string()))?
+            .into(),
+    ));
+    Ok(decoder)
+}
+
+/// strip(content: string = " ", left: number = 0, right: number = 0)
+fn strip(mut cx: FunctionContext) -> JsResult<JsDecoder> {
+    let content = cx.extract_opt::<char>(0)?.unwrap_or(' ');
+    let left = cx.extract_opt::<usize>(1)?.unwrap_or(0);
+    let right = cx.extract_opt::<usize>(2)?.unwrap_or(0);
+
+    let mut decoder = JsDecoder::new::<_, JsDecoder, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    decoder.borrow_mut(&guard).decoder = Some(Arc::new(
+        tk::decoders::strip::Strip::new(content, left, right).into(),
+    ));
+    Ok(decoder)
+}
+
+/// fuse()
+fn fuse(mut cx: FunctionContext) -> JsResult<JsDecoder> {
+    let mut decoder = JsDecoder::new::<_, JsDecoder, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    decoder.borrow_mut(&guard).decoder = Some(Arc::new(tk::decoders::fuse::Fuse.into()));
+    Ok(decoder)
+}
+
+/// ctc_decoder(pad_token: String = "<pad>", word_delimiter_token: String = "|", cleanup = true)
+fn ctc_decoder(mut cx: FunctionContext) -> JsResult<JsDecoder> {
+    let pad_token = cx
+        .extract_opt::<String>(0)?
+        .unwrap_or_else(|| String::from("<pad>"));
+    let word_delimiter_token = cx
+        .extract_opt::<String>(1)?
+        .unwrap_or_else(|| String::from("|"));
+    let cleanup = cx.extract_opt::<bool>(2)?.unwrap_or(true);
+
+    let mut decoder = JsDecoder::new::<_, JsDecoder, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    decoder.borrow_mut(&guard).decoder = Some(Arc::new(
+        tk::decoders::ctc::CTC::new(pad_token, word_delimiter_token, cleanup).into(),
+    ));
+    Ok(decoder)
+}
+
 /// Register everything here
 pub fn register(m: &mut ModuleContext, prefix: &str) -> NeonResult<()> {
     m.export_function(&format!("{}_ByteLevel", prefix), byte_level)?;
     m.export_function(&format!("{}_WordPiece", prefix), wordpiece)?;
     m.export_function(&format!("{}_Metaspace", prefix), metaspace)?;
     m.export_function(&format!("{}_BPEDecoder", prefix), bpe_decoder)?;
+    m.export_function(&format!("{}_ByteFallback", prefix), byte_fallback)?;
+    m.export_function(&format!("{}_Sequence", prefix), sequence)?;
+    m.export_function(&format!("{}_Replace", prefix), replace)?;
+    m.export_function(&format!("{}_Strip", prefix), strip)?;
+    m.export_function(&format!("{}_Fuse", prefix), fuse)?;
+    m.export_function(&format!("{}_CTC", prefix), ctc_decoder)?;
     Ok(())
 }
@@ -1,7 +1,7 @@
 extern crate tokenizers as tk;
 
 use crate::extraction::*;
-use crate::tokenizer::PaddingParams;
+use crate::tokenizer::{PaddingParams, TruncationDirection};
 use neon::prelude::*;
 
 /// Encoding
@@ -27,6 +27,18 @@ declare_types! {
             Ok(cx.number(length as f64).upcast())
         }
 
+        method getNSequences(mut cx) {
+            // getNSequences(): number
+
+            let this = cx.this();
+            let guard = cx.lock();
+            let n = this.borrow(&guard)
+                .encoding.as_ref().expect("Uninitialized Encoding")
+                .n_sequences();
+
+            Ok(cx.number(n as f64).upcast())
+        }
+
         method getIds(mut cx) {
             // getIds(): number[]
 
@@ -105,6 +117,18 @@ declare_types! {
             Ok(neon_serde::to_value(&mut cx, &ids)?)
         }
 
+        method getSequenceIds(mut cx) {
+            // getSequenceIds(): (number | undefined)[]
+
+            let this = cx.this();
+            let guard = cx.lock();
+            let ids = this.borrow(&guard)
+                .encoding.as_ref().expect("Uninitialized Encoding")
+                .sequence_ids();
+
+            Ok(neon_serde::to_value(&mut cx, &ids)?)
+        }
+
         method getOffsets(mut cx) {
             // getOffsets(): [number, number][]
 
@@ -145,16 +169,20 @@ declare_types! {
         }
 
         method wordToTokens(mut cx) {
-            // wordToTokens(word: number): [number, number] | undefined
+            // wordToTokens(word: number, seqId?: number): [number, number] | undefined
 
             let word = cx.extract::<u32>(0)?;
+            let seq = cx.extract_opt::<usize>(1)?;
 
             let this = cx.this();
             let guard = cx.lock();
 
-            let res = this.borrow(&guard)
-                .encoding.as_ref().expect("Uninitialized Encoding")
-                .word_to_tokens(word);
+            let borrowed = this.borrow(&guard);
+            let encoding = borrowed.encoding.as_ref().expect("Uninitialized Encoding");
+            let res = match seq {
+                Some(seq) => encoding.word_to_tokens_in_sequence(word, seq),
+                None => encoding.word_to_tokens(word),
+            };
 
             if let Some(tokens) = res {
                 Ok(neon_serde::to_value(&mut cx, &tokens)?)
@@ -164,16 +192,20 @@ declare_types! {
         }
 
         method wordToChars(mut cx) {
-            // wordToChars(word: number): [number, number] | undefined
+            // wordToChars(word: number, seqId?: number): [number, number] | undefined
 
             let word = cx.argument::<JsNumber>(0)?.value() as u32;
+            let seq = cx.extract_opt::<usize>(1)?;
 
             let this = cx.this();
             let guard = cx.lock();
 
-            let res = this.borrow(&guard)
-                .encoding.as_ref().expect("Uninitialized Encoding")
-                .word_to_chars(word);
+            let borrowed = this.borrow(&guard);
+            let encoding = borrowed.encoding.as_ref().expect("Uninitialized Encoding");
+            let res = match seq {
+                Some(seq) => encoding.word_to_chars_in_sequence(word, seq),
+                None => encoding.word_to_chars(word),
+            };
 
             if let Some(offsets) = res {
                 Ok(neon_serde::to_value(&mut cx, &offsets)?)
@@ -201,6 +233,24 @@ declare_types! {
             }
         }
 
+        method tokenToSequence(mut cx) {
+            // tokenToSequence(token: number): number | undefined
+
+            let token = cx.argument::<JsNumber>(0)?.value() as usize;
+
+            let this = cx.this();
+            let guard = cx.lock();
+            let index = this.borrow(&guard)
+                .encoding.as_ref().expect("Uninitialized Encoding")
+                .token_to_sequence(token);
+
+            if let Some(index) = index {
+                Ok(cx.number(index as f64).upcast())
+            } else {
+                Ok(cx.undefined().upcast())
+            }
+        }
+
         method tokenToWord(mut cx) {
             // tokenToWord(token: number): number | undefined
 
@@ -220,15 +270,19 @@ declare_types! {
         }
 
         method charToToken(mut cx) {
-            // charToToken(pos: number): number | undefined
+            // charToToken(pos: number, seqId?: number): number | undefined
 
             let pos = cx.argument::<JsNumber>(0)?.value() as usize;
+            let seq = cx.extract_opt::<usize>(1)?;
 
             let this = cx.this();
             let guard = cx.lock();
-            let index = this.borrow(&guard)
-                .encoding.as_ref().expect("Uninitialized Encoding")
-                .char_to_token(pos);
+            let borrowed = this.borrow(&guard);
+            let encoding = borrowed.encoding.as_ref().expect("Uninitialized Encoding");
+            let index = match seq {
+                Some(seq) => encoding.char_to_token_in_sequence(pos, seq),
+                None => encoding.char_to_token(pos),
+            };
 
             if let Some(index) = index {
                 Ok(cx.number(index as f64).upcast())
@@ -238,15 +292,19 @@ declare_types! {
         }
 
         method charToWord(mut cx) {
-            // charToWord(pos: number): number | undefined
+            // charToWord(pos: number, seqId?: number): number | undefined
 
             let pos = cx.argument::<JsNumber>(0)?.value() as usize;
+            let seq = cx.extract_opt::<usize>(1)?;
 
             let this = cx.this();
             let guard = cx.lock();
-            let index = this.borrow(&guard)
-                .encoding.as_ref().expect("Uninitialized Encoding")
-                .char_to_word(pos);
+            let borrowed = this.borrow(&guard);
+            let encoding = borrowed.encoding.as_ref().expect("Uninitialized Encoding");
+            let index = match seq {
+                Some(seq) => encoding.char_to_word_in_sequence(pos, seq),
+                None => encoding.char_to_word(pos),
+            };
 
             if let Some(index) = index {
                 Ok(cx.number(index as f64).upcast())
@@ -282,16 +340,18 @@ declare_types! {
         }
 
         method truncate(mut cx) {
-            // truncate(length: number, stride: number = 0)
+            // truncate(length: number, stride: number = 0, direction: 'left' | 'right' = 'right')
 
             let length = cx.extract::<usize>(0)?;
             let stride = cx.extract_opt::<usize>(1)?.unwrap_or(0);
+            let direction = cx.extract_opt::<TruncationDirection>(2)?
+                .map_or(tk::TruncationDirection::Right, |d| d.0);
 
             let mut this = cx.this();
             let guard = cx.lock();
             this.borrow_mut(&guard)
                 .encoding.as_mut().expect("Uninitialized Encoding")
-                .truncate(length, stride);
+                .truncate(length, stride, direction);
 
             Ok(cx.undefined().upcast())
         }
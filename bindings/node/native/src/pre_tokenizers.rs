@@ -2,9 +2,11 @@ extern crate tokenizers as tk;
 
 use crate::extraction::*;
 use neon::prelude::*;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use serde::{ser::SerializeStruct, Serialize, Serializer};
+use tk::pre_tokenizers::unicode_scripts::Script;
 use tk::pre_tokenizers::PreTokenizerWrapper;
 use tk::PreTokenizedString;
 
@@ -186,6 +188,51 @@ fn char_delimiter_split(mut cx: FunctionContext) -> JsResult<JsPreTokenizer> {
     Ok(pretok)
 }
 
+/// split(pattern: string, behavior: string, invert: bool = false)
+fn split(mut cx: FunctionContext) -> JsResult<JsPreTokenizer> {
+    let pattern = cx.extract::<String>(0)?;
+    let behavior = match cx.extract::<String>(1)?.as_ref() {
+        "removed" => tk::SplitDelimiterBehavior::Removed,
+        "isolated" => tk::SplitDelimiterBehavior::Isolated,
+        "merged_with_previous" => tk::SplitDelimiterBehavior::MergedWithPrevious,
+        "merged_with_next" => tk::SplitDelimiterBehavior::MergedWithNext,
+        "contiguous" => tk::SplitDelimiterBehavior::Contiguous,
+        b => {
+            return cx.throw_error(format!(
+                "Wrong value for SplitDelimiterBehavior, expected one of: \
+                `removed, isolated, merged_with_previous, merged_with_next, contiguous`, got {}",
+                b
+            ))
+        }
+    };
+    let invert = cx.extract_opt::<bool>(2)?.unwrap_or(false);
+
+    let split = tk::pre_tokenizers::split::Split::new(pattern, behavior, invert)
+        .map_err(|e| Error(e.to_string()))?;
+    let mut pretok = JsPreTokenizer::new::<_, JsPreTokenizer, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    pretok.borrow_mut(&guard).pretok = Some(split.into());
+
+    Ok(pretok)
+}
+
+/// unicode_scripts(scriptMappings?: { [script: string]: string }, charMappings?: { [char: string]: string })
+fn unicode_scripts(mut cx: FunctionContext) -> JsResult<JsPreTokenizer> {
+    let mut unicode_scripts = tk::pre_tokenizers::unicode_scripts::UnicodeScripts::default();
+    if let Some(script_mappings) = cx.extract_opt::<BTreeMap<Script, Script>>(0)? {
+        unicode_scripts = unicode_scripts.script_mappings(script_mappings);
+    }
+    if let Some(char_mappings) = cx.extract_opt::<BTreeMap<char, Script>>(1)? {
+        unicode_scripts = unicode_scripts.char_mappings(char_mappings);
+    }
+
+    let mut pretok = JsPreTokenizer::new::<_, JsPreTokenizer, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    pretok.borrow_mut(&guard).pretok = Some(unicode_scripts.into());
+
+    Ok(pretok)
+}
+
 /// Register everything here
 pub fn register(m: &mut ModuleContext, prefix: &str) -> NeonResult<()> {
     m.export_function(&format!("{}_ByteLevel", prefix), byte_level)?;
@@ -203,6 +250,8 @@ pub fn register(m: &mut ModuleContext, prefix: &str) -> NeonResult<()> {
     )?;
     m.export_function(&format!("{}_Punctuation", prefix), punctuation)?;
     m.export_function(&format!("{}_Sequence", prefix), sequence)?;
+    m.export_function(&format!("{}_Split", prefix), split)?;
+    m.export_function(&format!("{}_UnicodeScripts", prefix), unicode_scripts)?;
     Ok(())
 }
 
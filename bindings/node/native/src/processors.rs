@@ -22,6 +22,13 @@ impl tk::PostProcessor for Processor {
             .added_tokens(is_pair)
     }
 
+    fn added_tokens_for_sequences(&self, n_sequences: usize) -> usize {
+        self.processor
+            .as_ref()
+            .expect("Uninitialized PostProcessor")
+            .added_tokens_for_sequences(n_sequences)
+    }
+
     fn process(
         &self,
         encoding: Encoding,
@@ -33,6 +40,17 @@ impl tk::PostProcessor for Processor {
             .ok_or("Uninitialized PostProcessor")?
             .process(encoding, pair_encoding, add_special_tokens)
     }
+
+    fn process_sequences(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> tk::Result<Encoding> {
+        self.processor
+            .as_ref()
+            .ok_or("Uninitialized PostProcessor")?
+            .process_sequences(encodings, add_special_tokens)
+    }
 }
 
 declare_types! {
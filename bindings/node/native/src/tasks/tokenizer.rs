@@ -3,11 +3,23 @@ extern crate tokenizers as tk;
 use crate::encoding::*;
 use crate::tokenizer::Tokenizer;
 use neon::prelude::*;
-use tk::tokenizer::{EncodeInput, Encoding};
+use tk::tokenizer::{EncodeInput, Encoding, OffsetReferential, OffsetType};
 
 pub enum EncodeTask<'s> {
-    Single(Tokenizer, Option<EncodeInput<'s>>, bool),
-    Batch(Tokenizer, Option<Vec<EncodeInput<'s>>>, bool),
+    Single(
+        Tokenizer,
+        Option<EncodeInput<'s>>,
+        bool,
+        OffsetReferential,
+        OffsetType,
+    ),
+    Batch(
+        Tokenizer,
+        Option<Vec<EncodeInput<'s>>>,
+        bool,
+        OffsetReferential,
+        OffsetType,
+    ),
 }
 
 pub enum EncodeOutput {
@@ -22,7 +34,7 @@ impl Task for EncodeTask<'static> {
 
     fn perform(&self) -> Result<Self::Output, Self::Error> {
         match self {
-            EncodeTask::Single(worker, input, add_special_tokens) => {
+            EncodeTask::Single(worker, input, add_special_tokens, offset_ref, offset_type) => {
                 let mut input: Option<EncodeInput> =
                     unsafe { std::ptr::replace(input as *const _ as *mut _, None) };
 
@@ -30,14 +42,16 @@ impl Task for EncodeTask<'static> {
                     .tokenizer
                     .read()
                     .unwrap()
-                    .encode_char_offsets(
+                    .encode_with_offsets(
                         input.take().ok_or("No provided input")?,
                         *add_special_tokens,
+                        *offset_ref,
+                        *offset_type,
                     )
                     .map_err(|e| format!("{}", e))
                     .map(EncodeOutput::Single)
             }
-            EncodeTask::Batch(worker, input, add_special_tokens) => {
+            EncodeTask::Batch(worker, input, add_special_tokens, offset_ref, offset_type) => {
                 let mut input: Option<Vec<EncodeInput>> =
                     unsafe { std::ptr::replace(input as *const _ as *mut _, None) };
 
@@ -45,9 +59,11 @@ impl Task for EncodeTask<'static> {
                     .tokenizer
                     .read()
                     .unwrap()
-                    .encode_batch_char_offsets(
+                    .encode_batch_with_offsets(
                         input.take().ok_or("No provided input")?,
                         *add_special_tokens,
+                        *offset_ref,
+                        *offset_type,
                     )
                     .map_err(|e| format!("{}", e))
                     .map(EncodeOutput::Batch)
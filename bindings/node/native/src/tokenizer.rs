@@ -210,6 +210,29 @@ impl<'s> From<TextEncodeInput<'s>> for tk::EncodeInput<'s> {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "tk::OffsetType", rename_all = "lowercase")]
+enum OffsetTypeDef {
+    Byte,
+    Char,
+    Utf16,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "tk::OffsetReferential", rename_all = "lowercase")]
+enum OffsetReferentialDef {
+    Original,
+    Normalized,
+}
+
+fn default_offset_type() -> tk::OffsetType {
+    tk::OffsetType::Char
+}
+
+fn default_offset_referential() -> tk::OffsetReferential {
+    tk::OffsetReferential::Original
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct EncodeOptions {
@@ -217,12 +240,18 @@ struct EncodeOptions {
     is_pretokenized: bool,
     #[serde(default)]
     add_special_tokens: bool,
+    #[serde(with = "OffsetTypeDef", default = "default_offset_type")]
+    offset_type: tk::OffsetType,
+    #[serde(with = "OffsetReferentialDef", default = "default_offset_referential")]
+    offset_referential: tk::OffsetReferential,
 }
 impl Default for EncodeOptions {
     fn default() -> Self {
         Self {
             is_pretokenized: false,
             add_special_tokens: true,
+            offset_type: default_offset_type(),
+            offset_referential: default_offset_referential(),
         }
     }
 }
@@ -256,8 +285,22 @@ pub enum TruncationStrategyDef {
     LongestFirst,
     OnlyFirst,
     OnlySecond,
+    OnlySequence(usize),
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "tk::TruncationDirection", rename_all = "camelCase")]
+pub enum TruncationDirectionDef {
+    Left,
+    Right,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TruncationDirection(
+    #[serde(with = "TruncationDirectionDef")] pub tk::TruncationDirection,
+);
+
 #[derive(Serialize, Deserialize)]
 #[serde(
     remote = "tk::TruncationParams",
@@ -269,6 +312,8 @@ pub struct TruncationParamsDef {
     #[serde(with = "TruncationStrategyDef")]
     strategy: tk::TruncationStrategy,
     stride: usize,
+    #[serde(with = "TruncationDirectionDef")]
+    direction: tk::TruncationDirection,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -493,7 +538,11 @@ declare_types! {
             let guard = cx.lock();
 
             let task = EncodeTask::Single(
-                this.borrow(&guard).clone(), Some(input), options.add_special_tokens
+                this.borrow(&guard).clone(),
+                Some(input),
+                options.add_special_tokens,
+                options.offset_referential,
+                options.offset_type,
             );
             task.schedule(callback);
 
@@ -546,7 +595,11 @@ declare_types! {
             let guard = cx.lock();
 
             let task = EncodeTask::Batch(
-                this.borrow(&guard).clone(), Some(inputs), options.add_special_tokens
+                this.borrow(&guard).clone(),
+                Some(inputs),
+                options.add_special_tokens,
+                options.offset_referential,
+                options.offset_type,
             );
             task.schedule(callback);
 
@@ -662,7 +715,7 @@ declare_types! {
         method setTruncation(mut cx) {
             // setTruncation(
             //   maxLength: number,
-            //   options?: { stride?: number; strategy?: string }
+            //   options?: { stride?: number; strategy?: string; direction?: string }
             // )
 
             let max_length = cx.extract::<usize>(0)?;
@@ -7,7 +7,9 @@ use neon::prelude::*;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use tk::models::{bpe::BpeTrainer, wordpiece::WordPieceTrainer, TrainerWrapper};
+use tk::models::{
+    bpe::BpeTrainer, wordlevel::WordLevelTrainer, wordpiece::WordPieceTrainer, TrainerWrapper,
+};
 
 /// Trainer
 #[derive(Clone)]
@@ -236,9 +238,84 @@ fn wordpiece_trainer(mut cx: FunctionContext) -> JsResult<JsTrainer> {
     Ok(js_trainer)
 }
 
+// WordLevel
+
+struct WordLevelTrainerOptions(WordLevelTrainer);
+impl From<WordLevelTrainerOptions> for WordLevelTrainer {
+    fn from(v: WordLevelTrainerOptions) -> Self {
+        v.0
+    }
+}
+impl FromJsValue for WordLevelTrainerOptions {
+    fn from_value<'c, C: Context<'c>>(from: Handle<'c, JsValue>, cx: &mut C) -> LibResult<Self> {
+        if let Ok(options) = from.downcast::<JsObject>() {
+            let mut builder = WordLevelTrainer::builder();
+
+            if let Ok(size) = options.get(cx, "vocabSize") {
+                if let Some(size) = Option::from_value(size, cx)? {
+                    builder.vocab_size(size);
+                }
+            }
+            if let Ok(freq) = options.get(cx, "minFrequency") {
+                if let Some(freq) = Option::from_value(freq, cx)? {
+                    builder.min_frequency(freq);
+                }
+            }
+            if let Ok(tokens) = options.get(cx, "specialTokens") {
+                if tokens.downcast::<JsNull>().is_err() && tokens.downcast::<JsUndefined>().is_err()
+                {
+                    builder.special_tokens(
+                        tokens
+                            .downcast::<JsArray>()
+                            .map_err(|e| Error(format!("{}", e)))?
+                            .to_vec(cx)?
+                            .into_iter()
+                            .map(|token| Ok(AddedToken::from_value(token, cx)?.into()))
+                            .collect::<Result<Vec<_>, Error>>()?,
+                    );
+                }
+            }
+            if let Ok(show) = options.get(cx, "showProgress") {
+                if let Some(show) = Option::from_value(show, cx)? {
+                    builder.show_progress(show);
+                }
+            }
+            if let Ok(token) = options.get(cx, "unkToken") {
+                if let Some(token) = Option::from_value(token, cx)? {
+                    builder.unk_token(token);
+                }
+            }
+
+            Ok(Self(builder.build().map_err(|e| Error(e.to_string()))?))
+        } else {
+            Err(Error("Expected options type: object".into()))
+        }
+    }
+}
+
+/// word_level_trainer(options?: {
+///   vocabSize?: number = 30000,
+///   minFrequency?: number = 0,
+///   specialTokens?: string[] = [],
+///   showProgress?: bool = true,
+///   unkToken?: string = "<unk>",
+/// })
+fn word_level_trainer(mut cx: FunctionContext) -> JsResult<JsTrainer> {
+    let trainer = cx
+        .extract_opt::<WordLevelTrainerOptions>(0)?
+        .map_or_else(WordLevelTrainer::default, |o| o.into());
+
+    let mut js_trainer = JsTrainer::new::<_, JsTrainer, _>(&mut cx, vec![])?;
+    let guard = cx.lock();
+    js_trainer.borrow_mut(&guard).trainer = Some(Arc::new(trainer.into()));
+
+    Ok(js_trainer)
+}
+
 /// Register everything here
 pub fn register(m: &mut ModuleContext, prefix: &str) -> NeonResult<()> {
     m.export_function(&format!("{}_BPETrainer", prefix), bpe_trainer)?;
     m.export_function(&format!("{}_WordPieceTrainer", prefix), wordpiece_trainer)?;
+    m.export_function(&format!("{}_WordLevelTrainer", prefix), word_level_trainer)?;
     Ok(())
 }
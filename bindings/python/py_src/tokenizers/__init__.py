@@ -25,6 +25,7 @@ class OffsetReferential(Enum):
 class OffsetType(Enum):
     BYTE = "byte"
     CHAR = "char"
+    UTF16 = "utf16"
 
 
 class SplitDelimiterBehavior(Enum):
@@ -35,6 +35,7 @@ class OffsetReferential(Enum):
 class OffsetType(Enum):
     BYTE = "byte"
     CHAR = "char"
+    UTF16 = "utf16"
 
 class SplitDelimiterBehavior(Enum):
     REMOVED = "removed"
@@ -273,6 +274,10 @@ class Encoding:
         """
         pass
     @property
+    def n_sequences(self) -> int:
+        """ The number of input sequences represented by this Encoding """
+        pass
+    @property
     def ids(self) -> List[int]:
         """ The tokenized ids """
         pass
@@ -305,10 +310,16 @@ class Encoding:
         """ The attention mask """
         pass
     @property
+    def sequence_ids(self) -> List[Optional[int]]:
+        """ The index of the input sequence of each token, None for the special tokens """
+        pass
+    @property
     def overflowing(self) -> Optional[Encoding]:
         """ The overflowing encoding, after truncation """
         pass
-    def word_to_tokens(self, word_index: int) -> Optional[Tuple[int, int]]:
+    def word_to_tokens(
+        self, word_index: int, sequence_index: Optional[int] = None
+    ) -> Optional[Tuple[int, int]]:
         """
         Get the encoded tokens corresponding to the word at the given index in the input
         sequence, with the form [start_token, end_token + 1]
@@ -316,18 +327,26 @@ class Encoding:
         Args:
             word_index: int:
                 The index of the word in the input sequence.
+            sequence_index: (`optional`) int:
+                The index of the input sequence in which to look. By default, the whole
+                Encoding is used.
 
         Returns:
             The range of tokens with the form [start_token, end_token + 1]
         """
         pass
-    def word_to_chars(self, word_index: int) -> Optional[Offsets]:
+    def word_to_chars(
+        self, word_index: int, sequence_index: Optional[int] = None
+    ) -> Optional[Offsets]:
         """
         Get the offsets of the word at the given index in the input sequence.
 
         Args:
             word_index: int:
                 The index of the word in the input sequence.
+            sequence_index: (`optional`) int:
+                The index of the input sequence in which to look. By default, the whole
+                Encoding is used.
 
         Returns:
             The word offsets
@@ -357,25 +376,43 @@ class Encoding:
             The index of the word in the input sequence.
         """
         pass
-    def char_to_token(self, pos: int) -> Optional[int]:
+    def token_to_sequence(self, token_index: int) -> Optional[int]:
+        """
+        Get the index of the input sequence that contains the token at the given index
+
+        Args:
+            token_index: int:
+                The index of the token in the encoded sequence.
+
+        Returns:
+            The index of the input sequence, or None for the special tokens
+        """
+        pass
+    def char_to_token(self, pos: int, sequence_index: Optional[int] = None) -> Optional[int]:
         """
         Get the token that contains the char at the given position
 
         Args:
             pos: int:
                 The position of a char in the input string
+            sequence_index: (`optional`) int:
+                The index of the input sequence in which to look. By default, the whole
+                Encoding is used.
 
         Returns:
             The index of the token that contains this char
         """
         pass
-    def char_to_word(self, pos: int) -> Optional[int]:
+    def char_to_word(self, pos: int, sequence_index: Optional[int] = None) -> Optional[int]:
         """
         Get the word that contains the given char.
 
         Args:
             pos: int:
                 The position of a char in the input string
+            sequence_index: (`optional`) int:
+                The index of the input sequence in which to look. By default, the whole
+                Encoding is used.
 
         Returns:
             The index of the word that contains this char
@@ -408,7 +445,9 @@ class Encoding:
                 The pad token to be used when padding
         """
         pass
-    def truncate(self, max_length: int, stride: Optional[int] = 0):
+    def truncate(
+        self, max_length: int, stride: Optional[int] = 0, direction: Optional[str] = "right"
+    ):
         """Truncate the current Encoding at the given max_length
 
         Args:
@@ -418,6 +457,9 @@ class Encoding:
             stride: (`optional`) unsigned int:
                 The length of the previous first sequence to be included
                 in the overflowing sequence
+
+            direction: (`optional`) str:
+                Can be one of `right` or `left`, the side from which tokens are removed
         """
         pass
 
@@ -517,6 +559,26 @@ class Tokenizer:
         """
         pass
     @staticmethod
+    def from_sentencepiece_file(
+        path: str, add_bos: bool = False, add_eos: bool = False
+    ) -> Tokenizer:
+        """Instantiate a new Tokenizer from a SentencePiece `.model` file
+
+        Args:
+            path: str:
+                Path to a SentencePiece `.model` file
+
+            add_bos: bool:
+                Whether to add the beginning of sentence piece to each sequence
+
+            add_eos: bool:
+                Whether to add the end of sentence piece to each sequence
+
+        Returns:
+            Tokenizer
+        """
+        pass
+    @staticmethod
     def from_buffer(buffer: bytes) -> Tokenizer:
         """Instantiate a new Tokenizer from the given buffer
 
@@ -614,7 +676,13 @@ class Tokenizer:
             The size of the vocabulary
         """
         pass
-    def enable_truncation(self, max_length: int, stride: Optional[int], strategy: Optional[str]):
+    def enable_truncation(
+        self,
+        max_length: int,
+        stride: Optional[int],
+        strategy: Optional[Union[str, int]],
+        direction: Optional[str] = "right",
+    ):
         """Enable the truncation
 
         Args:
@@ -625,8 +693,12 @@ class Tokenizer:
                 The length of the previous first sequence to be included
                 in the overflowing sequence
 
-            strategy: (`optional) str:
-                Can be one of `longest_first`, `only_first` or `only_second`
+            strategy: (`optional) Union[str, int]:
+                Can be one of `longest_first`, `only_first` or `only_second`, or the index
+                of the only sequence to truncate
+
+            direction: (`optional`) str:
+                Can be one of `right` or `left`, the side from which tokens are removed
         """
         pass
     def no_truncation(self):
@@ -693,6 +765,8 @@ class Tokenizer:
         pair: Optional[InputSequence],
         is_pretokenized: bool = False,
         add_special_tokens: bool = True,
+        offset_type: OffsetType = OffsetType.CHAR,
+        offset_referential: OffsetReferential = OffsetReferential.ORIGINAL,
     ) -> Encoding:
         """Encode the given sequence and pair. This method can process raw text sequences as well
         as already pre-tokenized sequences.
@@ -712,6 +786,13 @@ class Tokenizer:
             add_special_tokens: bool:
                 Whether to add the special tokens while encoding.
 
+            offset_type: OffsetType:
+                Whether the offsets are expressed in bytes, chars or UTF-16 code units.
+
+            offset_referential: OffsetReferential:
+                Whether the offsets are relative to the original string, or the normalized one.
+                The overflowing encodings use the same offsets.
+
         Returns:
             An Encoding
         """
@@ -721,6 +802,8 @@ class Tokenizer:
         inputs: List[EncodeInput],
         is_pretokenized: bool = False,
         add_special_tokens: bool = True,
+        offset_type: OffsetType = OffsetType.CHAR,
+        offset_referential: OffsetReferential = OffsetReferential.ORIGINAL,
     ) -> List[Encoding]:
         """Encode the given inputs. This method accept both raw text sequences as well as already
         pre-tokenized sequences.
@@ -744,6 +827,13 @@ class Tokenizer:
             add_special_tokens: bool:
                 Whether to add the special tokens while encoding.
 
+            offset_type: OffsetType:
+                Whether the offsets are expressed in bytes, chars or UTF-16 code units.
+
+            offset_referential: OffsetReferential:
+                Whether the offsets are relative to the original string, or the normalized one.
+                The overflowing encodings use the same offsets.
+
         Returns:
             A list of Encoding
         """
@@ -778,6 +868,64 @@ class Tokenizer:
             A list of decoded strings
         """
         pass
+    def alignment_report(self, sequence: str, json: bool = False) -> str:
+        """Show how the given sequence is aligned with its normalized, pre-tokenized and
+        tokenized versions. No special tokens are added.
+
+        Args:
+            sequence: str:
+                The sequence to inspect
+
+            json: (`optional`) bool:
+                Whether to return the report as JSON instead of a text table
+
+        Returns:
+            A text table with a row per normalized char, or the JSON report with the
+            alignments of each char, and the splits with their tokens. All the offsets are
+            in bytes.
+        """
+        pass
+    def token_frequencies(self, sequences: List[str]) -> Dict[int, int]:
+        """Count how many times each token is used to encode the given sequences.
+        Neither the special tokens added by the post-processor, nor the padding are counted.
+
+        Args:
+            sequences: List[str]:
+                The sequences to encode
+
+        Returns:
+            A Dict with the number of occurrences of each token id
+        """
+        pass
+    def prune(
+        self,
+        tokens: Optional[List[str]] = None,
+        frequencies: Optional[Dict[int, int]] = None,
+        min_frequency: int = 0,
+        vocab_size: Optional[int] = None,
+    ) -> Dict[int, int]:
+        """Remove some tokens from the vocabulary of the model, and re-number the remaining
+        ones, along with the added tokens. The unknown token and the added tokens are never
+        removed. The post-processor is not updated.
+
+        Args:
+            tokens: (`optional`) List[str]:
+                Some tokens to remove
+
+            frequencies: (`optional`) Dict[int, int]:
+                The number of occurrences of each token id, as returned by `token_frequencies`
+
+            min_frequency: (`optional`) int:
+                Remove the tokens seen less than this number of times. Requires `frequencies`
+
+            vocab_size: (`optional`) int:
+                The maximum size of the vocabulary of the model, keeping the most frequent
+                tokens
+
+        Returns:
+            A Dict mapping the old ids to the new ones, for all the tokens that were kept
+        """
+        pass
     def token_to_id(self, token: str) -> Optional[int]:
         """Convert the given token to its corresponding id
 
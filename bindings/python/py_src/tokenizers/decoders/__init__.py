@@ -5,3 +5,9 @@ ByteLevel = decoders.ByteLevel
 WordPiece = decoders.WordPiece
 Metaspace = decoders.Metaspace
 BPEDecoder = decoders.BPEDecoder
+ByteFallback = decoders.ByteFallback
+Sequence = decoders.Sequence
+Replace = decoders.Replace
+Strip = decoders.Strip
+Fuse = decoders.Fuse
+CTC = decoders.CTC
@@ -1,4 +1,5 @@
-from typing import List
+from .. import Regex
+from typing import List, Union
 
 class Decoder:
     """Base class for all decoders
@@ -10,6 +11,23 @@ class Decoder:
     def decode(self, tokens: List[str]) -> str:
         """ Decode the given list of string to a final string """
         pass
+    def decode_chain(self, tokens: List[str]) -> List[str]:
+        """ Process the given list of string, and return the list given to the next decoder """
+        pass
+    @staticmethod
+    def custom(decoder) -> Decoder:
+        """ Wrap a Python object implementing `decode` """
+        pass
+    @staticmethod
+    def register(name: str, cls: type):
+        """Register the class of some custom Decoders under the given name
+
+        The Decoders made with `Decoder.custom` from the instances of this class are then saved
+        along with the tokenizer, and loaded back. Their state is saved as JSON, so it must be
+        serializable with `json`. Like with `pickle`, it is handled by `__getstate__` and
+        `__setstate__` when they are defined, or else taken from `__dict__`.
+        """
+        pass
 
 class ByteLevel(Decoder):
     """ ByteLevel Decoder """
@@ -63,3 +81,97 @@ class BPEDecoder(Decoder):
                 be replaced by whitespaces during the decoding
         """
         pass
+
+class ByteFallback(Decoder):
+    """ ByteFallback Decoder """
+
+    def __init__(self) -> None:
+        """Instantiate a new ByteFallback Decoder
+
+        ByteFallback converts tokens looking like `<0x61>` back to their byte, and
+        attempts to make them into a string. Invalid sequences of bytes are replaced
+        by the replacement character.
+        """
+        pass
+
+class Sequence(Decoder):
+    """ Sequence Decoder """
+
+    def __init__(self, decoders: List[Decoder]) -> None:
+        """Instantiate a new Sequence Decoder
+
+        Args:
+            decoders: List[Decoder]:
+                The decoders to run in order, each of them receiving the tokens produced
+                by the previous one.
+        """
+        pass
+
+class Replace(Decoder):
+    """ Replace Decoder """
+
+    def __init__(self, pattern: Union[str, Regex], content: str) -> None:
+        """Instantiate a new Replace Decoder
+
+        Args:
+            pattern: Union[str, Regex]:
+                The pattern to look for in each token
+
+            content: str:
+                The content used to replace each match
+        """
+        pass
+
+class Strip(Decoder):
+    """ Strip Decoder """
+
+    def __init__(self, content: str = " ", left: int = 0, right: int = 0) -> None:
+        """Instantiate a new Strip Decoder
+
+        Args:
+            content: str:
+                The character to strip. Must be exactly one character.
+
+            left: int:
+                The maximum number of characters to strip at the beginning of each token
+
+            right: int:
+                The maximum number of characters to strip at the end of each token
+        """
+        pass
+
+class Fuse(Decoder):
+    """ Fuse Decoder """
+
+    def __init__(self) -> None:
+        """Instantiate a new Fuse Decoder
+
+        Fuse fuses all the tokens into a single one, which lets the following decoders
+        of a Sequence work on the whole string.
+        """
+        pass
+
+class CTC(Decoder):
+    """ CTC Decoder """
+
+    def __init__(
+        self, pad_token: str = "<pad>", word_delimiter_token: str = "|", cleanup: bool = True
+    ) -> None:
+        """Instantiate a new CTC Decoder
+
+        The consecutive duplicate tokens are collapsed, then the pad token is removed and
+        the word delimiter token is replaced by a space. Decode with `skip_special_tokens=False`
+        to keep the pad tokens that separate the repeated characters.
+
+        Args:
+            pad_token: str:
+                The pad token used by CTC to delimit a new token
+
+            word_delimiter_token: str:
+                The word delimiter token. It will be replaced by a space
+
+            cleanup: bool:
+                Whether to cleanup some tokenization artifacts. Mainly spaces before punctuation,
+                and some abbreviated english forms.
+        """
+        pass
@@ -57,6 +57,9 @@ class BPE(Model):
 
        fuse_unk: (`optional`) bool:
            Multiple unk tokens get fused into only 1
+
+       byte_fallback: (`optional`) bool:
+           Use the `<0xNN>` byte tokens instead of the unk token for unknown characters
     """
 
     def __init__(
@@ -69,6 +72,7 @@ class BPE(Model):
         continuing_subword_prefix: Optional[str],
         end_of_word_suffix: Optional[str],
         fuse_unk: Optional[bool],
+        byte_fallback: Optional[bool],
     ):
         pass
     @staticmethod
@@ -164,8 +168,37 @@ class Unigram(Model):
        vocab: ('`optional`) string:
            A list of vocabulary items and their relative score [("am", -0.2442),...]
 
+       unk_id: ('`optional`) int:
+           The id of the unknown token in the vocabulary
+
+       byte_fallback: ('`optional`) bool:
+           Use the `<0xNN>` byte tokens instead of the unknown token for unknown pieces
+
+       alpha: ('`optional`) float:
+           Activates the subword regularization with the given smoothing parameter: each
+           encoding then samples a segmentation instead of using the best one
+
+       nbest_size: ('`optional`) int:
+           The number of best segmentations to sample from. A negative value means all of them
+
+       seed: ('`optional`) int:
+           A seed to make the sampling reproducible. Each occurrence of a word gets its own
+           segmentation, and encoding the same inputs in the same order gives the same ones.
+           This does not hold with `encode_batch`, which encodes the inputs in parallel
+
     """
 
     @staticmethod
-    def __init__(self, vocab: Optional[List[Tuple[str, float]]]):
+    def __init__(
+        self,
+        vocab: Optional[List[Tuple[str, float]]],
+        unk_id: Optional[int],
+        byte_fallback: Optional[bool],
+        alpha: Optional[float],
+        nbest_size: Optional[int],
+        seed: Optional[int],
+    ):
+        pass
+    def tokenize_nbest(self, sentence: str, n: int) -> List[Tuple[List[Token], float]]:
+        """ Returns the `n` best segmentations of the given sentence with their score """
         pass
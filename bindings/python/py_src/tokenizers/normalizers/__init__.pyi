@@ -14,6 +14,20 @@ class Normalizer:
     def normalize_str(self, sequence: str) -> str:
         """ Normalize the given str """
         pass
+    @staticmethod
+    def custom(normalizer) -> Normalizer:
+        """ Wrap a Python object implementing `normalize` """
+        pass
+    @staticmethod
+    def register(name: str, cls: type):
+        """Register the class of some custom Normalizers under the given name
+
+        The Normalizers made with `Normalizer.custom` from the instances of this class are then
+        saved along with the tokenizer, and loaded back. Their state is saved as JSON, so it
+        must be serializable with `json`. Like with `pickle`, it is handled by `__getstate__`
+        and `__setstate__` when they are defined, or else taken from `__dict__`.
+        """
+        pass
 
 class BertNormalizer(Normalizer):
     """BertNormalizer
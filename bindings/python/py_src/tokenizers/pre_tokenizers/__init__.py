@@ -10,3 +10,5 @@ BertPreTokenizer = pre_tokenizers.BertPreTokenizer
 Metaspace = pre_tokenizers.Metaspace
 CharDelimiterSplit = pre_tokenizers.CharDelimiterSplit
 Digits = pre_tokenizers.Digits
+Split = pre_tokenizers.Split
+UnicodeScripts = pre_tokenizers.UnicodeScripts
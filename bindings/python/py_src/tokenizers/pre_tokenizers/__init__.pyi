@@ -1,5 +1,5 @@
-from .. import PreTokenizedString
-from typing import Optional, List, Tuple
+from .. import PreTokenizedString, Regex
+from typing import Dict, Optional, List, Tuple, Union
 
 Offsets = Tuple[int, int]
 
@@ -16,6 +16,20 @@ class PreTokenizer:
     def pre_tokenize_str(self, sequence: str) -> List[Tuple[str, Offsets]]:
         """ Pre tokenize the given sequence """
         pass
+    @staticmethod
+    def custom(pre_tokenizer) -> PreTokenizer:
+        """ Wrap a Python object implementing `pre_tokenize` """
+        pass
+    @staticmethod
+    def register(name: str, cls: type):
+        """Register the class of some custom PreTokenizers under the given name
+
+        The PreTokenizers made with `PreTokenizer.custom` from the instances of this class are
+        then saved along with the tokenizer, and loaded back. Their state is saved as JSON, so
+        it must be serializable with `json`. Like with `pickle`, it is handled by `__getstate__`
+        and `__setstate__` when they are defined, or else taken from `__dict__`.
+        """
+        pass
 
 class ByteLevel(PreTokenizer):
     """ByteLevel PreTokenizer
@@ -148,3 +162,56 @@ class Digits(PreTokenizer):
 
         """
         pass
+
+class Split(PreTokenizer):
+    """Split PreTokenizer
+
+    This versatile pre-tokenizer splits using the provided pattern and
+    according to the provided behavior. The pattern can be inverted by
+    making use of the invert flag.
+    """
+
+    def __init__(
+        self, pattern: Union[str, Regex], behavior: str, invert: bool = False
+    ) -> None:
+        """Instantiate a new Split
+
+        Args:
+            pattern: Union[str, Regex]:
+                A pattern used to split the string. Usually a string or a Regex
+
+            behavior: str:
+                The behavior to use when splitting.
+                Choices: "removed", "isolated", "merged_with_previous", "merged_with_next",
+                "contiguous"
+
+            invert: bool:
+                Whether to invert the pattern. With a pattern that matches words,
+                this allows to split on anything else.
+        """
+        pass
+
+class UnicodeScripts(PreTokenizer):
+    """UnicodeScripts PreTokenizer
+
+    This pre-tokenizer splits on the boundaries between Unicode scripts. Chars with the "Any"
+    script never start a new split.
+    """
+
+    def __init__(
+        self,
+        script_mappings: Optional[Dict[str, str]] = None,
+        char_mappings: Optional[Dict[str, str]] = None,
+    ) -> None:
+        """Instantiate a new UnicodeScripts
+
+        Args:
+            script_mappings: (`optional`) Dict[str, str]:
+                The scripts to merge into another one, like `{"Hiragana": "Han"}`.
+                Defaults to Hiragana and Katakana merged into Han.
+
+            char_mappings: (`optional`) Dict[str, str]:
+                The scripts given to specific chars, like `{" ": "Any"}`.
+                Defaults to "ー" as Han, and spaces as Any.
+        """
+        pass
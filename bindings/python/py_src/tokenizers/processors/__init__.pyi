@@ -1,5 +1,5 @@
 from .. import Encoding
-from typing import Tuple, Union, List
+from typing import Optional, Tuple, Union, List
 
 class PostProcessor:
     """Base class for all post-processors
@@ -143,13 +143,22 @@ class TemplateProcessing(PostProcessor):
 
     The same construct is used for special tokens: `<identifier>(:<type_id>)?`.
 
+    When more than two sequences are provided, the `multiple` template is used. The sequences
+    after `$B` are identified with the next letters: `$C`, `$D`, ...
+
     **Warning**: You must ensure that you are giving the correct tokens/ids as these
     will be added to the Encoding without any further check. If the given ids correspond
     to something totally different in a `Tokenizer` using this `PostProcessor`, it
     might lead to unexpected results.
     """
 
-    def __init__(self, single: Template, pair: Template, special_tokens: Tokens) -> None:
+    def __init__(
+        self,
+        single: Template,
+        pair: Template,
+        special_tokens: Tokens,
+        multiple: Optional[Template] = None,
+    ) -> None:
         """Instantiate a new TemplateProcessing
 
         Args:
@@ -159,6 +168,9 @@ class TemplateProcessing(PostProcessor):
             pair: Template:
                 The template used when both sequences are specified
 
+            multiple: (`optional`) Template:
+                The template used when more than two sequences are specified
+
             special_tokens: Tokens:
                 The list of special tokens used in each sequences
 
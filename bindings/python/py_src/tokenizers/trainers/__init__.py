@@ -4,3 +4,4 @@ Trainer = trainers.Trainer
 BpeTrainer = trainers.BpeTrainer
 WordPieceTrainer = trainers.WordPieceTrainer
 UnigramTrainer = trainers.UnigramTrainer
+WordLevelTrainer = trainers.WordLevelTrainer
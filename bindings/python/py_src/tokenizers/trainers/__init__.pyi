@@ -1,4 +1,5 @@
 from .. import AddedToken
+from ..models import BPE, Unigram
 from typing import Optional, List, Union
 
 class Trainer:
@@ -24,6 +25,8 @@ class BpeTrainer(Trainer):
         initial_alphabet: List[str] = [],
         continuing_subword_prefix: Optional[str] = None,
         end_of_word_suffix: Optional[str] = None,
+        base_model: Optional[BPE] = None,
+        max_new_tokens: Optional[int] = None,
     ) -> None:
         """Instantiate a new BpeTrainer with the given options:
 
@@ -55,6 +58,15 @@ class BpeTrainer(Trainer):
             end_of_word_suffix: Optional[str]:
                 A suffix to be used for every subword that is a end-of-word.
 
+            base_model: Optional[BPE]:
+                An existing model to extend. Its tokens keep their ids and its merges keep
+                their priorities, only new tokens and merges are learned. Its
+                continuing_subword_prefix and end_of_word_suffix are used.
+
+            max_new_tokens: Optional[int]:
+                The maximum number of new tokens to add to the vocabulary, including the
+                special tokens and the new chars, of which only the most frequent are kept.
+
         Returns:
             Trainer
         """
@@ -123,6 +135,8 @@ class UnigramTrainer(Trainer):
         vocab_size: int = 8000,
         show_progress: bool = True,
         special_tokens: List[Union[str, AddedToken]] = [],
+        base_model: Optional[Unigram] = None,
+        max_new_tokens: Optional[int] = None,
     ) -> Trainer:
         """Instantiate a new UnigramTrainer with the given options:
 
@@ -136,6 +150,51 @@ class UnigramTrainer(Trainer):
             special_tokens: List[Union[str, AddedToken]]:
                 A list of special tokens the model should know of.
 
+            base_model: Optional[Unigram]:
+                An existing model to extend. Its pieces keep their ids and scores, only new
+                pieces are learned.
+
+            max_new_tokens: Optional[int]:
+                The maximum number of new pieces to add to the vocabulary, including the new
+                chars, of which only the most frequent are kept.
+
+        Returns:
+            Trainer
+        """
+        pass
+
+class WordLevelTrainer(Trainer):
+    """WordLevelTrainer
+
+    Capable of training a WordLevel model
+    """
+
+    def __init__(
+        self,
+        vocab_size: int = 30000,
+        min_frequency: int = 0,
+        show_progress: bool = True,
+        special_tokens: List[Union[str, AddedToken]] = [],
+        unk_token: str = "<unk>",
+    ) -> Trainer:
+        """Instantiate a new WordLevelTrainer with the given options:
+
+        Args:
+            vocab_size: unsigned int:
+                The size of the final vocabulary, including all tokens.
+
+            min_frequency: unsigned int:
+                The minimum frequency a word should have in order to be kept.
+
+            show_progress: boolean:
+                Whether to show progress bars while training.
+
+            special_tokens: List[Union[str, AddedToken]]:
+                A list of special tokens the model should know of.
+
+            unk_token: str:
+                The token used for unknown words. It is always added as a special token.
+
         Returns:
             Trainer
         """
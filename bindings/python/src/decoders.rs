@@ -6,14 +6,21 @@ use pyo3::types::*;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tk::decoders::bpe::BPEDecoder;
+use tk::decoders::byte_fallback::ByteFallback;
 use tk::decoders::byte_level::ByteLevel;
+use tk::decoders::ctc::CTC;
+use tk::decoders::fuse::Fuse;
 use tk::decoders::metaspace::Metaspace;
+use tk::decoders::replace::Replace;
+use tk::decoders::sequence::Sequence;
+use tk::decoders::strip::Strip;
 use tk::decoders::wordpiece::WordPiece;
 use tk::decoders::DecoderWrapper;
 use tk::Decoder;
 use tokenizers as tk;
 
 use super::error::ToPyResult;
+use super::utils::{register_class, registered_type, serialize_state, PyPattern};
 
 #[pyclass(dict, module = "tokenizers.decoders", name=Decoder)]
 #[derive(Clone, Deserialize, Serialize)]
@@ -38,6 +45,15 @@ impl PyDecoder {
                 DecoderWrapper::WordPiece(_) => Py::new(py, (PyWordPieceDec {}, base))?.into_py(py),
                 DecoderWrapper::ByteLevel(_) => Py::new(py, (PyByteLevelDec {}, base))?.into_py(py),
                 DecoderWrapper::BPE(_) => Py::new(py, (PyBPEDecoder {}, base))?.into_py(py),
+                DecoderWrapper::ByteFallback(_) => {
+                    Py::new(py, (PyByteFallbackDec {}, base))?.into_py(py)
+                }
+                DecoderWrapper::Sequence(_) => Py::new(py, (PySequenceDec {}, base))?.into_py(py),
+                DecoderWrapper::Replace(_) => Py::new(py, (PyReplaceDec {}, base))?.into_py(py),
+                DecoderWrapper::Strip(_) => Py::new(py, (PyStripDec {}, base))?.into_py(py),
+                DecoderWrapper::Fuse(_) => Py::new(py, (PyFuseDec {}, base))?.into_py(py),
+                DecoderWrapper::CTC(_) => Py::new(py, (PyCTCDecoder {}, base))?.into_py(py),
+                DecoderWrapper::Custom(_) => Py::new(py, base)?.into_py(py),
             },
         })
     }
@@ -47,16 +63,34 @@ impl Decoder for PyDecoder {
     fn decode(&self, tokens: Vec<String>) -> tk::Result<String> {
         self.decoder.decode(tokens)
     }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> tk::Result<Vec<String>> {
+        self.decoder.decode_chain(tokens)
+    }
 }
 
 #[pymethods]
 impl PyDecoder {
     #[staticmethod]
-    fn custom(decoder: PyObject) -> PyResult<Self> {
-        let decoder = PyDecoderWrapper::Custom(CustomDecoder::new(decoder).map(Arc::new)?);
+    fn custom(py: Python, decoder: PyObject) -> PyResult<Self> {
+        let decoder = match registered_type(decoder.as_ref(py))? {
+            Some(name) => PyDecoderWrapper::Wrapped(Arc::new(
+                tk::decoders::CustomDecoder::new(name, CustomDecoder::new(decoder)?).into(),
+            )),
+            None => PyDecoderWrapper::Custom(CustomDecoder::new(decoder).map(Arc::new)?),
+        };
         Ok(PyDecoder::new(decoder))
     }
 
+    /// Register the class of some custom decoders under the given name, so that they can
+    /// be saved along with the tokenizer, and loaded back
+    #[staticmethod]
+    fn register(name: &str, cls: &PyType) -> PyResult<()> {
+        register_class(&tk::decoders::REGISTRY, name, cls, |inner| CustomDecoder {
+            inner,
+        })
+    }
+
     fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
         let data = serde_json::to_string(&self.decoder).map_err(|e| {
             exceptions::PyException::new_err(format!(
@@ -85,6 +119,10 @@ impl PyDecoder {
     fn decode(&self, tokens: Vec<String>) -> PyResult<String> {
         ToPyResult(self.decoder.decode(tokens)).into()
     }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> PyResult<Vec<String>> {
+        ToPyResult(self.decoder.decode_chain(tokens)).into()
+    }
 }
 
 #[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=ByteLevel)]
@@ -176,6 +214,110 @@ impl PyBPEDecoder {
     }
 }
 
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=ByteFallback)]
+pub struct PyByteFallbackDec {}
+#[pymethods]
+impl PyByteFallbackDec {
+    #[new]
+    fn new() -> PyResult<(Self, PyDecoder)> {
+        Ok((PyByteFallbackDec {}, ByteFallback.into()))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Sequence)]
+pub struct PySequenceDec {}
+#[pymethods]
+impl PySequenceDec {
+    #[new]
+    fn new(decoders: &PyList) -> PyResult<(Self, PyDecoder)> {
+        let mut sequence = Vec::with_capacity(decoders.len());
+        for d in decoders.iter() {
+            let decoder: PyRef<PyDecoder> = d.extract()?;
+            match &decoder.decoder {
+                PyDecoderWrapper::Wrapped(inner) => sequence.push(inner.as_ref().clone()),
+                PyDecoderWrapper::Custom(_) => {
+                    return Err(exceptions::PyValueError::new_err(
+                        "Custom decoders cannot be used in a Sequence",
+                    ))
+                }
+            }
+        }
+        Ok((PySequenceDec {}, Sequence::new(sequence).into()))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &[PyList::empty(py)]))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Replace)]
+pub struct PyReplaceDec {}
+#[pymethods]
+impl PyReplaceDec {
+    #[new]
+    fn new(pattern: PyPattern, content: String) -> PyResult<(Self, PyDecoder)> {
+        Ok((
+            PyReplaceDec {},
+            ToPyResult(Replace::new(pattern, content)).into_py()?.into(),
+        ))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &["", ""]))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Strip)]
+pub struct PyStripDec {}
+#[pymethods]
+impl PyStripDec {
+    #[new]
+    #[args(content = "' '", left = "0", right = "0")]
+    fn new(content: char, left: usize, right: usize) -> PyResult<(Self, PyDecoder)> {
+        Ok((PyStripDec {}, Strip::new(content, left, right).into()))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=Fuse)]
+pub struct PyFuseDec {}
+#[pymethods]
+impl PyFuseDec {
+    #[new]
+    fn new() -> PyResult<(Self, PyDecoder)> {
+        Ok((PyFuseDec {}, Fuse.into()))
+    }
+}
+
+#[pyclass(extends=PyDecoder, module = "tokenizers.decoders", name=CTC)]
+pub struct PyCTCDecoder {}
+#[pymethods]
+impl PyCTCDecoder {
+    #[new]
+    #[args(kwargs = "**")]
+    fn new(kwargs: Option<&PyDict>) -> PyResult<(Self, PyDecoder)> {
+        let mut pad_token = String::from("<pad>");
+        let mut word_delimiter_token = String::from("|");
+        let mut cleanup = true;
+
+        if let Some(kwargs) = kwargs {
+            for (key, value) in kwargs {
+                let key: &str = key.extract()?;
+                match key {
+                    "pad_token" => pad_token = value.extract()?,
+                    "word_delimiter_token" => word_delimiter_token = value.extract()?,
+                    "cleanup" => cleanup = value.extract()?,
+                    _ => println!("Ignored unknown kwarg option {}", key),
+                }
+            }
+        }
+
+        Ok((
+            PyCTCDecoder {},
+            CTC::new(pad_token, word_delimiter_token, cleanup).into(),
+        ))
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct CustomDecoder {
     inner: PyObject,
@@ -197,16 +339,32 @@ impl Decoder for CustomDecoder {
             Ok(decoded)
         })
     }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> tk::Result<Vec<String>> {
+        Python::with_gil(|py| {
+            if self.inner.as_ref(py).hasattr("decode_chain")? {
+                let decoded = self
+                    .inner
+                    .call_method(py, "decode_chain", (tokens,), None)?
+                    .extract::<Vec<String>>(py)?;
+                Ok(decoded)
+            } else {
+                let decoded = self
+                    .inner
+                    .call_method(py, "decode", (tokens,), None)?
+                    .extract::<String>(py)?;
+                Ok(vec![decoded])
+            }
+        })
+    }
 }
 
 impl Serialize for CustomDecoder {
-    fn serialize<S>(&self, _serializer: S) -> std::result::Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        Err(serde::ser::Error::custom(
-            "Custom PyDecoder cannot be serialized",
-        ))
+        serialize_state(&self.inner, serializer)
     }
 }
 
@@ -219,13 +377,28 @@ impl<'de> Deserialize<'de> for CustomDecoder {
     }
 }
 
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Deserialize)]
 #[serde(untagged)]
 pub(crate) enum PyDecoderWrapper {
     Custom(Arc<CustomDecoder>),
     Wrapped(Arc<DecoderWrapper>),
 }
 
+impl Serialize for PyDecoderWrapper {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            PyDecoderWrapper::Wrapped(inner) => inner.serialize(serializer),
+            PyDecoderWrapper::Custom(_) => Err(serde::ser::Error::custom(
+                "Custom PyDecoder cannot be serialized, its class must be registered with \
+                 `Decoder.register` first",
+            )),
+        }
+    }
+}
+
 impl<I> From<I> for PyDecoderWrapper
 where
     I: Into<DecoderWrapper>,
@@ -253,6 +426,13 @@ impl Decoder for PyDecoderWrapper {
             PyDecoderWrapper::Custom(inner) => inner.decode(tokens),
         }
     }
+
+    fn decode_chain(&self, tokens: Vec<String>) -> tk::Result<Vec<String>> {
+        match self {
+            PyDecoderWrapper::Wrapped(inner) => inner.decode_chain(tokens),
+            PyDecoderWrapper::Custom(inner) => inner.decode_chain(tokens),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@ use pyo3::exceptions;
 use pyo3::prelude::*;
 use pyo3::types::*;
 use pyo3::{PyObjectProtocol, PySequenceProtocol};
-use tk::tokenizer::{Offsets, PaddingDirection};
+use tk::tokenizer::{Offsets, PaddingDirection, TruncationDirection};
 use tokenizers as tk;
 
 use crate::error::PyError;
@@ -81,6 +81,11 @@ impl PyEncoding {
         .into()
     }
 
+    #[getter]
+    fn get_n_sequences(&self) -> usize {
+        self.encoding.n_sequences()
+    }
+
     #[getter]
     fn get_ids(&self) -> Vec<u32> {
         self.encoding.get_ids().to_vec()
@@ -116,6 +121,11 @@ impl PyEncoding {
         self.encoding.get_attention_mask().to_vec()
     }
 
+    #[getter]
+    fn get_sequence_ids(&self) -> Vec<Option<usize>> {
+        self.encoding.sequence_ids()
+    }
+
     #[getter]
     fn get_overflowing(&self) -> Vec<PyEncoding> {
         self.encoding
@@ -126,12 +136,24 @@ impl PyEncoding {
             .collect()
     }
 
-    fn word_to_tokens(&self, word_index: u32) -> Option<(usize, usize)> {
-        self.encoding.word_to_tokens(word_index)
+    #[args(sequence_index = "None")]
+    fn word_to_tokens(
+        &self,
+        word_index: u32,
+        sequence_index: Option<usize>,
+    ) -> Option<(usize, usize)> {
+        match sequence_index {
+            Some(seq) => self.encoding.word_to_tokens_in_sequence(word_index, seq),
+            None => self.encoding.word_to_tokens(word_index),
+        }
     }
 
-    fn word_to_chars(&self, word_index: u32) -> Option<Offsets> {
-        self.encoding.word_to_chars(word_index)
+    #[args(sequence_index = "None")]
+    fn word_to_chars(&self, word_index: u32, sequence_index: Option<usize>) -> Option<Offsets> {
+        match sequence_index {
+            Some(seq) => self.encoding.word_to_chars_in_sequence(word_index, seq),
+            None => self.encoding.word_to_chars(word_index),
+        }
     }
 
     fn token_to_chars(&self, token_index: usize) -> Option<Offsets> {
@@ -142,12 +164,24 @@ impl PyEncoding {
         self.encoding.token_to_word(token_index)
     }
 
-    fn char_to_token(&self, char_pos: usize) -> Option<usize> {
-        self.encoding.char_to_token(char_pos)
+    fn token_to_sequence(&self, token_index: usize) -> Option<usize> {
+        self.encoding.token_to_sequence(token_index)
+    }
+
+    #[args(sequence_index = "None")]
+    fn char_to_token(&self, char_pos: usize, sequence_index: Option<usize>) -> Option<usize> {
+        match sequence_index {
+            Some(seq) => self.encoding.char_to_token_in_sequence(char_pos, seq),
+            None => self.encoding.char_to_token(char_pos),
+        }
     }
 
-    fn char_to_word(&self, char_pos: usize) -> Option<u32> {
-        self.encoding.char_to_word(char_pos)
+    #[args(sequence_index = "None")]
+    fn char_to_word(&self, char_pos: usize, sequence_index: Option<usize>) -> Option<u32> {
+        match sequence_index {
+            Some(seq) => self.encoding.char_to_word_in_sequence(char_pos, seq),
+            None => self.encoding.char_to_word(char_pos),
+        }
     }
 
     #[args(kwargs = "**")]
@@ -189,17 +223,31 @@ impl PyEncoding {
     #[args(kwargs = "**")]
     fn truncate(&mut self, max_length: usize, kwargs: Option<&PyDict>) -> PyResult<()> {
         let mut stride = 0;
+        let mut direction = TruncationDirection::Right;
 
         if let Some(kwargs) = kwargs {
             for (key, value) in kwargs {
                 let key: &str = key.extract()?;
                 match key {
                     "stride" => stride = value.extract()?,
+                    "direction" => {
+                        let value: &str = value.extract()?;
+                        direction = match value {
+                            "left" => Ok(TruncationDirection::Left),
+                            "right" => Ok(TruncationDirection::Right),
+                            other => Err(PyError(format!(
+                                "Unknown `direction`: `{}`. Use \
+                                 one of `left` or `right`",
+                                other
+                            ))
+                            .into_pyerr::<exceptions::PyValueError>()),
+                        }?;
+                    }
                     _ => println!("Ignored unknown kwarg option {}", key),
                 }
             }
         }
-        self.encoding.truncate(max_length, stride);
+        self.encoding.truncate(max_length, stride, direction);
         Ok(())
     }
 }
@@ -45,6 +45,7 @@ fn trainers(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<trainers::PyBpeTrainer>()?;
     m.add_class::<trainers::PyWordPieceTrainer>()?;
     m.add_class::<trainers::PyUnigramTrainer>()?;
+    m.add_class::<trainers::PyWordLevelTrainer>()?;
     Ok(())
 }
 
@@ -72,6 +73,8 @@ fn pre_tokenizers(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<pre_tokenizers::PyPunctuation>()?;
     m.add_class::<pre_tokenizers::PySequence>()?;
     m.add_class::<pre_tokenizers::PyDigits>()?;
+    m.add_class::<pre_tokenizers::PyUnicodeScripts>()?;
+    m.add_class::<pre_tokenizers::PySplit>()?;
     Ok(())
 }
 
@@ -83,6 +86,12 @@ fn decoders(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<decoders::PyWordPieceDec>()?;
     m.add_class::<decoders::PyMetaspaceDec>()?;
     m.add_class::<decoders::PyBPEDecoder>()?;
+    m.add_class::<decoders::PyByteFallbackDec>()?;
+    m.add_class::<decoders::PySequenceDec>()?;
+    m.add_class::<decoders::PyReplaceDec>()?;
+    m.add_class::<decoders::PyStripDec>()?;
+    m.add_class::<decoders::PyFuseDec>()?;
+    m.add_class::<decoders::PyCTCDecoder>()?;
     Ok(())
 }
 
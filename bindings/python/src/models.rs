@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -8,7 +8,7 @@ use pyo3::prelude::*;
 use pyo3::types::*;
 use serde::{Deserialize, Serialize};
 use tk::models::bpe::{BpeBuilder, Merges, Vocab, BPE};
-use tk::models::unigram::Unigram;
+use tk::models::unigram::{Unigram, UnigramSampling};
 use tk::models::wordlevel::WordLevel;
 use tk::models::wordpiece::{WordPiece, WordPieceBuilder};
 use tk::models::ModelWrapper;
@@ -40,6 +40,7 @@ impl PyModel {
             ModelWrapper::WordPiece(_) => Py::new(py, (PyWordPiece {}, base))?.into_py(py),
             ModelWrapper::WordLevel(_) => Py::new(py, (PyWordLevel {}, base))?.into_py(py),
             ModelWrapper::Unigram(_) => Py::new(py, (PyUnigram {}, base))?.into_py(py),
+            ModelWrapper::Custom(_) => Py::new(py, base)?.into_py(py),
         })
     }
 }
@@ -65,9 +66,17 @@ impl Model for PyModel {
         self.model.get_vocab_size()
     }
 
+    fn unk_token(&self) -> Option<&str> {
+        self.model.unk_token()
+    }
+
     fn save(&self, folder: &Path, name: Option<&str>) -> tk::Result<Vec<PathBuf>> {
         self.model.save(folder, name)
     }
+
+    fn prune(&mut self, ids: &HashSet<u32>) -> tk::Result<HashMap<u32, u32>> {
+        Arc::make_mut(&mut self.model).prune(ids)
+    }
 }
 
 #[pymethods]
@@ -159,6 +168,7 @@ impl PyBPE {
                     }
                     "end_of_word_suffix" => builder = builder.end_of_word_suffix(value.extract()?),
                     "fuse_unk" => builder = builder.fuse_unk(value.extract()?),
+                    "byte_fallback" => builder = builder.byte_fallback(value.extract()?),
                     _ => println!("Ignored unknown kwarg option {}", key),
                 };
             }
@@ -415,13 +425,32 @@ pub struct PyUnigram {}
 #[pymethods]
 impl PyUnigram {
     #[new]
-    fn new(vocab: Option<Vec<(String, f64)>>, unk_id: Option<usize>) -> PyResult<(Self, PyModel)> {
+    #[args(
+        byte_fallback = false,
+        alpha = "None",
+        nbest_size = "-1",
+        seed = "None"
+    )]
+    fn new(
+        vocab: Option<Vec<(String, f64)>>,
+        unk_id: Option<usize>,
+        byte_fallback: bool,
+        alpha: Option<f64>,
+        nbest_size: i32,
+        seed: Option<u64>,
+    ) -> PyResult<(Self, PyModel)> {
         if vocab.is_some() && unk_id.is_none() || vocab.is_none() && unk_id.is_some() {}
         match (vocab, unk_id) {
             (Some(vocab), Some(unk_id)) => {
-                let model = Unigram::from(vocab, unk_id).map_err(|e| {
+                let mut model = Unigram::from(vocab, unk_id).map_err(|e| {
                     exceptions::PyException::new_err(format!("Error while loading Unigram: {}", e))
                 })?;
+                model.set_byte_fallback(byte_fallback);
+                model.set_sampling(alpha.map(|alpha| UnigramSampling {
+                    alpha,
+                    nbest_size,
+                    seed,
+                }));
                 Ok((PyUnigram {}, PyModel::new(Arc::new(model.into()))))
             }
             (None, None) => Ok((
@@ -433,6 +462,22 @@ impl PyUnigram {
             )),
         }
     }
+
+    fn tokenize_nbest(
+        self_: PyRef<Self>,
+        sentence: &str,
+        n: usize,
+    ) -> PyResult<Vec<(Vec<PyToken>, f64)>> {
+        let base: &PyModel = self_.as_ref();
+        match base.model.as_ref() {
+            ModelWrapper::Unigram(model) => Ok(ToPyResult(model.tokenize_nbest(sentence, n))
+                .into_py()?
+                .into_iter()
+                .map(|(tokens, score)| (tokens.into_iter().map(|t| t.into()).collect(), score))
+                .collect()),
+            _ => unreachable!(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -5,7 +5,10 @@ use pyo3::prelude::*;
 use pyo3::types::*;
 
 use crate::error::ToPyResult;
-use crate::utils::{PyNormalizedString, PyNormalizedStringRefMut, PyPattern};
+use crate::utils::{
+    register_class, registered_type, serialize_state, PyNormalizedString, PyNormalizedStringRefMut,
+    PyPattern,
+};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tk::normalizers::{
@@ -59,6 +62,7 @@ impl PyNormalizer {
                     }
                     NormalizerWrapper::Replace(_) => Py::new(py, (PyReplace {}, base))?.into_py(py),
                     NormalizerWrapper::Nmt(_) => Py::new(py, (PyNmt {}, base))?.into_py(py),
+                    NormalizerWrapper::Custom(_) => Py::new(py, base)?.into_py(py),
                 },
             },
         })
@@ -74,12 +78,25 @@ impl Normalizer for PyNormalizer {
 #[pymethods]
 impl PyNormalizer {
     #[staticmethod]
-    fn custom(obj: PyObject) -> PyResult<Self> {
+    fn custom(py: Python, obj: PyObject) -> PyResult<Self> {
+        let normalizer = match registered_type(obj.as_ref(py))? {
+            Some(name) => PyNormalizerWrapper::Wrapped(
+                tk::normalizers::CustomNormalizer::new(name, CustomNormalizer::new(obj)).into(),
+            ),
+            None => PyNormalizerWrapper::Custom(CustomNormalizer::new(obj)),
+        };
         Ok(Self {
-            normalizer: PyNormalizerWrapper::Custom(CustomNormalizer::new(obj)).into(),
+            normalizer: normalizer.into(),
         })
     }
 
+    /// Register the class of some custom normalizers under the given name, so that they
+    /// can be saved along with the tokenizer, and loaded back
+    #[staticmethod]
+    fn register(name: &str, cls: &PyType) -> PyResult<()> {
+        register_class(&tk::normalizers::REGISTRY, name, cls, CustomNormalizer::new)
+    }
+
     fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
         let data = serde_json::to_string(&self.normalizer).map_err(|e| {
             exceptions::PyException::new_err(format!(
@@ -276,13 +293,11 @@ impl tk::tokenizer::Normalizer for CustomNormalizer {
 }
 
 impl Serialize for CustomNormalizer {
-    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        Err(serde::ser::Error::custom(
-            "Custom Normalizer cannot be serialized",
-        ))
+        serialize_state(&self.inner, serializer)
     }
 }
 
@@ -311,7 +326,10 @@ impl Serialize for PyNormalizerWrapper {
     {
         match self {
             PyNormalizerWrapper::Wrapped(inner) => inner.serialize(serializer),
-            PyNormalizerWrapper::Custom(inner) => inner.serialize(serializer),
+            PyNormalizerWrapper::Custom(_) => Err(serde::ser::Error::custom(
+                "Custom Normalizer cannot be serialized, its class must be registered with \
+                 `Normalizer.register` first",
+            )),
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use pyo3::exceptions;
@@ -5,6 +6,7 @@ use pyo3::prelude::*;
 use pyo3::types::*;
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::json;
 
 use tk::pre_tokenizers::bert::BertPreTokenizer;
 use tk::pre_tokenizers::byte_level::ByteLevel;
@@ -12,6 +14,8 @@ use tk::pre_tokenizers::delimiter::CharDelimiterSplit;
 use tk::pre_tokenizers::digits::Digits;
 use tk::pre_tokenizers::metaspace::Metaspace;
 use tk::pre_tokenizers::punctuation::Punctuation;
+use tk::pre_tokenizers::split::Split;
+use tk::pre_tokenizers::unicode_scripts::UnicodeScripts;
 use tk::pre_tokenizers::whitespace::{Whitespace, WhitespaceSplit};
 use tk::pre_tokenizers::PreTokenizerWrapper;
 use tk::tokenizer::Offsets;
@@ -70,6 +74,11 @@ impl PyPreTokenizer {
                         Py::new(py, (PyBertPreTokenizer {}, base))?.into_py(py)
                     }
                     PreTokenizerWrapper::Digits(_) => Py::new(py, (PyDigits {}, base))?.into_py(py),
+                    PreTokenizerWrapper::Split(_) => Py::new(py, (PySplit {}, base))?.into_py(py),
+                    PreTokenizerWrapper::UnicodeScripts(_) => {
+                        Py::new(py, (PyUnicodeScripts {}, base))?.into_py(py)
+                    }
+                    PreTokenizerWrapper::Custom(_) => Py::new(py, base)?.into_py(py),
                 },
             },
         })
@@ -85,12 +94,31 @@ impl PreTokenizer for PyPreTokenizer {
 #[pymethods]
 impl PyPreTokenizer {
     #[staticmethod]
-    fn custom(pretok: PyObject) -> PyResult<Self> {
+    fn custom(py: Python, pretok: PyObject) -> PyResult<Self> {
+        let pretok = match registered_type(pretok.as_ref(py))? {
+            Some(name) => PyPreTokenizerWrapper::Wrapped(
+                tk::pre_tokenizers::CustomPreTokenizer::new(name, CustomPreTokenizer::new(pretok))
+                    .into(),
+            ),
+            None => PyPreTokenizerWrapper::Custom(CustomPreTokenizer::new(pretok)),
+        };
         Ok(PyPreTokenizer {
-            pretok: PyPreTokenizerWrapper::Custom(CustomPreTokenizer::new(pretok)).into(),
+            pretok: pretok.into(),
         })
     }
 
+    /// Register the class of some custom pre-tokenizers under the given name, so that they
+    /// can be saved along with the tokenizer, and loaded back
+    #[staticmethod]
+    fn register(name: &str, cls: &PyType) -> PyResult<()> {
+        register_class(
+            &tk::pre_tokenizers::REGISTRY,
+            name,
+            cls,
+            CustomPreTokenizer::new,
+        )
+    }
+
     fn __getstate__(&self, py: Python) -> PyResult<PyObject> {
         let data = serde_json::to_string(&self.pretok).map_err(|e| {
             exceptions::PyException::new_err(format!(
@@ -186,6 +214,30 @@ impl PyWhitespaceSplit {
     }
 }
 
+#[pyclass(extends=PyPreTokenizer, module = "tokenizers.pre_tokenizers", name=Split)]
+pub struct PySplit {}
+#[pymethods]
+impl PySplit {
+    #[new]
+    #[args(invert = false)]
+    fn new(
+        pattern: PyPattern,
+        behavior: PySplitDelimiterBehavior,
+        invert: bool,
+    ) -> PyResult<(Self, PyPreTokenizer)> {
+        Ok((
+            PySplit {},
+            ToPyResult(Split::new(pattern, behavior.into(), invert))
+                .into_py()?
+                .into(),
+        ))
+    }
+
+    fn __getnewargs__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyTuple> {
+        Ok(PyTuple::new(py, &[" ", "removed"]))
+    }
+}
+
 #[pyclass(extends=PyPreTokenizer, module = "tokenizers.pre_tokenizers", name=CharDelimiterSplit)]
 pub struct PyCharDelimiterSplit {}
 #[pymethods]
@@ -297,6 +349,33 @@ impl PyDigits {
     }
 }
 
+#[pyclass(extends=PyPreTokenizer, module = "tokenizers.pre_tokenizers", name=UnicodeScripts)]
+pub struct PyUnicodeScripts {}
+#[pymethods]
+impl PyUnicodeScripts {
+    #[new]
+    #[args(script_mappings = "None", char_mappings = "None")]
+    fn new(
+        script_mappings: Option<HashMap<String, String>>,
+        char_mappings: Option<HashMap<String, String>>,
+    ) -> PyResult<(Self, PyPreTokenizer)> {
+        let mappings_err = |e: serde_json::Error| {
+            exceptions::PyValueError::new_err(format!("Invalid mappings: {}", e))
+        };
+
+        let mut unicode_scripts = UnicodeScripts::default();
+        if let Some(mappings) = script_mappings {
+            unicode_scripts = unicode_scripts
+                .script_mappings(serde_json::from_value(json!(mappings)).map_err(mappings_err)?);
+        }
+        if let Some(mappings) = char_mappings {
+            unicode_scripts = unicode_scripts
+                .char_mappings(serde_json::from_value(json!(mappings)).map_err(mappings_err)?);
+        }
+        Ok((PyUnicodeScripts {}, unicode_scripts.into()))
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct CustomPreTokenizer {
     inner: PyObject,
@@ -320,13 +399,11 @@ impl tk::tokenizer::PreTokenizer for CustomPreTokenizer {
 }
 
 impl Serialize for CustomPreTokenizer {
-    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        Err(serde::ser::Error::custom(
-            "Custom PreTokenizer cannot be serialized",
-        ))
+        serialize_state(&self.inner, serializer)
     }
 }
 
@@ -355,7 +432,10 @@ impl Serialize for PyPreTokenizerWrapper {
     {
         match self {
             PyPreTokenizerWrapper::Wrapped(inner) => inner.serialize(serializer),
-            PyPreTokenizerWrapper::Custom(inner) => inner.serialize(serializer),
+            PyPreTokenizerWrapper::Custom(_) => Err(serde::ser::Error::custom(
+                "Custom PreTokenizer cannot be serialized, its class must be registered with \
+                 `PreTokenizer.register` first",
+            )),
         }
     }
 }
@@ -41,6 +41,7 @@ impl PyPostProcessor {
             PostProcessorWrapper::Template(_) => {
                 Py::new(py, (PyTemplateProcessing {}, base))?.into_py(py)
             }
+            PostProcessorWrapper::Custom(_) => Py::new(py, base)?.into_py(py),
         })
     }
 }
@@ -50,6 +51,10 @@ impl PostProcessor for PyPostProcessor {
         self.processor.added_tokens(is_pair)
     }
 
+    fn added_tokens_for_sequences(&self, n_sequences: usize) -> usize {
+        self.processor.added_tokens_for_sequences(n_sequences)
+    }
+
     fn process(
         &self,
         encoding: Encoding,
@@ -59,6 +64,15 @@ impl PostProcessor for PyPostProcessor {
         self.processor
             .process(encoding, pair_encoding, add_special_tokens)
     }
+
+    fn process_sequences(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> tk::Result<Encoding> {
+        self.processor
+            .process_sequences(encodings, add_special_tokens)
+    }
 }
 
 #[pymethods]
@@ -249,10 +263,16 @@ pub struct PyTemplateProcessing {}
 #[pymethods]
 impl PyTemplateProcessing {
     #[new]
-    #[args(single = "None", pair = "None", special_tokens = "None")]
+    #[args(
+        single = "None",
+        pair = "None",
+        multiple = "None",
+        special_tokens = "None"
+    )]
     fn new(
         single: Option<PyTemplate>,
         pair: Option<PyTemplate>,
+        multiple: Option<PyTemplate>,
         special_tokens: Option<Vec<PySpecialToken>>,
     ) -> PyResult<(Self, PyPostProcessor)> {
         let mut builder = tk::processors::template::TemplateProcessing::builder();
@@ -263,6 +283,9 @@ impl PyTemplateProcessing {
         if let Some(seq) = pair {
             builder.pair(seq.into());
         }
+        if let Some(seq) = multiple {
+            builder.multiple(seq.into());
+        }
         if let Some(sp) = special_tokens {
             builder.special_tokens(sp);
         }
@@ -7,9 +7,10 @@ use pyo3::prelude::*;
 use pyo3::types::*;
 use pyo3::PyObjectProtocol;
 use tk::models::bpe::BPE;
+use tk::tokenizer::sentencepiece::SentencePieceModel;
 use tk::tokenizer::{
-    PaddingDirection, PaddingParams, PaddingStrategy, PostProcessor, TokenizerImpl,
-    TruncationParams, TruncationStrategy,
+    OffsetReferential, OffsetType, PaddingDirection, PaddingParams, PaddingStrategy, PostProcessor,
+    PruneParams, TokenizerImpl, TruncationDirection, TruncationParams, TruncationStrategy,
 };
 use tokenizers as tk;
 
@@ -20,6 +21,7 @@ use super::models::PyModel;
 use super::normalizers::PyNormalizer;
 use super::pre_tokenizers::PyPreTokenizer;
 use super::trainers::PyTrainer;
+use super::utils::{PyOffsetReferential, PyOffsetType};
 use crate::processors::PyPostProcessor;
 
 #[pyclass(dict, module = "tokenizers", name=AddedToken)]
@@ -430,6 +432,17 @@ impl PyTokenizer {
         Ok(Self::new(tokenizer?))
     }
 
+    #[staticmethod]
+    #[args(add_bos = false, add_eos = false)]
+    fn from_sentencepiece_file(path: &str, add_bos: bool, add_eos: bool) -> PyResult<Self> {
+        let tokenizer: PyResult<_> = ToPyResult(
+            SentencePieceModel::from_file(path)
+                .and_then(|model| model.into_tokenizer_with(add_bos, add_eos)),
+        )
+        .into();
+        Ok(Self::new(tokenizer?))
+    }
+
     #[staticmethod]
     fn from_buffer(buffer: &PyBytes) -> PyResult<Self> {
         let tokenizer = serde_json::from_slice(buffer.as_bytes()).map_err(|e| {
@@ -478,15 +491,32 @@ impl PyTokenizer {
                 let key: &str = key.extract()?;
                 match key {
                     "stride" => params.stride = value.extract()?,
+                    "direction" => {
+                        let value: &str = value.extract()?;
+                        params.direction = match value {
+                            "left" => Ok(TruncationDirection::Left),
+                            "right" => Ok(TruncationDirection::Right),
+                            _ => Err(PyError(format!(
+                                "Unknown `direction`: `{}`. Use \
+                                 one of `left` or `right`",
+                                value
+                            ))
+                            .into_pyerr::<exceptions::PyValueError>()),
+                        }?
+                    }
                     "strategy" => {
+                        if let Ok(index) = value.extract::<usize>() {
+                            params.strategy = TruncationStrategy::OnlySequence(index);
+                            continue;
+                        }
                         let value: &str = value.extract()?;
                         params.strategy = match value {
                             "longest_first" => Ok(TruncationStrategy::LongestFirst),
                             "only_first" => Ok(TruncationStrategy::OnlyFirst),
                             "only_second" => Ok(TruncationStrategy::OnlySecond),
                             _ => Err(PyError(format!(
-                                "Unknown `strategy`: `{}`. Use \
-                                 one of `longest_first`, `only_first`, or `only_second`",
+                                "Unknown `strategy`: `{}`. Use one of `longest_first`, \
+                                 `only_first`, `only_second`, or the index of a sequence",
                                 value
                             ))
                             .into_pyerr::<exceptions::PyValueError>()),
@@ -513,7 +543,11 @@ impl PyTokenizer {
 
             dict.set_item("max_length", params.max_length)?;
             dict.set_item("stride", params.stride)?;
-            dict.set_item("strategy", params.strategy.as_ref())?;
+            match params.strategy {
+                TruncationStrategy::OnlySequence(index) => dict.set_item("strategy", index)?,
+                strategy => dict.set_item("strategy", strategy.as_ref())?,
+            }
+            dict.set_item("direction", params.direction.as_ref())?;
 
             Ok(Some(dict))
         })
@@ -610,13 +644,22 @@ impl PyTokenizer {
     ///     [ "A", "pre", "tokenized", "sequence" ], [ "And", "its", "pair" ],
     ///     is_pretokenized=True
     /// )
-    #[args(pair = "None", is_pretokenized = "false", add_special_tokens = "true")]
+    /// encode("A sequence", offset_type="utf16", offset_referential="normalized")
+    #[args(
+        pair = "None",
+        is_pretokenized = "false",
+        add_special_tokens = "true",
+        offset_type = "PyOffsetType(OffsetType::Char)",
+        offset_referential = "PyOffsetReferential(OffsetReferential::Original)"
+    )]
     fn encode(
         &self,
         sequence: &PyAny,
         pair: Option<&PyAny>,
         is_pretokenized: bool,
         add_special_tokens: bool,
+        offset_type: PyOffsetType,
+        offset_referential: PyOffsetReferential,
     ) -> PyResult<PyEncoding> {
         let sequence: tk::InputSequence = if is_pretokenized {
             sequence.extract::<PreTokenizedInputSequence>()?.into()
@@ -637,7 +680,12 @@ impl PyTokenizer {
 
         ToPyResult(
             self.tokenizer
-                .encode_char_offsets(input, add_special_tokens)
+                .encode_with_offsets(
+                    input,
+                    add_special_tokens,
+                    offset_referential.0,
+                    offset_type.0,
+                )
                 .map(|e| e.into()),
         )
         .into()
@@ -650,12 +698,19 @@ impl PyTokenizer {
     ///   [ "A", "pre", "tokenized", "sequence" ],
     ///   ([ "A", "pre", "tokenized", "sequence" ], "And its pair")
     /// ])
-    #[args(is_pretokenized = "false", add_special_tokens = "true")]
+    #[args(
+        is_pretokenized = "false",
+        add_special_tokens = "true",
+        offset_type = "PyOffsetType(OffsetType::Char)",
+        offset_referential = "PyOffsetReferential(OffsetReferential::Original)"
+    )]
     fn encode_batch(
         &self,
         input: Vec<&PyAny>,
         is_pretokenized: bool,
         add_special_tokens: bool,
+        offset_type: PyOffsetType,
+        offset_referential: PyOffsetReferential,
     ) -> PyResult<Vec<PyEncoding>> {
         let input: Vec<tk::EncodeInput> = input
             .into_iter()
@@ -672,7 +727,12 @@ impl PyTokenizer {
         gil.python().allow_threads(|| {
             ToPyResult(
                 self.tokenizer
-                    .encode_batch_char_offsets(input, add_special_tokens)
+                    .encode_batch_with_offsets(
+                        input,
+                        add_special_tokens,
+                        offset_referential.0,
+                        offset_type.0,
+                    )
                     .map(|encodings| encodings.into_iter().map(|e| e.into()).collect()),
             )
             .into()
@@ -702,6 +762,49 @@ impl PyTokenizer {
         })
     }
 
+    #[args(json = false)]
+    fn alignment_report(&self, sequence: &str, json: bool) -> PyResult<String> {
+        let report = ToPyResult(self.tokenizer.alignment_report(sequence)).into_py()?;
+        if json {
+            serde_json::to_string(&report).map_err(|e| {
+                exceptions::PyException::new_err(format!(
+                    "Error while serializing the alignment report: {}",
+                    e
+                ))
+            })
+        } else {
+            Ok(report.to_string())
+        }
+    }
+
+    fn token_frequencies(&self, sequences: Vec<String>) -> PyResult<HashMap<u32, u64>> {
+        let gil = Python::acquire_gil();
+        gil.python()
+            .allow_threads(|| ToPyResult(self.tokenizer.token_frequencies(sequences)).into())
+    }
+
+    #[args(
+        tokens = "None",
+        frequencies = "None",
+        min_frequency = 0,
+        vocab_size = "None"
+    )]
+    fn prune(
+        &mut self,
+        tokens: Option<Vec<String>>,
+        frequencies: Option<HashMap<u32, u64>>,
+        min_frequency: u64,
+        vocab_size: Option<usize>,
+    ) -> PyResult<HashMap<u32, u32>> {
+        let params = PruneParams {
+            tokens: tokens.unwrap_or_default(),
+            frequencies,
+            min_frequency,
+            vocab_size,
+        };
+        ToPyResult(self.tokenizer.prune(&params)).into()
+    }
+
     fn token_to_id(&self, token: &str) -> Option<u32> {
         self.tokenizer.token_to_id(token)
     }
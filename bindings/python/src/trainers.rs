@@ -4,7 +4,7 @@ use std::sync::Arc;
 use pyo3::exceptions;
 use pyo3::prelude::*;
 use pyo3::types::*;
-use tk::models::TrainerWrapper;
+use tk::models::{ModelWrapper, TrainerWrapper};
 use tk::Trainer;
 use tokenizers as tk;
 
@@ -97,6 +97,18 @@ impl PyBpeTrainer {
                         builder = builder.continuing_subword_prefix(val.extract()?)
                     }
                     "end_of_word_suffix" => builder = builder.end_of_word_suffix(val.extract()?),
+                    "base_model" => {
+                        let model: PyRef<PyModel> = val.extract()?;
+                        match model.model.as_ref() {
+                            ModelWrapper::BPE(bpe) => builder = builder.base_model(bpe.clone()),
+                            _ => {
+                                return Err(exceptions::PyTypeError::new_err(
+                                    "base_model must be a BPE model",
+                                ))
+                            }
+                        }
+                    }
+                    "max_new_tokens" => builder = builder.max_new_tokens(val.extract()?),
                     _ => println!("Ignored unknown kwargs option {}", key),
                 };
             }
@@ -173,6 +185,58 @@ impl PyWordPieceTrainer {
     }
 }
 
+#[pyclass(extends=PyTrainer, name=WordLevelTrainer)]
+pub struct PyWordLevelTrainer {}
+#[pymethods]
+impl PyWordLevelTrainer {
+    /// Create a new WordLevelTrainer with the given configuration
+    #[new]
+    #[args(kwargs = "**")]
+    pub fn new(kwargs: Option<&PyDict>) -> PyResult<(Self, PyTrainer)> {
+        let mut builder = tk::models::wordlevel::WordLevelTrainer::builder();
+        if let Some(kwargs) = kwargs {
+            for (key, val) in kwargs {
+                let key: &str = key.extract()?;
+                match key {
+                    "vocab_size" => builder.vocab_size(val.extract()?),
+                    "min_frequency" => builder.min_frequency(val.extract()?),
+                    "show_progress" => builder.show_progress(val.extract()?),
+                    "unk_token" => builder.unk_token(val.extract()?),
+                    "special_tokens" => builder.special_tokens(
+                        val.cast_as::<PyList>()?
+                            .into_iter()
+                            .map(|token| {
+                                if let Ok(content) = token.extract::<String>() {
+                                    Ok(PyAddedToken::from(content, Some(true)).get_token())
+                                } else if let Ok(mut token) =
+                                    token.extract::<PyRefMut<PyAddedToken>>()
+                                {
+                                    token.is_special_token = true;
+                                    Ok(token.get_token())
+                                } else {
+                                    Err(exceptions::PyTypeError::new_err(
+                                        "special_tokens must be a List[Union[str, AddedToken]]",
+                                    ))
+                                }
+                            })
+                            .collect::<PyResult<Vec<_>>>()?,
+                    ),
+                    _ => {
+                        println!("Ignored unknown kwargs option {}", key);
+                        &mut builder
+                    }
+                };
+            }
+        }
+
+        let trainer: tokenizers::models::wordlevel::WordLevelTrainer =
+            builder.build().map_err(|e| {
+                exceptions::PyException::new_err(format!("Cannot build WordLevelTrainer: {}", e))
+            })?;
+        Ok((PyWordLevelTrainer {}, PyTrainer::new(trainer.into())))
+    }
+}
+
 #[pyclass(extends=PyTrainer, name=UnigramTrainer)]
 pub struct PyUnigramTrainer {}
 #[pymethods]
@@ -193,6 +257,20 @@ impl PyUnigramTrainer {
                     "unk_token" => builder.unk_token(val.extract()?),
                     "max_piece_length" => builder.max_piece_length(val.extract()?),
                     "seed_size" => builder.seed_size(val.extract()?),
+                    "base_model" => {
+                        let model: PyRef<PyModel> = val.extract()?;
+                        match model.model.as_ref() {
+                            ModelWrapper::Unigram(unigram) => {
+                                builder.base_model(Some(unigram.clone()))
+                            }
+                            _ => {
+                                return Err(exceptions::PyTypeError::new_err(
+                                    "base_model must be a Unigram model",
+                                ))
+                            }
+                        }
+                    }
+                    "max_new_tokens" => builder.max_new_tokens(val.extract()?),
                     "special_tokens" => builder.special_tokens(
                         val.cast_as::<PyList>()?
                             .into_iter()
@@ -0,0 +1,84 @@
+use pyo3::prelude::*;
+use pyo3::types::PyType;
+use serde::ser::Error;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use tk::utils::registry::{Component, Registry};
+use tokenizers as tk;
+
+use crate::error::ToPyResult;
+
+/// The attribute holding the name a Python class has been registered with
+const REGISTERED_TYPE: &str = "__tokenizers_type__";
+
+/// Register the Python class `cls` under the given name, so that the custom components made
+/// from its instances are saved with this name as their `type`, and can be loaded back.
+/// `wrap` makes the component from an instance.
+pub fn register_class<T, C, F>(
+    registry: &Registry<T>,
+    name: &str,
+    cls: &PyType,
+    wrap: F,
+) -> PyResult<()>
+where
+    T: ?Sized,
+    C: Component<T> + Serialize + Send + Sync + 'static,
+    F: Fn(PyObject) -> C + Send + Sync + 'static,
+{
+    let class = cls.to_object(cls.py());
+    ToPyResult(registry.register_loader(name, move |state| {
+        let obj = Python::with_gil(|py| from_state(py, class.as_ref(py), state))?;
+        Ok(wrap(obj))
+    }))
+    .into_py()?;
+    cls.setattr(REGISTERED_TYPE, name)
+}
+
+/// The name the class of `obj` has been registered with, if any. The subclasses of a
+/// registered class need to be registered too.
+pub fn registered_type(obj: &PyAny) -> PyResult<Option<String>> {
+    obj.get_type()
+        .getattr("__dict__")?
+        .call_method1("get", (REGISTERED_TYPE,))?
+        .extract()
+}
+
+/// Create an instance of `cls` from its state, the way `pickle` does: with its
+/// `__setstate__` method if it has one, or else by updating its `__dict__`
+fn from_state(py: Python, cls: &PyAny, state: Value) -> PyResult<PyObject> {
+    let obj = cls.call_method1("__new__", (cls,))?;
+    if !state.is_null() {
+        let state = py
+            .import("json")?
+            .call_method1("loads", (state.to_string(),))?;
+        if obj.hasattr("__setstate__")? {
+            obj.call_method1("__setstate__", (state,))?;
+        } else {
+            obj.getattr("__dict__")?.call_method1("update", (state,))?;
+        }
+    }
+    Ok(obj.to_object(py))
+}
+
+/// Serialize the state of `obj`, the way `pickle` gets it: with its `__getstate__` method if it
+/// has one, or else from its `__dict__`. This state must be serializable with `json`.
+pub fn serialize_state<S>(obj: &PyObject, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let state = Python::with_gil(|py| -> PyResult<String> {
+        let obj = obj.as_ref(py);
+        let state = if obj.hasattr("__getstate__")? {
+            obj.call_method0("__getstate__")?
+        } else {
+            obj.getattr("__dict__")?
+        };
+        py.import("json")?
+            .call_method1("dumps", (state,))?
+            .extract()
+    })
+    .map_err(|e| S::Error::custom(format!("Cannot serialize the state: {}", e)))?;
+    serde_json::from_str::<Value>(&state)
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
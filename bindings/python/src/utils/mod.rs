@@ -1,10 +1,12 @@
 use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
 
+mod custom;
 mod normalization;
 mod pretokenization;
 mod regex;
 
+pub use custom::*;
 pub use normalization::*;
 pub use pretokenization::*;
 pub use regex::*;
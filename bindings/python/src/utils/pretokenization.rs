@@ -66,7 +66,7 @@ fn tokenize(pretok: &mut PreTokenizedString, func: &PyAny) -> PyResult<()> {
 }
 
 #[derive(Clone)]
-pub struct PyOffsetReferential(OffsetReferential);
+pub struct PyOffsetReferential(pub OffsetReferential);
 impl FromPyObject<'_> for PyOffsetReferential {
     fn extract(obj: &PyAny) -> PyResult<Self> {
         let s = obj.extract::<&str>()?;
@@ -82,7 +82,7 @@ impl FromPyObject<'_> for PyOffsetReferential {
 }
 
 #[derive(Clone)]
-pub struct PyOffsetType(OffsetType);
+pub struct PyOffsetType(pub OffsetType);
 impl FromPyObject<'_> for PyOffsetType {
     fn extract(obj: &PyAny) -> PyResult<Self> {
         let s = obj.extract::<&str>()?;
@@ -90,8 +90,9 @@ impl FromPyObject<'_> for PyOffsetType {
         Ok(Self(match s {
             "byte" => Ok(OffsetType::Byte),
             "char" => Ok(OffsetType::Char),
+            "utf16" => Ok(OffsetType::Utf16),
             _ => Err(exceptions::PyValueError::new_err(
-                "Wrong value for OffsetType, expected one of `byte, char`",
+                "Wrong value for OffsetType, expected one of `byte, char, utf16`",
             )),
         }?))
     }
@@ -122,11 +123,12 @@ fn to_encoding(
     type_id: u32,
     word_idx: Option<u32>,
 ) -> PyResult<PyEncoding> {
-    Ok(ToPyResult(
-        pretok
-            .clone()
-            .into_encoding(word_idx, type_id, tk::OffsetType::Char),
-    )
+    Ok(ToPyResult(pretok.clone().into_encoding(
+        word_idx,
+        type_id,
+        OffsetReferential::Original,
+        OffsetType::Char,
+    ))
     .into_py()?
     .into())
 }
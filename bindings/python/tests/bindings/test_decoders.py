@@ -1,7 +1,20 @@
 import pytest
 import pickle
 
-from tokenizers.decoders import Decoder, ByteLevel, WordPiece, Metaspace, BPEDecoder
+from tokenizers import Regex
+from tokenizers.decoders import (
+    Decoder,
+    ByteLevel,
+    WordPiece,
+    Metaspace,
+    BPEDecoder,
+    ByteFallback,
+    Sequence,
+    Replace,
+    Strip,
+    Fuse,
+    CTC,
+)
 
 
 class TestByteLevel:
@@ -68,3 +81,84 @@ class TestBPEDecoder:
         )
         decoder = BPEDecoder(suffix="_")
         assert decoder.decode(["My_", "na", "me_", "is_", "Jo", "hn_"]) == "My name is John"
+
+
+class TestByteFallback:
+    def test_instantiate(self):
+        assert ByteFallback() is not None
+        assert isinstance(ByteFallback(), Decoder)
+        assert isinstance(ByteFallback(), ByteFallback)
+        assert isinstance(pickle.loads(pickle.dumps(ByteFallback())), ByteFallback)
+
+    def test_decoding(self):
+        decoder = ByteFallback()
+        assert decoder.decode(["My", " na", "me"]) == "My name"
+        assert decoder.decode(["<0x61>"]) == "a"
+        assert decoder.decode(["<0xE5>", "<0x8f>", "<0xab>", "a"]) == "叫a"
+        assert decoder.decode(["<0xE5>", "<0x8f>", "a"]) == "�a"
+
+    def test_decode_chain(self):
+        decoder = ByteFallback()
+        assert decoder.decode_chain(["My", "<0xE5>", "<0x8f>", "<0xab>"]) == ["My", "叫"]
+
+
+class TestReplace:
+    def test_instantiate(self):
+        assert Replace("▁", " ") is not None
+        assert isinstance(Replace("▁", " "), Decoder)
+        assert isinstance(Replace("▁", " "), Replace)
+        assert isinstance(pickle.loads(pickle.dumps(Replace("▁", " "))), Replace)
+
+    def test_decoding(self):
+        assert Replace("▁", " ").decode_chain(["▁My", "▁name"]) == [" My", " name"]
+        assert Replace(Regex("_+"), "-").decode(["My__", "name"]) == "My-name"
+
+
+class TestStrip:
+    def test_instantiate(self):
+        assert Strip() is not None
+        assert isinstance(Strip(), Decoder)
+        assert isinstance(Strip(), Strip)
+        assert isinstance(pickle.loads(pickle.dumps(Strip(left=1))), Strip)
+
+    def test_decoding(self):
+        decoder = Strip(left=1, right=2)
+        assert decoder.decode_chain(["  My  ", "name   "]) == [" My", "name "]
+
+
+class TestFuse:
+    def test_instantiate(self):
+        assert Fuse() is not None
+        assert isinstance(Fuse(), Decoder)
+        assert isinstance(Fuse(), Fuse)
+        assert isinstance(pickle.loads(pickle.dumps(Fuse())), Fuse)
+
+    def test_decoding(self):
+        assert Fuse().decode_chain(["My", " name"]) == ["My name"]
+
+
+class TestSequence:
+    def test_instantiate(self):
+        assert Sequence([]) is not None
+        assert isinstance(Sequence([]), Decoder)
+        assert isinstance(Sequence([]), Sequence)
+        assert isinstance(pickle.loads(pickle.dumps(Sequence([Fuse()]))), Sequence)
+
+    def test_decoding(self):
+        decoder = Sequence([Replace("▁", " "), ByteFallback(), Fuse(), Strip(left=1)])
+        assert decoder.decode(["▁My", "▁na", "<0x6D>", "e"]) == "My name"
+
+
+class TestCTC:
+    def test_instantiate(self):
+        assert CTC() is not None
+        assert isinstance(CTC(), Decoder)
+        assert isinstance(CTC(), CTC)
+        assert isinstance(pickle.loads(pickle.dumps(CTC())), CTC)
+
+    def test_decoding(self):
+        decoder = CTC()
+        tokens = "<pad> h e e l l <pad> l o o | w o <pad> o".split(" ")
+        assert decoder.decode(tokens) == "hello woo"
+        decoder = CTC(pad_token="[PAD]", word_delimiter_token="_", cleanup=False)
+        assert decoder.decode(["a", "a", "[PAD]", "a", "_", "_", "b"]) == "aa b"
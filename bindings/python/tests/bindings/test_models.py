@@ -3,7 +3,7 @@ import pickle
 
 from ..utils import data_dir, roberta_files, bert_files
 
-from tokenizers.models import Model, BPE, WordPiece, WordLevel
+from tokenizers.models import Model, BPE, WordPiece, WordLevel, Unigram
 
 
 class TestBPE:
@@ -74,3 +74,31 @@ class TestWordLevel:
             assert isinstance(WordLevel(roberta_files["vocab"]), Model)
         with pytest.deprecated_call():
             assert isinstance(WordLevel(roberta_files["vocab"]), WordLevel)
+
+
+class TestUnigram:
+    def test_instantiate(self):
+        assert isinstance(Unigram(), Model)
+        assert isinstance(Unigram(), Unigram)
+
+        vocab = [("<unk>", 0.0), ("a", -2.0), ("b", -2.0), ("ab", -1.0)]
+        assert isinstance(Unigram(vocab, 0), Unigram)
+        assert isinstance(Unigram(vocab, 0, alpha=0.1, nbest_size=-1, seed=42), Unigram)
+
+    def test_tokenize_nbest(self):
+        vocab = [("<unk>", 0.0), ("a", -2.0), ("b", -2.0), ("ab", -1.0)]
+        model = Unigram(vocab, 0)
+
+        nbest = model.tokenize_nbest("ab", 2)
+        assert [[t.value for t in tokens] for (tokens, _) in nbest] == [["ab"], ["a", "b"]]
+        assert [score for (_, score) in nbest] == [-1.0, -4.0]
+        assert [t.offsets for t in nbest[1][0]] == [(0, 1), (1, 2)]
+
+    def test_sampling_seed(self):
+        vocab = [("<unk>", 0.0), ("a", -2.0), ("b", -2.0), ("ab", -1.0)]
+        first = Unigram(vocab, 0, alpha=0.1, seed=42)
+        second = Unigram(vocab, 0, alpha=0.1, seed=42)
+
+        assert [[t.value for t in first.tokenize("abab")] for _ in range(10)] == [
+            [t.value for t in second.tokenize("abab")] for _ in range(10)
+        ]
@@ -132,3 +132,25 @@ class TestCustomNormalizer:
 
         assert repr(normalized) == 'NormalizedString(original="Hey there!", normalized="Hey you!")'
         assert str(normalized) == "Hey you!"
+
+    def test_serialization(self):
+        class Prefix:
+            def __init__(self, prefix):
+                self.prefix = prefix
+
+            def normalize(self, normalized):
+                normalized.prepend(self.prefix)
+
+        tokenizer = Tokenizer(BPE())
+        tokenizer.normalizer = Normalizer.custom(Prefix("_"))
+        with pytest.raises(Exception, match="must be registered with `Normalizer.register`"):
+            tokenizer.to_str()
+
+        Normalizer.register("Prefix", Prefix)
+        tokenizer.normalizer = Normalizer.custom(Prefix("_"))
+        serialized = tokenizer.to_str()
+        assert '"normalizer":{"type":"Prefix","prefix":"_"}' in serialized
+
+        tokenizer = Tokenizer.from_str(serialized)
+        assert tokenizer.normalizer.normalize_str("hello") == "_hello"
+        assert tokenizer.to_str() == serialized
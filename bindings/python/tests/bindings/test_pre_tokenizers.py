@@ -1,6 +1,7 @@
 import pytest
 import pickle
 
+from tokenizers import Regex
 from tokenizers.pre_tokenizers import (
     PreTokenizer,
     ByteLevel,
@@ -12,6 +13,8 @@ from tokenizers.pre_tokenizers import (
     Punctuation,
     Sequence,
     Digits,
+    Split,
+    UnicodeScripts,
 )
 
 
@@ -121,6 +124,54 @@ class TestDigits:
         assert isinstance(pickle.loads(pickle.dumps(Digits())), Digits)
 
 
+class TestSplit:
+    def test_instantiate(self):
+        pre_tokenizer = Split(pattern=" ", behavior="removed")
+        assert pre_tokenizer is not None
+        assert isinstance(pre_tokenizer, PreTokenizer)
+        assert isinstance(pre_tokenizer, Split)
+        assert isinstance(pickle.loads(pickle.dumps(Split(" ", "removed"))), Split)
+
+        # test with invert=True
+        pre_tokenizer_with_invert = Split(pattern=" ", behavior="isolated", invert=True)
+        assert pre_tokenizer_with_invert is not None
+        assert isinstance(pre_tokenizer_with_invert, PreTokenizer)
+        assert isinstance(pre_tokenizer_with_invert, Split)
+
+    def test_pre_tokenize_str(self):
+        pretok = Split(Regex(r"\w+"), "isolated", invert=True)
+        assert pretok.pre_tokenize_str("Hey, friend!") == [
+            ("Hey", (0, 3)),
+            (", ", (3, 5)),
+            ("friend", (5, 11)),
+            ("!", (11, 12)),
+        ]
+
+
+class TestUnicodeScripts:
+    def test_instantiate(self):
+        assert UnicodeScripts() is not None
+        assert isinstance(UnicodeScripts(), PreTokenizer)
+        assert isinstance(UnicodeScripts(), UnicodeScripts)
+        assert isinstance(pickle.loads(pickle.dumps(UnicodeScripts())), UnicodeScripts)
+        with pytest.raises(ValueError, match="Invalid mappings"):
+            UnicodeScripts(script_mappings={"Klingon": "Han"})
+
+    def test_pre_tokenize_str(self):
+        pretok = UnicodeScripts()
+        assert pretok.pre_tokenize_str("東京タワーはTokyo") == [
+            ("東京タワーは", (0, 6)),
+            ("Tokyo", (6, 11)),
+        ]
+
+        pretok = UnicodeScripts(script_mappings={}, char_mappings={})
+        assert pretok.pre_tokenize_str("タワーは") == [
+            ("タワ", (0, 2)),
+            ("ー", (2, 3)),
+            ("は", (3, 4)),
+        ]
+
+
 class TestCustomPreTokenizer:
     class BadCustomPretok:
         def pre_tokenize(self, pretok, wrong):
@@ -35,6 +35,10 @@ class TestBertProcessing:
         output = tokenizer.encode("my name", "pair")
         assert output.tokens == ["[CLS]", "my", "name", "[SEP]", "pair", "[SEP]"]
         assert output.ids == [1, 2, 3, 0, 6, 0]
+        assert output.sequence_ids == [None, 0, 0, None, 1, None]
+        assert output.char_to_token(0) == 1
+        assert output.char_to_token(0, sequence_index=1) == 4
+        assert output.word_to_tokens(0, sequence_index=1) == (4, 5)
 
 
 class TestRobertaProcessing:
@@ -149,6 +153,16 @@ class TestTemplateProcessing:
         # Special tokens must be provided when used in template:
         with pytest.raises(Exception, match="Missing SpecialToken\(s\) with id\(s\)"):
             processor = TemplateProcessing(single=["[CLS]"])
+        # A template for more than two sequences can be provided:
+        processor = TemplateProcessing(
+            single="[CLS] $A [SEP]",
+            pair="[CLS] $A [SEP] $B:1 [SEP]:1",
+            multiple="[CLS] $A [SEP] $B:1 [SEP]:1 $C:2 [SEP]:2",
+            special_tokens=[("[CLS]", 1), ("[SEP]", 0)],
+        )
+        assert isinstance(pickle.loads(pickle.dumps(processor)), TemplateProcessing)
+        with pytest.raises(Exception, match="must use at least three sequences"):
+            processor = TemplateProcessing(multiple="$A $C:2")
 
     def test_bert_parity(self):
         tokenizer = Tokenizer(BPE())
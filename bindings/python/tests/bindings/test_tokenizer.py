@@ -1,3 +1,4 @@
+import json
 import numpy as np
 import pickle
 import pytest
@@ -9,8 +10,8 @@ from ..utils import (
 )
 
 from tokenizers import AddedToken, Tokenizer, Encoding
-from tokenizers.models import Model, BPE, WordPiece
-from tokenizers.pre_tokenizers import ByteLevel
+from tokenizers.models import Model, BPE, WordPiece, WordLevel
+from tokenizers.pre_tokenizers import ByteLevel, WhitespaceSplit
 from tokenizers.processors import RobertaProcessing, BertProcessing
 from tokenizers.normalizers import Lowercase
 from tokenizers.implementations import BertWordPieceTokenizer
@@ -143,6 +144,26 @@ class TestTokenizer:
         output = tokenizer.encode_batch(["my name is john", ("my name is john", "pair")])
         assert len(output) == 2
 
+    def test_encode_offsets(self):
+        tokenizer = Tokenizer(WordLevel({"[UNK]": 0, "a": 1, "😀": 2, "b": 3}, unk_token="[UNK]"))
+        tokenizer.pre_tokenizer = WhitespaceSplit()
+        tokenizer.enable_truncation(2)
+
+        # Offsets in chars by default
+        output = tokenizer.encode("a 😀 b 😀")
+        assert output.offsets == [(0, 1), (2, 3)]
+        assert output.overflowing[0].offsets == [(4, 5), (6, 7)]
+
+        # The overflowing encodings use the same offsets
+        output = tokenizer.encode("a 😀 b 😀", offset_type="utf16")
+        assert output.offsets == [(0, 1), (2, 4)]
+        assert output.overflowing[0].offsets == [(5, 6), (7, 9)]
+        output = tokenizer.encode_batch(
+            ["a 😀 b 😀"], offset_type="byte", offset_referential="normalized"
+        )[0]
+        assert output.offsets == [(0, 1), (1, 5)]
+        assert output.overflowing[0].offsets == [(5, 6), (6, 10)]
+
     def test_encode_formats(self, bert_files):
         with pytest.deprecated_call():
             tokenizer = BertWordPieceTokenizer(bert_files["vocab"])
@@ -311,6 +332,13 @@ class TestTokenizer:
         trunc = tokenizer.truncation
         tokenizer.enable_truncation(**trunc)
 
+        # Can truncate from the left
+        tokenizer.enable_truncation(2, direction="left")
+        assert tokenizer.truncation["direction"] == "left"
+        output = tokenizer.encode("my name is john")
+        assert output.tokens == ["is", "john"]
+        assert output.overflowing[0].tokens == ["my", "name"]
+
     def test_padding(self):
         tokenizer = Tokenizer(BPE())
         tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
@@ -347,6 +375,43 @@ class TestTokenizer:
         output = tokenizer.decode_batch([[0, 1, 2, 3], [4]])
         assert output == ["my name is john", "pair"]
 
+    def test_alignment_report(self):
+        tokenizer = Tokenizer(BPE())
+        tokenizer.normalizer = Lowercase()
+        tokenizer.add_tokens(["my", "name"])
+
+        report = json.loads(tokenizer.alignment_report("My name", json=True))
+        assert report["original"] == "My name"
+        assert report["normalized"] == "my name"
+        assert report["alignments"][0] == {
+            "normalized": "m",
+            "normalized_offsets": [0, 1],
+            "original": "M",
+            "original_offsets": [0, 1],
+        }
+        assert [s["normalized"] for s in report["splits"]] == ["my", " ", "name"]
+        assert [t["id"] for t in report["splits"][2]["tokens"]] == [1]
+
+        table = tokenizer.alignment_report("My name")
+        assert table.splitlines()[1].startswith('"m"')
+
+    def test_prune(self):
+        vocab = {"[UNK]": 0, "the": 1, "cat": 2, "sat": 3, "on": 4, "mat": 5}
+        tokenizer = Tokenizer(WordLevel(vocab, unk_token="[UNK]"))
+        tokenizer.pre_tokenizer = WhitespaceSplit()
+        tokenizer.add_special_tokens(["[CLS]"])
+
+        frequencies = tokenizer.token_frequencies(["the cat sat", "the mat [CLS]"])
+        assert frequencies == {1: 2, 2: 1, 3: 1, 5: 1, 6: 1}
+
+        mapping = tokenizer.prune(tokens=["sat"], frequencies=frequencies, min_frequency=1)
+        assert mapping == {0: 0, 1: 1, 2: 2, 5: 3, 6: 4}
+        assert tokenizer.get_vocab() == {"[UNK]": 0, "the": 1, "cat": 2, "mat": 3, "[CLS]": 4}
+        assert tokenizer.encode("[CLS] the mat").ids == [4, 1, 3]
+
+        with pytest.raises(Exception, match="Cannot remove"):
+            tokenizer.prune(tokens=["[UNK]"])
+
     def test_get_vocab(self):
         tokenizer = Tokenizer(BPE())
         tokenizer.add_tokens(["my", "name", "is", "john", "pair"])
@@ -2,7 +2,8 @@ import os
 import pytest
 import pickle
 
-from tokenizers import SentencePieceUnigramTokenizer
+from tokenizers import SentencePieceUnigramTokenizer, Tokenizer, models, pre_tokenizers, trainers
+from tokenizers.trainers import WordLevelTrainer
 from ..utils import data_dir, train_files
 
 
@@ -14,3 +15,43 @@ class TestUnigram:
         filename = "tests/data/unigram_trained.json"
         tokenizer.save(filename)
         os.remove(filename)
+
+
+class TestWordLevelTrainer:
+    def test_instantiate(self):
+        assert WordLevelTrainer() is not None
+        assert isinstance(WordLevelTrainer(), trainers.Trainer)
+        assert isinstance(WordLevelTrainer(vocab_size=100, min_frequency=2), WordLevelTrainer)
+
+    def test_train(self, train_files):
+        tokenizer = Tokenizer(models.WordLevel())
+        tokenizer.pre_tokenizer = pre_tokenizers.Whitespace()
+        trainer = WordLevelTrainer(
+            vocab_size=100, show_progress=False, special_tokens=["<unk>", "<pad>"]
+        )
+        tokenizer.train(trainer, [train_files["small"]])
+
+        assert tokenizer.get_vocab_size() == 100
+        assert tokenizer.token_to_id("<unk>") == 0
+        assert tokenizer.token_to_id("<pad>") == 1
+
+
+class TestBpeTrainer:
+    def test_extend(self, train_files):
+        tokenizer = Tokenizer(models.BPE())
+        tokenizer.pre_tokenizer = pre_tokenizers.Whitespace()
+        trainer = trainers.BpeTrainer(vocab_size=200, show_progress=False)
+        tokenizer.train(trainer, [train_files["small"]])
+        base_vocab = tokenizer.get_vocab()
+
+        trainer = trainers.BpeTrainer(
+            base_model=tokenizer.model, max_new_tokens=50, show_progress=False
+        )
+        tokenizer.train(trainer, [train_files["small"]])
+        vocab = tokenizer.get_vocab()
+
+        assert len(vocab) == len(base_vocab) + 50
+        assert all(vocab[token] == id for token, id in base_vocab.items())
+
+        with pytest.raises(TypeError, match="must be a BPE model"):
+            trainers.BpeTrainer(base_model=models.Unigram())
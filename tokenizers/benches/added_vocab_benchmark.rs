@@ -0,0 +1,129 @@
+#[macro_use]
+extern crate criterion;
+
+mod common;
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use criterion::{black_box, Criterion};
+use tokenizers::models::wordlevel::WordLevel;
+use tokenizers::normalizers::utils::Lowercase;
+use tokenizers::pre_tokenizers::whitespace::Whitespace;
+use tokenizers::tokenizer::{AddedToken, EncodeInput};
+use tokenizers::Tokenizer;
+
+use common::{iter_bench_encode, iter_bench_encode_batch};
+
+static BATCH_SIZE: usize = 1_000;
+static VOCAB_SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+/// Collect up to `n` distinct words from the given lines, to be used as added tokens.
+fn collect_words(lines: &[String], n: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+    lines
+        .iter()
+        .flat_map(|line| line.split_whitespace())
+        .filter(|word| seen.insert(word.to_string()))
+        .take(n)
+        .map(|word| word.to_owned())
+        .collect()
+}
+
+fn create_tokenizer(words: &[String], special: bool) -> Tokenizer {
+    let mut vocab = HashMap::new();
+    vocab.insert("<unk>".to_string(), 0);
+    let mut tokenizer = Tokenizer::new(WordLevel::builder().vocab(vocab).build());
+    tokenizer.with_normalizer(Lowercase);
+    tokenizer.with_pre_tokenizer(Whitespace::default());
+
+    let tokens = words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            AddedToken::from(word.as_str(), special)
+                .single_word(i % 2 == 0)
+                .lstrip(i % 3 == 0)
+                .rstrip(i % 5 == 0)
+        })
+        .collect::<Vec<_>>();
+    if special {
+        tokenizer.add_special_tokens(&tokens);
+    } else {
+        tokenizer.add_tokens(&tokens);
+    }
+    tokenizer
+}
+
+fn iter_bench_add_tokens(iters: u64, words: &[String]) -> Duration {
+    let mut duration = Duration::new(0, 0);
+    for _i in 0..iters {
+        let mut tokenizer = Tokenizer::new(WordLevel::default());
+        let start = Instant::now();
+        for word in words {
+            black_box(tokenizer.add_tokens(&[AddedToken::from(word.as_str(), false)]));
+        }
+        duration = duration.checked_add(start.elapsed()).unwrap();
+    }
+    duration
+}
+
+fn bench_added_vocab(c: &mut Criterion) {
+    let lines = BufReader::new(File::open(Path::new("data/big.txt")).unwrap())
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    let inputs: Vec<EncodeInput> = lines.iter().map(|line| line.as_str().into()).collect();
+    let batches: Vec<Vec<EncodeInput>> = inputs
+        .chunks(BATCH_SIZE)
+        .map(|batch| batch.to_vec())
+        .collect();
+
+    for &size in VOCAB_SIZES.iter() {
+        let words = collect_words(&lines, size);
+
+        let tokenizer = create_tokenizer(&words, false);
+        c.bench_function(&format!("AddedVocabulary encode ({} tokens)", size), |b| {
+            b.iter_custom(|iters| iter_bench_encode(iters, &tokenizer, &inputs))
+        });
+        c.bench_function(
+            &format!("AddedVocabulary encode batch ({} tokens)", size),
+            |b| b.iter_custom(|iters| iter_bench_encode_batch(iters, &tokenizer, &batches)),
+        );
+
+        let tokenizer = create_tokenizer(&words, true);
+        c.bench_function(
+            &format!("AddedVocabulary encode special ({} tokens)", size),
+            |b| b.iter_custom(|iters| iter_bench_encode(iters, &tokenizer, &inputs)),
+        );
+    }
+}
+
+fn bench_add_tokens(c: &mut Criterion) {
+    let lines = BufReader::new(File::open(Path::new("data/big.txt")).unwrap())
+        .lines()
+        .map(|line| line.unwrap())
+        .collect::<Vec<_>>();
+    let words = collect_words(&lines, 1_000);
+
+    c.bench_function("AddedVocabulary add tokens one by one (1000 tokens)", |b| {
+        b.iter_custom(|iters| iter_bench_add_tokens(iters, &words))
+    });
+}
+
+criterion_group! {
+    name = added_vocab_benches;
+    config = Criterion::default().sample_size(20);
+    targets = bench_added_vocab
+}
+
+criterion_group! {
+    name = add_tokens_benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_add_tokens
+}
+
+criterion_main!(added_vocab_benches, add_tokens_benches);
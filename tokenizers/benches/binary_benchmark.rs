@@ -0,0 +1,106 @@
+#[macro_use]
+extern crate criterion;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use criterion::{black_box, Criterion};
+use tempfile::TempDir;
+use tokenizers::models::bpe::BPE;
+use tokenizers::models::unigram::Unigram;
+use tokenizers::pre_tokenizers::whitespace::Whitespace;
+use tokenizers::{BinaryTokenizer, Tokenizer};
+
+/// All the words of one to three letters, each one being the merge of its prefix with its
+/// last letter: 18_278 tokens, and as many merges minus the letters.
+fn words() -> Vec<String> {
+    let letters = (b'a'..=b'z').map(|c| (c as char).to_string());
+    let mut words: Vec<String> = letters.clone().collect();
+    let mut last = words.clone();
+    for _ in 0..2 {
+        last = last
+            .iter()
+            .flat_map(|prefix| {
+                letters
+                    .clone()
+                    .map(move |letter| format!("{}{}", prefix, letter))
+            })
+            .collect();
+        words.extend(last.iter().cloned());
+    }
+    words
+}
+
+fn bpe_tokenizer() -> Tokenizer {
+    let words = words();
+    let merges = words
+        .iter()
+        .filter(|word| word.len() > 1)
+        .map(|word| {
+            let (prefix, letter) = word.split_at(word.len() - 1);
+            (prefix.to_owned(), letter.to_owned())
+        })
+        .collect();
+    let vocab: HashMap<_, _> = words
+        .into_iter()
+        .enumerate()
+        .map(|(id, word)| (word, id as u32))
+        .collect();
+    let bpe = BPE::builder()
+        .vocab_and_merges(vocab, merges)
+        .build()
+        .unwrap();
+    let mut tokenizer = Tokenizer::new(bpe);
+    tokenizer.with_pre_tokenizer(Whitespace::default());
+    tokenizer
+}
+
+fn unigram_tokenizer() -> Tokenizer {
+    let vocab = words()
+        .into_iter()
+        .enumerate()
+        .map(|(id, word)| {
+            let score = -(word.len() as f64) - id as f64 * 1e-6;
+            (word, score)
+        })
+        .collect();
+    let mut tokenizer = Tokenizer::new(Unigram::from(vocab, 0).unwrap());
+    tokenizer.with_pre_tokenizer(Whitespace::default());
+    tokenizer
+}
+
+/// Compare the loading of the tokenizer.json with the loading of the binary format, whose model
+/// uses the mapped tables, and with the lookups in these tables, without building the model.
+fn bench_load(c: &mut Criterion, name: &str, tokenizer: Tokenizer, dir: &Path) {
+    let json = dir.join(format!("{}.json", name));
+    let binary = dir.join(format!("{}.bin", name));
+    tokenizer.save(&json, false).unwrap();
+    tokenizer.save_binary(&binary).unwrap();
+
+    c.bench_function(&format!("{} load json", name), |b| {
+        b.iter(|| black_box(Tokenizer::from_file(&json).unwrap()))
+    });
+    c.bench_function(&format!("{} load binary", name), |b| {
+        b.iter(|| black_box(Tokenizer::from_binary_file(&binary).unwrap()))
+    });
+    c.bench_function(&format!("{} lookup binary", name), |b| {
+        b.iter(|| {
+            let tokenizer = BinaryTokenizer::from_file(&binary).unwrap();
+            black_box(tokenizer.token_to_id("xyz"))
+        })
+    });
+}
+
+fn bench_binary(c: &mut Criterion) {
+    let dir = TempDir::new().unwrap();
+    bench_load(c, "BPE", bpe_tokenizer(), dir.path());
+    bench_load(c, "Unigram", unigram_tokenizer(), dir.path());
+}
+
+criterion_group! {
+    name = binary_benches;
+    config = Criterion::default().sample_size(20);
+    targets = bench_binary
+}
+
+criterion_main!(binary_benches);
@@ -58,6 +58,7 @@ where
     duration
 }
 
+#[allow(dead_code)]
 pub fn iter_bench_train<T, M, N, PT, PP, D>(
     iters: u64,
     tokenizer: &mut TokenizerImpl<M, N, PT, PP, D>,
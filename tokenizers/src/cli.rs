@@ -3,29 +3,107 @@
 //!
 
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
-use std::io::{self, BufRead, Write};
-use tokenizers::models::bpe::BPE;
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use tokenizers::decoders::wordpiece::WordPiece as WordPieceDecoder;
+use tokenizers::models::bpe::{BpeTrainer, BPE};
+use tokenizers::models::unigram::{Unigram, UnigramTrainer};
+use tokenizers::models::wordlevel::{WordLevel, WordLevelTrainer};
+use tokenizers::models::wordpiece::{WordPiece, WordPieceTrainer};
+use tokenizers::models::{ModelWrapper, TrainerWrapper};
+use tokenizers::pre_tokenizers::bert::BertPreTokenizer;
 use tokenizers::pre_tokenizers::byte_level::ByteLevel;
-use tokenizers::tokenizer::{AddedToken, Result};
-use tokenizers::Tokenizer;
+use tokenizers::pre_tokenizers::metaspace::Metaspace;
+use tokenizers::pre_tokenizers::whitespace::Whitespace;
+use tokenizers::tokenizer::{AddedToken, EncodeInput, Result};
+use tokenizers::{BinaryTokenizer, Model, Tokenizer};
 
-fn shell(matches: &ArgMatches) -> Result<()> {
-    let vocab = matches
-        .value_of("vocab")
-        .expect("Must give a vocab.json file");
-    let merges = matches
-        .value_of("merges")
-        .expect("Must give a merges.txt file");
+/// Number of lines we encode at once when reading the input
+static BATCH_SIZE: usize = 1_000;
 
-    let bpe = BPE::from_file(vocab, merges).build()?;
-    let mut tokenizer = Tokenizer::new(bpe);
-    tokenizer
-        .with_pre_tokenizer(ByteLevel::default())
-        .with_decoder(ByteLevel::default());
+/// Open the file given as `INPUT`, or stdin if there is none
+fn input(matches: &ArgMatches) -> Result<Box<dyn BufRead>> {
+    Ok(match matches.value_of("INPUT") {
+        Some(path) if path != "-" => Box::new(BufReader::new(File::open(path)?)),
+        _ => Box::new(BufReader::new(io::stdin())),
+    })
+}
 
-    tokenizer.add_tokens(&[AddedToken::from(String::from("ing"), false).single_word(false)]);
+/// Whether the given file is a binary tokenizer, whatever its extension
+fn is_binary(path: &str) -> Result<bool> {
+    let mut start = vec![];
+    File::open(path)?.take(8).read_to_end(&mut start)?;
+    Ok(BinaryTokenizer::is_binary(&start))
+}
+
+/// Open a tokenizer.json, or a binary tokenizer
+fn open_tokenizer(path: &str) -> Result<Tokenizer> {
+    if is_binary(path)? {
+        Tokenizer::from_binary_file(path)
+    } else {
+        Tokenizer::from_file(path)
+    }
+}
+
+fn load_tokenizer(matches: &ArgMatches) -> Result<Tokenizer> {
+    let path = matches
+        .value_of("tokenizer")
+        .ok_or("Must give a tokenizer.json file")?;
+    open_tokenizer(path)
+}
+
+fn model_name(model: &ModelWrapper) -> &str {
+    match model {
+        ModelWrapper::BPE(_) => "BPE",
+        ModelWrapper::WordPiece(_) => "WordPiece",
+        ModelWrapper::WordLevel(_) => "WordLevel",
+        ModelWrapper::Unigram(_) => "Unigram",
+        ModelWrapper::Custom(custom) => custom.name(),
+    }
+}
+
+/// Build a `Tokenizer` around the given model, with the pipeline usually associated to it
+fn with_default_pipeline(model: ModelWrapper) -> Tokenizer {
+    let mut tokenizer = Tokenizer::new(model);
+    match tokenizer.get_model() {
+        ModelWrapper::BPE(_) => {
+            tokenizer
+                .with_pre_tokenizer(ByteLevel::default())
+                .with_decoder(ByteLevel::default());
+        }
+        ModelWrapper::WordPiece(_) => {
+            tokenizer
+                .with_pre_tokenizer(BertPreTokenizer)
+                .with_decoder(WordPieceDecoder::default());
+        }
+        ModelWrapper::WordLevel(_) => {
+            tokenizer.with_pre_tokenizer(Whitespace::default());
+        }
+        ModelWrapper::Unigram(_) => {
+            tokenizer
+                .with_pre_tokenizer(Metaspace::default())
+                .with_decoder(Metaspace::default());
+        }
+        ModelWrapper::Custom(_) => {}
+    }
     tokenizer
-        .add_special_tokens(&[AddedToken::from(String::from("[ENT]"), true).single_word(true)]);
+}
+
+fn shell(matches: &ArgMatches) -> Result<()> {
+    let tokenizer = match matches.value_of("tokenizer") {
+        Some(path) => open_tokenizer(path)?,
+        None => {
+            let vocab = matches
+                .value_of("vocab")
+                .ok_or("Must give a tokenizer.json or a vocab.json file")?;
+            let merges = matches
+                .value_of("merges")
+                .ok_or("Must give a merges.txt file")?;
+            with_default_pipeline(BPE::from_file(vocab, merges).build()?.into())
+        }
+    };
 
     let stdin = io::stdin();
     let mut handle = stdin.lock();
@@ -36,7 +114,9 @@ fn shell(matches: &ArgMatches) -> Result<()> {
 
         print!("\nEnter some text to tokenize:\n>  ");
         io::stdout().flush()?;
-        handle.read_line(&mut buffer)?;
+        if handle.read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
         let buffer = buffer.trim_end();
 
         let timer = std::time::Instant::now();
@@ -48,14 +128,381 @@ fn shell(matches: &ArgMatches) -> Result<()> {
         println!("Offsets:\t{:?}", encoded.get_offsets());
         println!(
             "Decoded:\t{}",
-            tokenizer.decode(encoded.get_ids().to_vec(), true).unwrap()
+            tokenizer.decode(encoded.get_ids().to_vec(), true)?
         );
         println!("Tokenized in {:?}", elapsed);
     }
 }
 
-fn main() -> Result<()> {
-    let matches = App::new("tokenizers")
+/// Encode each line of the input, writing one JSON object per line
+fn encode(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
+    let tokenizer = load_tokenizer(matches)?;
+    let add_special_tokens = matches.is_present("add-special-tokens");
+
+    let mut lines = input(matches)?.lines().peekable();
+    while lines.peek().is_some() {
+        let batch = lines
+            .by_ref()
+            .take(BATCH_SIZE)
+            .map(|line| line.map(EncodeInput::from))
+            .collect::<io::Result<Vec<_>>>()?;
+        for encoding in tokenizer.encode_batch(batch, add_special_tokens)? {
+            let line = json!({
+                "ids": encoding.get_ids(),
+                "tokens": encoding.get_tokens(),
+                "offsets": encoding.get_offsets(),
+            });
+            writeln!(output, "{}", line)?;
+        }
+    }
+    Ok(())
+}
+
+/// Parse a line of ids, either as the output of `encode`, a JSON list, or whitespace separated
+fn parse_ids(line: &str) -> Result<Vec<u32>> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        let value: Value = serde_json::from_str(line)?;
+        Ok(serde_json::from_value(value["ids"].clone())?)
+    } else if line.starts_with('[') {
+        Ok(serde_json::from_str(line)?)
+    } else {
+        Ok(line
+            .split_whitespace()
+            .map(|id| id.parse())
+            .collect::<std::result::Result<_, _>>()?)
+    }
+}
+
+/// Decode each line of ids of the input, writing one decoded sequence per line
+fn decode(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
+    let tokenizer = load_tokenizer(matches)?;
+    let skip_special_tokens = !matches.is_present("keep-special-tokens");
+
+    for line in input(matches)?.lines() {
+        let ids = parse_ids(&line?)?;
+        writeln!(output, "{}", tokenizer.decode(ids, skip_special_tokens)?)?;
+    }
+    Ok(())
+}
+
+fn train(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
+    let files = matches
+        .values_of("FILES")
+        .ok_or("Must give some files to train on")?
+        .map(|f| f.to_owned())
+        .collect::<Vec<_>>();
+    let path = matches
+        .value_of("output")
+        .ok_or("Must give an output file")?;
+    let show_progress = !matches.is_present("quiet");
+    let special_tokens = matches
+        .values_of("special-tokens")
+        .map(|tokens| tokens.map(|t| AddedToken::from(t, true)).collect())
+        .unwrap_or_default();
+    let vocab_size = matches
+        .value_of("vocab-size")
+        .map(|v| v.parse::<usize>())
+        .transpose()?;
+    let min_frequency = matches
+        .value_of("min-frequency")
+        .map(|v| v.parse::<u32>())
+        .transpose()?;
+
+    let (model, trainer): (ModelWrapper, TrainerWrapper) = match matches.value_of("model") {
+        Some("wordpiece") => {
+            let mut builder = WordPieceTrainer::builder()
+                .show_progress(show_progress)
+                .special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder = builder.vocab_size(vocab_size);
+            }
+            if let Some(min_frequency) = min_frequency {
+                builder = builder.min_frequency(min_frequency);
+            }
+            (WordPiece::default().into(), builder.build().into())
+        }
+        Some("wordlevel") => {
+            let mut builder = WordLevelTrainer::builder();
+            builder
+                .show_progress(show_progress)
+                .special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder.vocab_size(vocab_size);
+            }
+            if let Some(min_frequency) = min_frequency {
+                builder.min_frequency(min_frequency);
+            }
+            (WordLevel::default().into(), builder.build()?.into())
+        }
+        Some("unigram") => {
+            let mut builder = UnigramTrainer::builder();
+            builder
+                .show_progress(show_progress)
+                .special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder.vocab_size(vocab_size as u32);
+            }
+            if min_frequency.is_some() {
+                return Err("`--min-frequency` is not supported by the unigram trainer".into());
+            }
+            (Unigram::default().into(), builder.build()?.into())
+        }
+        _ => {
+            let mut builder = BpeTrainer::builder()
+                .show_progress(show_progress)
+                .special_tokens(special_tokens);
+            if let Some(vocab_size) = vocab_size {
+                builder = builder.vocab_size(vocab_size);
+            }
+            if let Some(min_frequency) = min_frequency {
+                builder = builder.min_frequency(min_frequency);
+            }
+            (BPE::default().into(), builder.build().into())
+        }
+    };
+
+    // Either start from the pipeline of the given tokenizer, or from the default one
+    let mut tokenizer = match matches.value_of("tokenizer") {
+        Some(path) => {
+            let mut tokenizer = open_tokenizer(path)?;
+            tokenizer.with_model(model);
+            tokenizer
+        }
+        None => with_default_pipeline(model),
+    };
+    tokenizer.train_and_replace(&trainer, files)?;
+    tokenizer.save(path, matches.is_present("pretty"))?;
+    writeln!(output, "Saved the trained tokenizer to {}", path)?;
+    Ok(())
+}
+
+fn inspect(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
+    let tokenizer = load_tokenizer(matches)?;
+    let serialized = serde_json::to_value(&tokenizer)?;
+    let component = |name: &str| match &serialized[name] {
+        Value::Null => "None".to_owned(),
+        value => value.to_string(),
+    };
+
+    let vocab = tokenizer.get_vocab(false);
+    let lengths = vocab.keys().map(|t| t.chars().count()).collect::<Vec<_>>();
+    let added_tokens = serialized["added_tokens"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let special_tokens = added_tokens
+        .iter()
+        .filter(|t| t["special"].as_bool().unwrap_or(false))
+        .count();
+
+    writeln!(output, "Model:\t\t\t{}", model_name(tokenizer.get_model()))?;
+    writeln!(output, "Normalizer:\t\t{}", component("normalizer"))?;
+    writeln!(output, "Pre-tokenizer:\t\t{}", component("pre_tokenizer"))?;
+    writeln!(output, "Post-processor:\t\t{}", component("post_processor"))?;
+    writeln!(output, "Decoder:\t\t{}", component("decoder"))?;
+    writeln!(output, "Truncation:\t\t{}", component("truncation"))?;
+    writeln!(output, "Padding:\t\t{}", component("padding"))?;
+    writeln!(output, "Vocab size:\t\t{}", tokenizer.get_vocab_size(false))?;
+    writeln!(
+        output,
+        "Added tokens:\t\t{} ({} special)",
+        added_tokens.len(),
+        special_tokens
+    )?;
+    if !lengths.is_empty() {
+        writeln!(
+            output,
+            "Token length (chars):\tmin {}, max {}, mean {:.2}",
+            lengths.iter().min().unwrap(),
+            lengths.iter().max().unwrap(),
+            lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+        )?;
+    }
+    Ok(())
+}
+
+fn convert(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
+    // tokenizer.json <-> binary tokenizer
+    if let (Some(path), true) = (matches.value_of("tokenizer"), matches.is_present("binary")) {
+        let tokenizer = open_tokenizer(path)?;
+        let saved = if is_binary(path)? {
+            let saved = matches.value_of("output").unwrap_or("tokenizer.json");
+            tokenizer.save(saved, matches.is_present("pretty"))?;
+            saved
+        } else {
+            let saved = matches.value_of("output").unwrap_or("tokenizer.bin");
+            tokenizer.save_binary(saved)?;
+            saved
+        };
+        writeln!(output, "Saved {}", saved)?;
+        return Ok(());
+    }
+
+    // tokenizer.json -> model files
+    if let Some(path) = matches.value_of("tokenizer") {
+        let tokenizer = open_tokenizer(path)?;
+        let folder = matches.value_of("output").unwrap_or(".");
+        for file in tokenizer
+            .get_model()
+            .save(Path::new(folder), matches.value_of("prefix"))?
+        {
+            writeln!(output, "Saved {}", file.display())?;
+        }
+        return Ok(());
+    }
+
+    // model files -> tokenizer.json
+    let vocab = matches
+        .value_of("vocab")
+        .ok_or("Must give either a tokenizer.json or a vocab file")?;
+    let unk_token = matches.value_of("unk-token").map(|t| t.to_owned());
+    let model: ModelWrapper = match matches.value_of("model") {
+        Some("wordpiece") => {
+            let mut builder = WordPiece::from_file(vocab);
+            if let Some(unk_token) = unk_token {
+                builder = builder.unk_token(unk_token);
+            }
+            builder.build()?.into()
+        }
+        Some("wordlevel") => {
+            WordLevel::from_file(vocab, unk_token.unwrap_or_else(|| "<unk>".into()))?.into()
+        }
+        // The unk_id is part of the saved model
+        Some("unigram") => Unigram::load(vocab)?.into(),
+        _ => {
+            let merges = matches
+                .value_of("merges")
+                .ok_or("Must give a merges.txt file for BPE")?;
+            let mut builder = BPE::from_file(vocab, merges);
+            if let Some(unk_token) = unk_token {
+                builder = builder.unk_token(unk_token);
+            }
+            builder.build()?.into()
+        }
+    };
+    let saved = matches.value_of("output").unwrap_or("tokenizer.json");
+    with_default_pipeline(model).save(saved, matches.is_present("pretty"))?;
+    writeln!(output, "Saved {}", saved)?;
+    Ok(())
+}
+
+/// Encode and decode each line of the input, and report the lines that are not decoded back
+fn validate(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
+    let tokenizer = load_tokenizer(matches)?;
+    let lines = input(matches)?.lines().collect::<io::Result<Vec<_>>>()?;
+    let report = tokenizer.validate_round_trip(lines)?;
+
+    if matches.is_present("json") {
+        writeln!(output, "{}", serde_json::to_string(&report)?)?;
+        return Ok(());
+    }
+
+    let max_mismatches = matches
+        .value_of("max-mismatches")
+        .ok_or("Must give a number of mismatches")?
+        .parse::<usize>()?;
+    for mismatch in report.mismatches.iter().take(max_mismatches) {
+        let (start, end) = mismatch.offsets;
+        let step = match &mismatch.component {
+            Some(component) => format!("{} {}", mismatch.step, component),
+            None => mismatch.step.to_string(),
+        };
+        writeln!(
+            output,
+            "Line {} [{}]: {:?} -> {:?}",
+            mismatch.index + 1,
+            step,
+            &mismatch.original[start..end],
+            mismatch.replacement
+        )?;
+        writeln!(output, "\tOriginal:\t{}", mismatch.original)?;
+        writeln!(output, "\tDecoded:\t{}", mismatch.decoded)?;
+    }
+    if report.mismatches.len() > max_mismatches {
+        writeln!(
+            output,
+            "... and {} other mismatches",
+            report.mismatches.len() - max_mismatches
+        )?;
+    }
+
+    let mut steps = report.mismatches_by_step().into_iter().collect::<Vec<_>>();
+    steps.sort_by_key(|(step, _)| *step as usize);
+    writeln!(output, "Samples:\t\t{}", report.samples)?;
+    writeln!(
+        output,
+        "Lossless round trips:\t{} ({:.2}%)",
+        report.lossless,
+        report.lossless_rate() * 100.0
+    )?;
+    if !steps.is_empty() {
+        writeln!(
+            output,
+            "Mismatches by step:\t{}",
+            steps
+                .iter()
+                .map(|(step, n)| format!("{} {}", step, n))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+    }
+    writeln!(
+        output,
+        "Unknown tokens:\t\t{} ({:.2}%)",
+        report.unk_tokens,
+        report.unk_rate() * 100.0
+    )?;
+    writeln!(output, "Tokens per word:\t{:.2}", report.tokens_per_word())?;
+    Ok(())
+}
+
+/// Print how each line of the input is aligned with its normalized and tokenized versions
+fn align(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
+    let tokenizer = load_tokenizer(matches)?;
+    let json = matches.is_present("json");
+
+    for (i, line) in input(matches)?.lines().enumerate() {
+        let report = tokenizer.alignment_report(&line?)?;
+        if json {
+            writeln!(output, "{}", serde_json::to_string(&report)?)?;
+        } else {
+            if i > 0 {
+                writeln!(output)?;
+            }
+            write!(output, "{}", report)?;
+        }
+    }
+    Ok(())
+}
+
+fn tokenizer_arg(required: bool) -> Arg<'static, 'static> {
+    Arg::with_name("tokenizer")
+        .long("tokenizer")
+        .short("t")
+        .value_name("TOKENIZER_FILE")
+        .help("Path to the tokenizer.json file")
+        .required(required)
+}
+
+fn input_arg() -> Arg<'static, 'static> {
+    Arg::with_name("INPUT")
+        .help("The file to read, one sequence per line. Reads stdin if missing or `-`")
+        .index(1)
+}
+
+fn model_arg() -> Arg<'static, 'static> {
+    Arg::with_name("model")
+        .long("model")
+        .short("m")
+        .value_name("MODEL")
+        .possible_values(&["bpe", "wordpiece", "wordlevel", "unigram"])
+        .default_value("bpe")
+        .help("The type of model")
+}
+
+fn app() -> App<'static, 'static> {
+    App::new("tokenizers")
         .version("0.0.1")
         .author("Anthony M. <anthony@huggingface.co>")
         .about("Generate custom Tokenizers or use existing ones")
@@ -63,25 +510,419 @@ fn main() -> Result<()> {
         .subcommand(
             SubCommand::with_name("shell")
                 .about("Interactively test a tokenizer")
+                .arg(tokenizer_arg(false).conflicts_with_all(&["vocab", "merges"]))
                 .arg(
                     Arg::with_name("vocab")
                         .long("vocab")
                         .value_name("VOCAB_FILE")
-                        .help("Path to the vocab.json file")
-                        .required(true),
+                        .help("Path to the vocab.json file of a byte-level BPE")
+                        .requires("merges"),
                 )
                 .arg(
                     Arg::with_name("merges")
                         .long("merges")
                         .value_name("MERGES_FILE")
-                        .help("Path to the merges.txt file")
+                        .help("Path to the merges.txt file of a byte-level BPE")
+                        .requires("vocab"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("encode")
+                .about("Encode each line of the input, and output JSON lines")
+                .arg(tokenizer_arg(true))
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("add-special-tokens")
+                        .long("add-special-tokens")
+                        .help("Add the special tokens of the post-processor"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("decode")
+                .about(
+                    "Decode each line of ids of the input. Lines can be the output of `encode`, \
+                     JSON lists, or whitespace separated ids",
+                )
+                .arg(tokenizer_arg(true))
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("keep-special-tokens")
+                        .long("keep-special-tokens")
+                        .help("Do not skip the special tokens while decoding"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("train")
+                .about("Train a new tokenizer on the given files")
+                .arg(model_arg())
+                .arg(tokenizer_arg(false).help(
+                    "Path to a tokenizer.json file, whose pipeline is used instead of the default \
+                     one of the model",
+                ))
+                .arg(
+                    Arg::with_name("vocab-size")
+                        .long("vocab-size")
+                        .value_name("SIZE")
+                        .help("The size of the final vocabulary"),
+                )
+                .arg(
+                    Arg::with_name("min-frequency")
+                        .long("min-frequency")
+                        .value_name("FREQUENCY")
+                        .help("The minimum frequency of a token to be part of the vocabulary"),
+                )
+                .arg(
+                    Arg::with_name("special-tokens")
+                        .long("special-tokens")
+                        .value_name("TOKENS")
+                        .use_delimiter(true)
+                        .multiple(true)
+                        .help("Comma separated list of special tokens"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .short("o")
+                        .value_name("OUTPUT_FILE")
+                        .default_value("tokenizer.json")
+                        .help("Where to save the trained tokenizer"),
+                )
+                .arg(
+                    Arg::with_name("pretty")
+                        .long("pretty")
+                        .help("Pretty print the saved tokenizer"),
+                )
+                .arg(
+                    Arg::with_name("quiet")
+                        .long("quiet")
+                        .short("q")
+                        .help("Do not show the progress bars"),
+                )
+                .arg(
+                    Arg::with_name("FILES")
+                        .help("The files to train on")
+                        .multiple(true)
                         .required(true),
                 ),
         )
-        .get_matches();
+        .subcommand(
+            SubCommand::with_name("validate")
+                .about(
+                    "Check that each line of the input is decoded back to its original form, and \
+                     print some statistics about the tokenization",
+                )
+                .arg(tokenizer_arg(true))
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("max-mismatches")
+                        .long("max-mismatches")
+                        .value_name("N")
+                        .default_value("10")
+                        .help("The maximum number of mismatches to print"),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help("Output the whole report as JSON"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("align")
+                .about(
+                    "Show how each line of the input is aligned with its normalized, \
+                     pre-tokenized and tokenized versions",
+                )
+                .arg(tokenizer_arg(true))
+                .arg(input_arg())
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help("Output JSON lines instead of tables"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("inspect")
+                .about("Print the pipeline and some vocabulary statistics of a tokenizer")
+                .arg(tokenizer_arg(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("convert")
+                .about(
+                    "Build a tokenizer.json from model files, export the model files of a \
+                     tokenizer.json, or convert it to and from the binary format",
+                )
+                .arg(tokenizer_arg(false).conflicts_with_all(&["vocab", "merges"]))
+                .arg(model_arg())
+                .arg(
+                    Arg::with_name("vocab")
+                        .long("vocab")
+                        .value_name("VOCAB_FILE")
+                        .help("Path to the vocabulary file, or the unigram.json of Unigram")
+                        .required_unless("tokenizer"),
+                )
+                .arg(
+                    Arg::with_name("merges")
+                        .long("merges")
+                        .value_name("MERGES_FILE")
+                        .help("Path to the merges.txt file, for BPE"),
+                )
+                .arg(
+                    Arg::with_name("unk-token")
+                        .long("unk-token")
+                        .value_name("TOKEN")
+                        .help("The unknown token of the model"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .short("o")
+                        .value_name("OUTPUT")
+                        .help(
+                            "The tokenizer.json file to write (default: tokenizer.json), or the \
+                             folder where to export the model files (default: .)",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("prefix")
+                        .long("prefix")
+                        .value_name("PREFIX")
+                        .help("Prefix of the exported model files"),
+                )
+                .arg(
+                    Arg::with_name("pretty")
+                        .long("pretty")
+                        .help("Pretty print the saved tokenizer"),
+                )
+                .arg(
+                    Arg::with_name("binary")
+                        .long("binary")
+                        .requires("tokenizer")
+                        .help(
+                            "Convert the tokenizer.json to the binary format (default output: \
+                             tokenizer.bin), or the binary tokenizer back to a tokenizer.json",
+                        ),
+                ),
+        )
+}
 
+/// Run the subcommand of the given matches, writing its results to `output`
+fn run(matches: &ArgMatches, output: &mut dyn Write) -> Result<()> {
     match matches.subcommand() {
-        ("shell", matches) => shell(matches.unwrap()),
-        (subcommand, _) => panic!("Unknown subcommand {}", subcommand),
+        ("shell", Some(matches)) => shell(matches),
+        ("encode", Some(matches)) => encode(matches, output),
+        ("decode", Some(matches)) => decode(matches, output),
+        ("train", Some(matches)) => train(matches, output),
+        ("validate", Some(matches)) => validate(matches, output),
+        ("align", Some(matches)) => align(matches, output),
+        ("inspect", Some(matches)) => inspect(matches, output),
+        ("convert", Some(matches)) => convert(matches, output),
+        (subcommand, _) => Err(format!("Unknown subcommand {}", subcommand).into()),
+    }
+}
+
+fn main() -> Result<()> {
+    let matches = app().get_matches();
+    let stdout = io::stdout();
+    let mut output = BufWriter::new(stdout.lock());
+    run(&matches, &mut output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn run_args(args: &[&str]) -> Result<String> {
+        let matches =
+            app().get_matches_from_safe(std::iter::once("tokenizers").chain(args.to_vec()))?;
+        let mut output = vec![];
+        run(&matches, &mut output)?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    /// Train a WordLevel tokenizer on a small corpus, returning its folder and its path
+    fn trained_tokenizer() -> (TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let corpus = dir.path().join("corpus.txt");
+        fs::write(&corpus, "hello world\nhello there\n").unwrap();
+        let path = dir.path().join("tokenizer.json");
+        let path = path.to_str().unwrap().to_owned();
+
+        let output = run_args(&[
+            "train",
+            "--model",
+            "wordlevel",
+            "--quiet",
+            "--special-tokens",
+            "[PAD],[CLS]",
+            "--output",
+            &path,
+            corpus.to_str().unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(output, format!("Saved the trained tokenizer to {}\n", path));
+        (dir, path)
+    }
+
+    #[test]
+    fn parse_args() {
+        // Missing or invalid arguments are reported by clap
+        assert!(run_args(&[]).is_err());
+        assert!(run_args(&["encode"]).is_err());
+        assert!(run_args(&["train", "--model", "unknown", "file.txt"]).is_err());
+        assert!(run_args(&["train", "--model", "bpe"]).is_err());
+        assert!(run_args(&["convert"]).is_err());
+        assert!(run_args(&["convert", "--vocab", "vocab.json", "--binary"]).is_err());
+
+        let matches = app()
+            .get_matches_from_safe(vec![
+                "tokenizers",
+                "train",
+                "--special-tokens",
+                "[CLS],[SEP]",
+                "a.txt",
+                "b.txt",
+            ])
+            .unwrap();
+        let matches = matches.subcommand_matches("train").unwrap();
+        assert_eq!(matches.value_of("model"), Some("bpe"));
+        assert_eq!(matches.value_of("output"), Some("tokenizer.json"));
+        assert_eq!(
+            matches
+                .values_of("special-tokens")
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec!["[CLS]", "[SEP]"]
+        );
+        assert_eq!(
+            matches.values_of("FILES").unwrap().collect::<Vec<_>>(),
+            vec!["a.txt", "b.txt"]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.json");
+        assert!(run_args(&["encode", "-t", missing.to_str().unwrap()]).is_err());
+
+        let vocab = dir.path().join("vocab.json");
+        fs::write(&vocab, r#"{"a": 0}"#).unwrap();
+        let err = run_args(&["convert", "--vocab", vocab.to_str().unwrap()]).unwrap_err();
+        assert_eq!(err.to_string(), "Must give a merges.txt file for BPE");
+        assert!(run_args(&["train", "--min-frequency", "many", "file.txt"]).is_err());
+    }
+
+    #[test]
+    fn parse_ids_formats() {
+        assert_eq!(parse_ids("1 2 3").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_ids("[1, 2, 3]").unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            parse_ids(r#"{"ids": [1, 2, 3], "tokens": ["a", "b", "c"]}"#).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(parse_ids("1 two").is_err());
+    }
+
+    #[test]
+    fn encode_decode() {
+        let (dir, tokenizer) = trained_tokenizer();
+        let input = dir.path().join("input.txt");
+        fs::write(&input, "hello world\nthere\n").unwrap();
+
+        let encoded = run_args(&["encode", "-t", &tokenizer, input.to_str().unwrap()]).unwrap();
+        let lines = encoded
+            .lines()
+            .map(|l| serde_json::from_str::<Value>(l).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["tokens"], json!(["hello", "world"]));
+        assert_eq!(lines[0]["offsets"], json!([[0, 5], [6, 11]]));
+        assert_eq!(lines[1]["tokens"], json!(["there"]));
+
+        let ids = dir.path().join("ids.txt");
+        fs::write(&ids, &encoded).unwrap();
+        let decoded = run_args(&["decode", "-t", &tokenizer, ids.to_str().unwrap()]).unwrap();
+        assert_eq!(decoded, "hello world\nthere\n");
+    }
+
+    #[test]
+    fn inspect_tokenizer() {
+        let (_dir, tokenizer) = trained_tokenizer();
+        let output = run_args(&["inspect", "-t", &tokenizer]).unwrap();
+        assert!(output.contains("Model:\t\t\tWordLevel\n"));
+        assert!(output.contains("Vocab size:\t\t6\n"));
+        assert!(output.contains("Added tokens:\t\t3 (3 special)\n"));
+    }
+
+    #[test]
+    fn convert_tokenizer() {
+        let (dir, tokenizer) = trained_tokenizer();
+
+        // tokenizer.json -> binary -> tokenizer.json
+        let binary = dir.path().join("tokenizer.bin");
+        let binary = binary.to_str().unwrap();
+        run_args(&["convert", "-t", &tokenizer, "--binary", "-o", binary]).unwrap();
+        assert!(is_binary(binary).unwrap());
+        let json = dir.path().join("converted.json");
+        let json = json.to_str().unwrap();
+        run_args(&["convert", "-t", binary, "--binary", "-o", json]).unwrap();
+        assert_eq!(
+            open_tokenizer(json).unwrap().get_vocab(true),
+            open_tokenizer(&tokenizer).unwrap().get_vocab(true)
+        );
+
+        // tokenizer.json -> model files -> tokenizer.json
+        let folder = dir.path().to_str().unwrap();
+        let output = run_args(&["convert", "-t", &tokenizer, "-o", folder]).unwrap();
+        let vocab = dir.path().join("vocab.json");
+        assert_eq!(output, format!("Saved {}\n", vocab.display()));
+        let rebuilt = dir.path().join("rebuilt.json");
+        let rebuilt = rebuilt.to_str().unwrap();
+        run_args(&[
+            "convert",
+            "--model",
+            "wordlevel",
+            "--vocab",
+            vocab.to_str().unwrap(),
+            "-o",
+            rebuilt,
+        ])
+        .unwrap();
+        assert_eq!(
+            open_tokenizer(rebuilt).unwrap().get_vocab(false),
+            open_tokenizer(&tokenizer).unwrap().get_vocab(false)
+        );
+    }
+
+    #[test]
+    fn convert_unigram() {
+        let dir = tempfile::tempdir().unwrap();
+        let vocab = vec![
+            ("<unk>".into(), 0.0),
+            ("a".into(), -1.0),
+            ("ab".into(), -2.0),
+        ];
+        let files = Unigram::from(vocab, 0)
+            .unwrap()
+            .save(dir.path(), None)
+            .unwrap();
+        let tokenizer = dir.path().join("tokenizer.json");
+        let tokenizer = tokenizer.to_str().unwrap();
+        run_args(&[
+            "convert",
+            "--model",
+            "unigram",
+            "--vocab",
+            files[0].to_str().unwrap(),
+            "-o",
+            tokenizer,
+        ])
+        .unwrap();
+
+        let tokenizer = open_tokenizer(tokenizer).unwrap();
+        assert!(matches!(tokenizer.get_model(), ModelWrapper::Unigram(_)));
+        assert_eq!(tokenizer.token_to_id("ab"), Some(2));
     }
 }
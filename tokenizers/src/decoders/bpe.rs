@@ -23,7 +23,15 @@ impl Default for BPEDecoder {
 }
 
 impl Decoder for BPEDecoder {
-    fn decode(&self, tokens: Vec<String>) -> Result<String> {
-        Ok(tokens.join("").replace(&self.suffix, " ").trim().to_owned())
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        let n = tokens.len().saturating_sub(1);
+        Ok(tokens
+            .into_iter()
+            .enumerate()
+            .map(|(i, token)| {
+                let replacement = if i == n { "" } else { " " };
+                token.replace(&self.suffix, replacement)
+            })
+            .collect())
     }
 }
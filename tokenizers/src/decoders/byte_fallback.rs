@@ -0,0 +1,96 @@
+use crate::tokenizer::{Decoder, Result};
+
+/// ByteFallback is a simple trick which converts tokens looking like `<0x61>`
+/// to pure bytes, and attempts to make them into a string. If the tokens
+/// cannot be decoded you will get � instead for each invalid sequence of bytes.
+/// Each run of byte tokens becomes a single token.
+#[derive(Copy, Clone, Debug)]
+pub struct ByteFallback;
+impl_serde_unit_struct!(ByteFallbackVisitor, ByteFallback);
+
+impl ByteFallback {
+    /// Parse the byte represented by a token like `<0x61>`, if any.
+    fn parse_byte(token: &str) -> Option<u8> {
+        if token.len() == 6 && token.starts_with("<0x") && token.ends_with('>') {
+            u8::from_str_radix(&token[3..5], 16).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl Decoder for ByteFallback {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        let mut output = Vec::with_capacity(tokens.len());
+        let mut bytes: Vec<u8> = vec![];
+
+        for token in tokens {
+            if let Some(byte) = Self::parse_byte(&token) {
+                bytes.push(byte);
+            } else {
+                if !bytes.is_empty() {
+                    output.push(String::from_utf8_lossy(&bytes).into_owned());
+                    bytes.clear();
+                }
+                output.push(token);
+            }
+        }
+        if !bytes.is_empty() {
+            output.push(String::from_utf8_lossy(&bytes).into_owned());
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let decoder = ByteFallback;
+        let res = decoder
+            .decode(vec!["Hey".into(), "friend!".into()])
+            .unwrap();
+        assert_eq!(res, "Heyfriend!");
+
+        let res = decoder.decode(vec!["<0x61>".into()]).unwrap();
+        assert_eq!(res, "a");
+
+        let res = decoder
+            .decode(vec![
+                "<0xE5>".into(),
+                "<0x8f>".into(),
+                "<0xab>".into(),
+                " is".into(),
+                "<0xC3>".into(),
+                "<0xA9>".into(),
+            ])
+            .unwrap();
+        assert_eq!(res, "叫 isé");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let decoder = ByteFallback;
+        let res = decoder
+            .decode(vec!["<0xE5>".into(), "<0x8f>".into(), "a".into()])
+            .unwrap();
+        assert_eq!(res, "\u{fffd}a");
+
+        let res = decoder
+            .decode(vec![
+                "<0xE5>".into(),
+                "<0xE5>".into(),
+                "<0x8f>".into(),
+                "<0xab>".into(),
+            ])
+            .unwrap();
+        assert_eq!(res, "\u{fffd}叫");
+
+        // Tokens that only look like bytes are left untouched
+        let res = decoder.decode(vec!["<0xZZ>".into()]).unwrap();
+        assert_eq!(res, "<0xZZ>");
+    }
+}
@@ -0,0 +1,149 @@
+use crate::decoders::wordpiece;
+use crate::tokenizer::{Decoder, Result};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone, Debug, Serialize)]
+/// The CTC (Connectionist Temporal Classification) decoder takes care of the output of
+/// speech-recognition models using character vocabularies: the consecutive duplicate tokens are
+/// collapsed, the `pad_token` (the CTC blank) is removed and the `word_delimiter_token` is
+/// replaced by a space.
+///
+/// Since the blanks separate the actual repeated characters, the `pad_token` must be kept
+/// until this decoder runs, by decoding with `skip_special_tokens` set to `false`.
+#[serde(tag = "type")]
+pub struct CTC {
+    /// The pad token used by CTC to delimit a new token
+    pad_token: String,
+    /// The word delimiter token, replaced by a space
+    word_delimiter_token: String,
+    /// Whether to cleanup some tokenization artifacts (spaces before punctuation, ...)
+    cleanup: bool,
+}
+
+impl CTC {
+    pub fn new(pad_token: String, word_delimiter_token: String, cleanup: bool) -> Self {
+        Self {
+            pad_token,
+            word_delimiter_token,
+            cleanup,
+        }
+    }
+}
+
+impl Default for CTC {
+    fn default() -> Self {
+        Self {
+            pad_token: "<pad>".to_string(),
+            word_delimiter_token: "|".to_string(),
+            cleanup: true,
+        }
+    }
+}
+
+impl Decoder for CTC {
+    /// The cleanup patterns span several tokens, so when `cleanup` is enabled the tokens
+    /// are fused into a single one before being cleaned up.
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        let tokens = tokens
+            .into_iter()
+            .dedup()
+            .filter(|token| token != &self.pad_token)
+            .map(|token| token.replace(&self.word_delimiter_token, " "))
+            .collect::<Vec<_>>();
+
+        if self.cleanup {
+            Ok(vec![wordpiece::cleanup(&tokens.concat())])
+        } else {
+            Ok(tokens)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(s: &str) -> Vec<String> {
+        s.split(' ').map(|t| t.to_owned()).collect()
+    }
+
+    #[test]
+    fn decode_repeated_characters() {
+        let decoder = CTC::default();
+        let res = decoder
+            .decode(tokens(
+                "<pad> <pad> h e e l l <pad> l o o o <pad> | | w o <pad> <pad> r l l d d",
+            ))
+            .unwrap();
+        assert_eq!(res, "hello world");
+
+        // Without any blank, repeated characters are collapsed
+        let res = decoder.decode(tokens("h e l l o")).unwrap();
+        assert_eq!(res, "helo");
+    }
+
+    #[test]
+    fn decode_cleanup() {
+        let input = tokens("i | d o | n o t | l l i k e | i t | .");
+        let res = CTC::default().decode(input.clone()).unwrap();
+        assert_eq!(res, "i don't like it.");
+
+        let res = CTC::new("<pad>".into(), "|".into(), false)
+            .decode(input)
+            .unwrap();
+        assert_eq!(res, "i do not like it .");
+    }
+
+    #[test]
+    fn decode_custom_tokens() {
+        let decoder = CTC::new("[PAD]".into(), "_".into(), false);
+        let res = decoder
+            .decode(tokens("a a [PAD] a _ _ b [PAD] [PAD] b"))
+            .unwrap();
+        assert_eq!(res, "aa bb");
+    }
+
+    #[test]
+    fn tokenizer_decode() {
+        use crate::models::wordlevel::WordLevel;
+        use crate::Tokenizer;
+
+        let vocab = ["<pad>", "|", "h", "e", "l", "o"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.to_string(), i as u32))
+            .collect();
+        let mut tokenizer = Tokenizer::new(
+            WordLevel::builder()
+                .vocab(vocab)
+                .unk_token("<pad>".into())
+                .build(),
+        );
+        tokenizer.with_decoder(CTC::default());
+
+        let hello = vec![2, 3, 3, 4, 0, 4, 5, 5];
+        assert_eq!(tokenizer.decode(hello.clone(), false).unwrap(), "hello");
+        assert_eq!(
+            tokenizer
+                .decode_batch(vec![hello, vec![2, 0, 2, 1, 5]], false)
+                .unwrap(),
+            vec!["hello", "hh o"]
+        );
+    }
+
+    #[test]
+    fn serialization() {
+        let decoder = CTC::default();
+        let decoder_s =
+            r#"{"type":"CTC","pad_token":"<pad>","word_delimiter_token":"|","cleanup":true}"#;
+        assert_eq!(serde_json::to_string(&decoder).unwrap(), decoder_s);
+        let deserialized: crate::decoders::DecoderWrapper =
+            serde_json::from_str(decoder_s).unwrap();
+        assert!(matches!(
+            deserialized,
+            crate::decoders::DecoderWrapper::CTC(_)
+        ));
+    }
+}
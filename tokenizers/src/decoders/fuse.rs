@@ -0,0 +1,28 @@
+use crate::tokenizer::{Decoder, Result};
+
+/// Fuse simply fuses every token into a single string.
+/// This is usually what we want as the last step of the decoding, but a `Decoder` that
+/// needs to work on the whole string can use it as a previous step in a `Sequence`.
+#[derive(Copy, Clone, Debug)]
+pub struct Fuse;
+impl_serde_unit_struct!(FuseVisitor, Fuse);
+
+impl Decoder for Fuse {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        Ok(vec![tokens.join("")])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let decoder = Fuse;
+        let res = decoder
+            .decode_chain(vec!["Hey".into(), " friend!".into()])
+            .unwrap();
+        assert_eq!(res, vec!["Hey friend!"]);
+    }
+}
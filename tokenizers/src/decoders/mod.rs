@@ -1,34 +1,84 @@
 pub mod bpe;
+pub mod byte_fallback;
+pub mod ctc;
+pub mod fuse;
+pub mod sequence;
+pub mod strip;
 pub mod wordpiece;
 
 // Re-export these as decoders
 pub use super::pre_tokenizers::byte_level;
 pub use super::pre_tokenizers::metaspace;
+pub use crate::normalizers::replace;
 
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
 use crate::decoders::bpe::BPEDecoder;
+use crate::decoders::byte_fallback::ByteFallback;
+use crate::decoders::ctc::CTC;
+use crate::decoders::fuse::Fuse;
+use crate::decoders::sequence::Sequence;
+use crate::decoders::strip::Strip;
 use crate::decoders::wordpiece::WordPiece;
+use crate::normalizers::replace::Replace;
 use crate::pre_tokenizers::byte_level::ByteLevel;
 use crate::pre_tokenizers::metaspace::Metaspace;
+use crate::utils::registry::{Custom, Registry};
 use crate::{Decoder, Result};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug)]
 #[serde(untagged)]
 pub enum DecoderWrapper {
     BPE(BPEDecoder),
     ByteLevel(ByteLevel),
     WordPiece(WordPiece),
     Metaspace(Metaspace),
+    ByteFallback(ByteFallback),
+    Sequence(Sequence),
+    Replace(Replace),
+    Strip(Strip),
+    Fuse(Fuse),
+    CTC(CTC),
+    Custom(CustomDecoder),
+}
+
+impl_serde_tagged_wrapper!(DecoderWrapper, "decoder", Custom(REGISTRY), {
+    "BPEDecoder" => BPE(BPEDecoder),
+    "ByteLevel" => ByteLevel(ByteLevel),
+    "WordPiece" => WordPiece(WordPiece),
+    "Metaspace" => Metaspace(Metaspace),
+    "ByteFallback" => ByteFallback(ByteFallback),
+    "Sequence" => Sequence(Sequence),
+    "Replace" => Replace(Replace),
+    "Strip" => Strip(Strip),
+    "Fuse" => Fuse(Fuse),
+    "CTC" => CTC(CTC),
+});
+
+/// A user-defined decoder, which can be saved and loaded once registered in the
+/// [`REGISTRY`](struct.REGISTRY.html).
+pub type CustomDecoder = Custom<dyn Decoder + Send + Sync>;
+
+lazy_static! {
+    /// The user-defined decoders, by the `type` they are serialized with.
+    pub static ref REGISTRY: Registry<dyn Decoder + Send + Sync> =
+        Registry::new("decoder", DecoderWrapper::BUILTIN_TYPES);
 }
 
 impl Decoder for DecoderWrapper {
-    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
         match self {
-            DecoderWrapper::BPE(bpe) => bpe.decode(tokens),
-            DecoderWrapper::ByteLevel(bl) => bl.decode(tokens),
-            DecoderWrapper::Metaspace(ms) => ms.decode(tokens),
-            DecoderWrapper::WordPiece(wp) => wp.decode(tokens),
+            DecoderWrapper::BPE(bpe) => bpe.decode_chain(tokens),
+            DecoderWrapper::ByteLevel(bl) => bl.decode_chain(tokens),
+            DecoderWrapper::Metaspace(ms) => ms.decode_chain(tokens),
+            DecoderWrapper::WordPiece(wp) => wp.decode_chain(tokens),
+            DecoderWrapper::ByteFallback(bf) => bf.decode_chain(tokens),
+            DecoderWrapper::Sequence(seq) => seq.decode_chain(tokens),
+            DecoderWrapper::Replace(rep) => rep.decode_chain(tokens),
+            DecoderWrapper::Strip(strip) => strip.decode_chain(tokens),
+            DecoderWrapper::Fuse(fuse) => fuse.decode_chain(tokens),
+            DecoderWrapper::CTC(ctc) => ctc.decode_chain(tokens),
+            DecoderWrapper::Custom(custom) => custom.decode_chain(tokens),
         }
     }
 }
@@ -37,3 +87,10 @@ impl_enum_from!(BPEDecoder, DecoderWrapper, BPE);
 impl_enum_from!(ByteLevel, DecoderWrapper, ByteLevel);
 impl_enum_from!(Metaspace, DecoderWrapper, Metaspace);
 impl_enum_from!(WordPiece, DecoderWrapper, WordPiece);
+impl_enum_from!(ByteFallback, DecoderWrapper, ByteFallback);
+impl_enum_from!(Sequence, DecoderWrapper, Sequence);
+impl_enum_from!(Replace, DecoderWrapper, Replace);
+impl_enum_from!(Strip, DecoderWrapper, Strip);
+impl_enum_from!(Fuse, DecoderWrapper, Fuse);
+impl_enum_from!(CTC, DecoderWrapper, CTC);
+impl_enum_from!(CustomDecoder, DecoderWrapper, Custom);
@@ -0,0 +1,71 @@
+use crate::decoders::DecoderWrapper;
+use crate::tokenizer::{Decoder, Result};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone, Debug, Serialize)]
+/// Runs each `Decoder` in order, giving the tokens produced by one of them to the next one.
+#[serde(tag = "type")]
+pub struct Sequence {
+    decoders: Vec<DecoderWrapper>,
+}
+
+impl Sequence {
+    pub fn new(decoders: Vec<DecoderWrapper>) -> Self {
+        Self { decoders }
+    }
+}
+
+impl Decoder for Sequence {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        self.decoders
+            .iter()
+            .try_fold(tokens, |tokens, decoder| decoder.decode_chain(tokens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoders::byte_fallback::ByteFallback;
+    use crate::decoders::fuse::Fuse;
+    use crate::decoders::strip::Strip;
+    use crate::normalizers::replace::Replace;
+
+    #[test]
+    fn decode() {
+        let decoder = Sequence::new(vec![
+            Replace::new("▁", " ").unwrap().into(),
+            ByteFallback.into(),
+            Fuse.into(),
+            Strip::new(' ', 1, 0).into(),
+        ]);
+        let tokens = vec!["▁Hey", "▁fr", "<0x69>", "end", "<0xE5>", "<0x8f>", "<0xab>"]
+            .into_iter()
+            .map(|t| t.to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(decoder.decode(tokens).unwrap(), "Hey friend叫");
+    }
+
+    #[test]
+    fn serialization() {
+        let decoder: DecoderWrapper = Sequence::new(vec![
+            ByteFallback.into(),
+            Fuse.into(),
+            Strip::new(' ', 1, 0).into(),
+        ])
+        .into();
+        let decoder_s = r#"{"type":"Sequence","decoders":[{"type":"ByteFallback"},{"type":"Fuse"},{"type":"Strip","content":" ","start":1,"stop":0}]}"#;
+        assert_eq!(serde_json::to_string(&decoder).unwrap(), decoder_s);
+
+        let deserialized: DecoderWrapper = serde_json::from_str(decoder_s).unwrap();
+        match deserialized {
+            DecoderWrapper::Sequence(seq) => {
+                assert!(matches!(seq.decoders[0], DecoderWrapper::ByteFallback(_)));
+                assert!(matches!(seq.decoders[1], DecoderWrapper::Fuse(_)));
+                assert!(matches!(seq.decoders[2], DecoderWrapper::Strip(_)));
+            }
+            _ => panic!("Expected a Sequence"),
+        }
+    }
+}
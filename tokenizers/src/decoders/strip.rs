@@ -0,0 +1,66 @@
+use crate::tokenizer::{Decoder, Result};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone, Debug, Serialize)]
+/// Strip removes up to `start` occurrences of the `content` char at the beginning of
+/// each token, and up to `stop` at its end.
+#[serde(tag = "type")]
+pub struct Strip {
+    content: char,
+    start: usize,
+    stop: usize,
+}
+
+impl Strip {
+    pub fn new(content: char, start: usize, stop: usize) -> Self {
+        Self {
+            content,
+            start,
+            stop,
+        }
+    }
+}
+
+impl Decoder for Strip {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        Ok(tokens
+            .into_iter()
+            .map(|token| {
+                let chars = token.chars().collect::<Vec<_>>();
+                let start_cut = chars
+                    .iter()
+                    .take(self.start)
+                    .take_while(|c| **c == self.content)
+                    .count();
+                let stop_cut = chars[start_cut..]
+                    .iter()
+                    .rev()
+                    .take(self.stop)
+                    .take_while(|c| **c == self.content)
+                    .count();
+                chars[start_cut..chars.len() - stop_cut].iter().collect()
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let decoder = Strip::new('H', 1, 0);
+        let res = decoder
+            .decode_chain(vec!["Hey".into(), " HHfriend!".into(), "HHH".into()])
+            .unwrap();
+        assert_eq!(res, vec!["ey", " HHfriend!", "HH"]);
+
+        let decoder = Strip::new('y', 0, 2);
+        let res = decoder
+            .decode_chain(vec!["Hey".into(), "yyy".into(), "".into()])
+            .unwrap();
+        assert_eq!(res, vec!["He", "y", ""]);
+    }
+}
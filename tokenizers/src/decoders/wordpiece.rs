@@ -28,24 +28,72 @@ impl Default for WordPiece {
     }
 }
 
+/// Cleanup some tokenization artifacts, like spaces before punctuation
+pub(crate) fn cleanup(dirty_input: &str) -> String {
+    dirty_input
+        .replace(" .", ".")
+        .replace(" ?", "?")
+        .replace(" !", "!")
+        .replace(" ,", ",")
+        .replace(" ' ", "'")
+        .replace(" n't", "n't")
+        .replace(" 'm", "'m")
+        .replace(" do not", " don't")
+        .replace(" 's", "'s")
+        .replace(" 've", "'ve")
+        .replace(" 're", "'re")
+}
+
 impl Decoder for WordPiece {
-    fn decode(&self, tokens: Vec<String>) -> Result<String> {
-        let mut output = tokens.join(" ").replace(&format!(" {}", self.prefix), "");
+    /// The cleanup patterns can span several tokens, so when `cleanup` is enabled the tokens
+    /// are fused into a single one before being cleaned up.
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        let tokens = tokens
+            .into_iter()
+            .enumerate()
+            .map(|(i, token)| {
+                if i == 0 {
+                    token
+                } else if let Some(stripped) = token.strip_prefix(&self.prefix) {
+                    stripped.to_owned()
+                } else {
+                    format!(" {}", token)
+                }
+            })
+            .collect::<Vec<_>>();
+
         if self.cleanup {
-            output = output
-                .replace(" .", ".")
-                .replace(" ?", "?")
-                .replace(" !", "!")
-                .replace(" ,", ",")
-                .replace(" ' ", "'")
-                .replace(" n't", "n't")
-                .replace(" 'm", "'m")
-                .replace(" do not", " don't")
-                .replace(" 's", "'s")
-                .replace(" 've", "'ve")
-                .replace(" 're", "'re");
+            Ok(vec![cleanup(&tokens.concat())])
+        } else {
+            Ok(tokens)
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let decoder = WordPiece::new("##".into(), false);
+        let res = decoder
+            .decode_chain(vec!["I".into(), "do".into(), "n".into(), "##ot".into()])
+            .unwrap();
+        assert_eq!(res, vec!["I", " do", " n", "ot"]);
+    }
 
-        Ok(output)
+    #[test]
+    fn cleanup_across_tokens() {
+        let decoder = WordPiece::default();
+        let tokens = vec![
+            "I".into(),
+            "do".into(),
+            "not".into(),
+            "like".into(),
+            ".".into(),
+        ];
+        assert_eq!(decoder.decode(tokens.clone()).unwrap(), "I don't like.");
+        assert_eq!(decoder.decode_chain(tokens).unwrap(), vec!["I don't like."]);
     }
 }
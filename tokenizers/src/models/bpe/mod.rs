@@ -24,6 +24,8 @@ pub enum Error {
     MergeTokenOutOfVocabulary(String),
     /// If the provided unk token is out of vocabulary
     UnkTokenOutOfVocabulary(String),
+    /// If a token of a merge contains a space, and can't be saved in a merges.txt file
+    MergeTokenWithSpace(String),
     /// Dropout not between 0 and 1.
     InvalidDropout,
 }
@@ -53,6 +55,11 @@ impl std::fmt::Display for Error {
             Error::UnkTokenOutOfVocabulary(token) => {
                 write!(f, "Unk token `{}` not found in the vocabulary", token)
             }
+            Error::MergeTokenWithSpace(token) => write!(
+                f,
+                "Token `{}` contains a space, and can't be saved in a merges.txt file",
+                token
+            ),
             Error::InvalidDropout => write!(f, "Dropout should be between 0 and 1"),
         }
     }
@@ -1,15 +1,16 @@
-use super::{super::OrderedVocabIter, Error, Pair, Word};
-use crate::tokenizer::{Model, Result, Token};
-use crate::utils::cache::{Cache, DEFAULT_CACHE_CAPACITY};
+use super::{super::prune_vocab, super::OrderedVocabIter, Error, Pair, Word};
+use crate::tokenizer::{MappedTables, Model, Result, Token};
+use crate::utils::cache::{Cache, CacheStats, DEFAULT_CACHE_CAPACITY};
 use crate::utils::iter::ResultShunt;
 use serde_json::Value;
 use std::borrow::Cow;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::File,
     io::prelude::*,
     io::{BufRead, BufReader},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 pub type Vocab = HashMap<String, u32>;
@@ -27,6 +28,8 @@ struct Config {
     continuing_subword_prefix: Option<String>,
     end_of_word_suffix: Option<String>,
     fuse_unk: bool,
+    byte_fallback: bool,
+    mapped: Option<Arc<MappedTables>>,
 }
 
 /// A `BpeBuilder` can be used to create a `BPE` model with a custom configuration.
@@ -47,6 +50,8 @@ impl Default for BpeBuilder {
                 continuing_subword_prefix: None,
                 end_of_word_suffix: None,
                 fuse_unk: false,
+                byte_fallback: false,
+                mapped: None,
             },
         }
     }
@@ -107,6 +112,18 @@ impl BpeBuilder {
         self
     }
 
+    /// Set the `byte_fallback` option.
+    pub fn byte_fallback(mut self, byte_fallback: bool) -> Self {
+        self.config.byte_fallback = byte_fallback;
+        self
+    }
+
+    /// Use the tables of a binary tokenizer instead of the vocab and the merges.
+    pub(crate) fn mapped_tables(mut self, tables: Arc<MappedTables>) -> Self {
+        self.config.mapped = Some(tables);
+        self
+    }
+
     /// Returns a `BPE` model that uses the `BpeBuilder`'s configuration.
     pub fn build(mut self) -> Result<BPE> {
         // Validate dropout.
@@ -135,11 +152,11 @@ impl BpeBuilder {
         };
 
         let vocab = self.config.vocab;
-        let prefix_len = if let Some(prefix) = &self.config.continuing_subword_prefix {
-            prefix.len()
-        } else {
-            0
-        };
+        let prefix = self
+            .config
+            .continuing_subword_prefix
+            .as_deref()
+            .unwrap_or("");
         let merge_map: MergeMap = self
             .config
             .merges
@@ -152,7 +169,7 @@ impl BpeBuilder {
                 let b_id = vocab
                     .get(&b)
                     .ok_or_else(|| Error::MergeTokenOutOfVocabulary(b.to_owned()))?;
-                let new_token = format!("{}{}", a, &b[prefix_len..]);
+                let new_token = format!("{}{}", a, b.strip_prefix(prefix).unwrap_or(&b));
                 let new_id = vocab
                     .get(&new_token)
                     .ok_or(Error::MergeTokenOutOfVocabulary(new_token))?;
@@ -172,12 +189,13 @@ impl BpeBuilder {
             continuing_subword_prefix: self.config.continuing_subword_prefix,
             end_of_word_suffix: self.config.end_of_word_suffix,
             fuse_unk: self.config.fuse_unk,
+            byte_fallback: self.config.byte_fallback,
+            mapped: self.config.mapped,
         })
     }
 }
 
 /// A [Byte Pair Encoding](https://www.aclweb.org/anthology/P16-1162/) model.
-#[derive(PartialEq)]
 pub struct BPE {
     /// The vocabulary assigns a number to each token.
     pub(crate) vocab: Vocab,
@@ -198,6 +216,24 @@ pub struct BPE {
     pub(super) end_of_word_suffix: Option<String>,
     /// Do multiple unk tokens get fused
     pub(super) fuse_unk: bool,
+    /// Whether unknown chars should be decomposed into their `<0xNN>` byte tokens, instead of
+    /// using the unknown token
+    pub(super) byte_fallback: bool,
+    /// The tables of the binary tokenizer the model was loaded from, if any. The tokens and the
+    /// merges are then looked up in these tables, and the maps above stay empty.
+    mapped: Option<Arc<MappedTables>>,
+}
+
+impl PartialEq for BPE {
+    fn eq(&self, other: &Self) -> bool {
+        self.dropout == other.dropout
+            && self.unk_token == other.unk_token
+            && self.continuing_subword_prefix == other.continuing_subword_prefix
+            && self.end_of_word_suffix == other.end_of_word_suffix
+            && self.fuse_unk == other.fuse_unk
+            && self.byte_fallback == other.byte_fallback
+            && self.maps() == other.maps()
+    }
 }
 
 impl std::fmt::Debug for BPE {
@@ -208,8 +244,9 @@ impl std::fmt::Debug for BPE {
             .field("continuing_subword_prefix", &self.continuing_subword_prefix)
             .field("end_of_word_suffix", &self.end_of_word_suffix)
             .field("fuse_unk", &self.fuse_unk)
-            .field("vocab", &self.vocab.len())
-            .field("merges", &self.merges.len())
+            .field("byte_fallback", &self.byte_fallback)
+            .field("vocab", &self.get_vocab_size())
+            .field("merges", &self.merges_len())
             .finish()
     }
 }
@@ -235,6 +272,8 @@ impl Clone for BPE {
             continuing_subword_prefix: self.continuing_subword_prefix.clone(),
             end_of_word_suffix: self.end_of_word_suffix.clone(),
             fuse_unk: self.fuse_unk,
+            byte_fallback: self.byte_fallback,
+            mapped: self.mapped.clone(),
         }
     }
 }
@@ -318,8 +357,84 @@ impl BPE {
         }
     }
 
+    /// The reversed vocabulary and the merges, copied out of the mapped tables if needed
+    pub(crate) fn maps(&self) -> (Cow<'_, VocabR>, Cow<'_, MergeMap>) {
+        match &self.mapped {
+            Some(tables) => {
+                let vocab_r = (0..tables.len() as u32)
+                    .filter_map(|id| Some((id, tables.id_to_token(id)?.to_owned())))
+                    .collect();
+                let merges = tables
+                    .merges()
+                    .enumerate()
+                    .map(|(rank, (a, b, new_id))| ((a, b), (rank as u32, new_id)))
+                    .collect();
+                (Cow::Owned(vocab_r), Cow::Owned(merges))
+            }
+            None => (Cow::Borrowed(&self.vocab_r), Cow::Borrowed(&self.merges)),
+        }
+    }
+
+    /// Copy the mapped tables into the maps, before modifying them
+    fn own_tables(&mut self) {
+        if self.mapped.is_some() {
+            let (vocab_r, merges) = self.maps();
+            let (vocab_r, merges) = (vocab_r.into_owned(), merges.into_owned());
+            self.vocab = vocab_r.iter().map(|(id, t)| (t.clone(), *id)).collect();
+            self.vocab_r = vocab_r;
+            self.merges = merges;
+            self.mapped = None;
+        }
+    }
+
+    fn merges_len(&self) -> usize {
+        match &self.mapped {
+            Some(tables) => tables.merges_len(),
+            None => self.merges.len(),
+        }
+    }
+
+    /// Find the merge of the given pair, as its rank and the id it produces
+    fn merge(&self, pair: &Pair) -> Option<(u32, u32)> {
+        match &self.mapped {
+            Some(tables) => tables.merge(*pair),
+            None => self.merges.get(pair).copied(),
+        }
+    }
+
+    /// The capacity of the cache, 0 meaning that there is no cache.
+    pub fn get_cache_capacity(&self) -> usize {
+        self.cache.as_ref().map_or(0, |cache| cache.capacity)
+    }
+
+    /// Get the statistics of the cache, if any.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Fill the cache with the given words, as they would be received by `tokenize` (ie. after
+    /// the normalization and pre-tokenization).
+    pub fn warm_cache<I, S>(&self, words: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        if let Some(ref cache) = self.cache {
+            for word in words {
+                let word = word.as_ref();
+                if !word.is_empty() {
+                    cache.set(word.to_owned(), self.merge_word(word)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn get_vocab(&self) -> &Vocab {
-        &self.vocab
+        match &self.mapped {
+            Some(tables) => tables.vocab(),
+            None => &self.vocab,
+        }
     }
 
     pub fn get_unk_token(&self) -> &Option<String> {
@@ -330,6 +445,17 @@ impl BPE {
         &self.continuing_subword_prefix
     }
 
+    /// Find the ids of the `<0xNN>` byte tokens representing the given char, if `byte_fallback`
+    /// is activated and all of them are part of the vocabulary.
+    fn byte_fallback_ids(&self, c: &str) -> Option<Vec<u32>> {
+        if !self.byte_fallback {
+            return None;
+        }
+        c.bytes()
+            .map(|b| self.token_to_id(&format!("<0x{:02X}>", b)))
+            .collect()
+    }
+
     fn merge_word(&self, w: &str) -> Result<Word> {
         let mut indices = w.char_indices().map(|(idx, _)| idx).peekable();
         let mut word = Word::with_capacity(w.len());
@@ -353,12 +479,20 @@ impl BPE {
                 )
             };
 
-            if let Some(id) = self.vocab.get(s.as_ref()) {
+            if let Some(id) = self.token_to_id(&s) {
                 if let Some((unk_id, unk_len)) = unk {
                     word.add(unk_id, unk_len);
                     unk = None;
                 }
-                word.add(*id, byte_len);
+                word.add(id, byte_len);
+            } else if let Some(byte_ids) = self.byte_fallback_ids(&w[i..i + byte_len]) {
+                if let Some((unk_id, unk_len)) = unk {
+                    word.add(unk_id, unk_len);
+                    unk = None;
+                }
+                for id in byte_ids {
+                    word.add(id, 1);
+                }
             } else if let Some(unk_token) = &self.unk_token {
                 unk = match (unk, self.fuse_unk) {
                     (Some((unk_id, unk_len)), true) => {
@@ -369,16 +503,14 @@ impl BPE {
                         // Do not fuse unk, add the previous one
                         word.add(unk_id, unk_len);
                         Some((
-                            *self.vocab.get(unk_token).ok_or_else(|| {
+                            self.token_to_id(unk_token).ok_or_else(|| {
                                 Error::UnkTokenOutOfVocabulary(unk_token.to_owned())
                             })?,
                             byte_len,
                         ))
                     }
                     _ => Some((
-                        *self
-                            .vocab
-                            .get(unk_token)
+                        self.token_to_id(unk_token)
                             .ok_or_else(|| Error::UnkTokenOutOfVocabulary(unk_token.to_owned()))?,
                         byte_len,
                     )),
@@ -389,23 +521,38 @@ impl BPE {
             word.add(unk_id, unk_len);
         }
 
-        word.merge_all(&self.merges, self.dropout);
+        word.merge_all(|pair| self.merge(pair), self.dropout);
 
         Ok(word)
     }
 
-    fn word_to_tokens<'a, 'b: 'a>(&'a self, word: &'b Word) -> impl Iterator<Item = Token> + 'a {
+    fn word_to_tokens<'a, 'b: 'a>(
+        &'a self,
+        word: &'b Word,
+        sequence: &'a str,
+    ) -> impl Iterator<Item = Token> + 'a {
         word.get_chars_iter()
             .zip(word.get_offsets_iter())
-            .map(move |(id, offsets)| Token::new(id, self.vocab_r[&id].clone(), offsets))
+            .map(move |(id, (start, end))| {
+                // Byte tokens may cover only a part of a char. We want them to point to the
+                // entire char they come from.
+                let start = (0..=start)
+                    .rev()
+                    .find(|i| sequence.is_char_boundary(*i))
+                    .unwrap_or(0);
+                let end = (end..=sequence.len())
+                    .find(|i| sequence.is_char_boundary(*i))
+                    .unwrap_or(sequence.len());
+                Token::new(id, self.id_to_token(id).unwrap().to_owned(), (start, end))
+            })
     }
 
     fn tokenize_with_cache(&self, sequence: &str) -> Result<Vec<Token>> {
         if let Some(ref hit) = self.cache.as_ref().and_then(|c| c.get(sequence)) {
-            Ok(self.word_to_tokens(hit).collect())
+            Ok(self.word_to_tokens(hit, sequence).collect())
         } else {
             let word = self.merge_word(sequence)?;
-            let ret = self.word_to_tokens(&word).collect();
+            let ret = self.word_to_tokens(&word, sequence).collect();
             if let Some(ref cache) = self.cache {
                 cache.set(sequence.to_owned(), word);
             }
@@ -416,11 +563,72 @@ impl BPE {
 
 impl Model for BPE {
     fn get_vocab(&self) -> &HashMap<String, u32> {
-        &self.vocab
+        BPE::get_vocab(self)
     }
 
     fn get_vocab_size(&self) -> usize {
-        self.vocab.len()
+        match &self.mapped {
+            Some(tables) => tables.len(),
+            None => self.vocab.len(),
+        }
+    }
+
+    fn unk_token(&self) -> Option<&str> {
+        self.unk_token.as_deref()
+    }
+
+    fn prune(&mut self, ids: &HashSet<u32>) -> Result<HashMap<u32, u32>> {
+        // The merges using a removed token are removed too, and so are the tokens that can only
+        // be produced by these merges
+        self.own_tables();
+        let mut removed = ids.clone();
+        loop {
+            let (kept, dropped): (Vec<_>, Vec<_>) =
+                self.merges.iter().partition(|((a, b), (_, new_id))| {
+                    !removed.contains(a) && !removed.contains(b) && !removed.contains(new_id)
+                });
+            let produced = kept
+                .into_iter()
+                .map(|(_, (_, new_id))| *new_id)
+                .collect::<HashSet<_>>();
+            let unreachable = dropped
+                .into_iter()
+                .map(|(_, (_, new_id))| *new_id)
+                .filter(|id| !produced.contains(id) && !removed.contains(id))
+                .collect::<Vec<_>>();
+            if unreachable.is_empty() {
+                break;
+            }
+            removed.extend(unreachable);
+        }
+
+        let unk_id = self
+            .unk_token
+            .as_ref()
+            .and_then(|unk| self.vocab.get(unk).copied());
+        let (vocab, mapping) = prune_vocab(&self.vocab_r, &removed, unk_id)?;
+
+        let mut merges = self
+            .merges
+            .iter()
+            .filter_map(|((a, b), (rank, new_id))| {
+                let pair = (*mapping.get(a)?, *mapping.get(b)?);
+                Some((*rank, pair, *mapping.get(new_id)?))
+            })
+            .collect::<Vec<_>>();
+        merges.sort_unstable();
+        self.merges = merges
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (_, pair, new_id))| (pair, (rank as u32, new_id)))
+            .collect();
+        self.vocab_r = vocab.iter().map(|(t, id)| (*id, t.clone())).collect();
+        self.vocab = vocab;
+        if let Some(ref cache) = self.cache {
+            cache.clear();
+        }
+
+        Ok(mapping)
     }
 
     fn tokenize(&self, sequence: &str) -> Result<Vec<Token>> {
@@ -432,16 +640,22 @@ impl Model for BPE {
             self.tokenize_with_cache(sequence)
         } else {
             let word = self.merge_word(sequence)?;
-            Ok(self.word_to_tokens(&word).collect())
+            Ok(self.word_to_tokens(&word, sequence).collect())
         }
     }
 
     fn token_to_id(&self, token: &str) -> Option<u32> {
-        self.vocab.get(token).copied()
+        match &self.mapped {
+            Some(tables) => tables.token_to_id(token),
+            None => self.vocab.get(token).copied(),
+        }
     }
 
     fn id_to_token(&self, id: u32) -> Option<&str> {
-        self.vocab_r.get(&id).map(String::as_ref)
+        match &self.mapped {
+            Some(tables) => tables.id_to_token(id),
+            None => self.vocab_r.get(&id).map(String::as_ref),
+        }
     }
 
     fn save(&self, folder: &Path, name: Option<&str>) -> Result<Vec<PathBuf>> {
@@ -450,16 +664,6 @@ impl Model for BPE {
             None => "vocab.json".to_string(),
         };
 
-        // Write vocab.json
-        let vocab_path: PathBuf = [folder, Path::new(vocab_file_name.as_str())]
-            .iter()
-            .collect();
-        let mut vocab_file = File::create(&vocab_path)?;
-        let order_vocab_iter = OrderedVocabIter::new(&self.vocab_r);
-        let serialized = serde_json::to_string(&order_vocab_iter)?;
-        vocab_file.write_all(&serialized.as_bytes())?;
-
-        // Write merges.txt
         let merges_file_name = match name {
             Some(name) => format!("{}-merges.txt", name),
             None => "merges.txt".to_string(),
@@ -468,19 +672,39 @@ impl Model for BPE {
         let merges_path: PathBuf = [folder, Path::new(merges_file_name.as_str())]
             .iter()
             .collect();
-        let mut merges_file = File::create(&merges_path)?;
-        let mut merges: Vec<(&Pair, &u32)> = self
-            .merges
+        let (vocab_r, merges) = self.maps();
+        let mut merges: Vec<(&Pair, &u32)> = merges
             .iter()
             .map(|(pair, (rank, _))| (pair, rank))
             .collect();
         merges.sort_unstable_by_key(|k| *k.1);
+        // The tokens are separated by a space in merges.txt, so we check them before writing
+        // any file
+        if let Some(token) = merges
+            .iter()
+            .flat_map(|(pair, _)| vec![&vocab_r[&pair.0], &vocab_r[&pair.1]])
+            .find(|token| token.contains(' '))
+        {
+            return Err(Error::MergeTokenWithSpace(token.to_owned()).into());
+        }
+
+        // Write vocab.json
+        let vocab_path: PathBuf = [folder, Path::new(vocab_file_name.as_str())]
+            .iter()
+            .collect();
+        let mut vocab_file = File::create(&vocab_path)?;
+        let order_vocab_iter = OrderedVocabIter::new(&vocab_r);
+        let serialized = serde_json::to_string(&order_vocab_iter)?;
+        vocab_file.write_all(&serialized.as_bytes())?;
+
+        // Write merges.txt
+        let mut merges_file = File::create(&merges_path)?;
         merges_file.write_all(b"#version: 0.2 - Trained by `huggingface/tokenizers`\n")?;
         merges_file.write_all(
             &merges
                 .into_iter()
                 .flat_map(|(pair, _)| {
-                    format!("{} {}\n", self.vocab_r[&pair.0], self.vocab_r[&pair.1]).into_bytes()
+                    format!("{} {}\n", vocab_r[&pair.0], vocab_r[&pair.1]).into_bytes()
                 })
                 .collect::<Vec<_>>()[..],
         )?;
@@ -573,6 +797,135 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_byte_fallback() {
+        let vocab: Vocab = [
+            ("<unk>".into(), 0),
+            ("<0xC3>".into(), 1),
+            ("<0xA9>".into(), 2),
+            ("a".into(), 3),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let bpe = BpeBuilder::default()
+            .vocab_and_merges(vocab, vec![])
+            .unk_token("<unk>".to_string())
+            .byte_fallback(true)
+            .build()
+            .unwrap();
+
+        // Each byte token points to the whole char
+        let tokens = bpe.tokenize("aé").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(3u32, "a".into(), (0, 1)),
+                Token::new(1u32, "<0xC3>".into(), (1, 3)),
+                Token::new(2u32, "<0xA9>".into(), (1, 3)),
+            ]
+        );
+
+        // We use the unk token if some byte tokens are missing
+        let tokens = bpe.tokenize("c").unwrap();
+        assert_eq!(tokens, vec![Token::new(0u32, "<unk>".into(), (0, 1))]);
+    }
+
+    #[test]
+    fn test_cache() {
+        let vocab: Vocab = [("a".into(), 0), ("b".into(), 1), ("ab".into(), 2)]
+            .iter()
+            .cloned()
+            .collect();
+        let merges = vec![("a".to_string(), "b".to_string())];
+        let bpe = BpeBuilder::default()
+            .vocab_and_merges(vocab, merges)
+            .cache_capacity(2)
+            .build()
+            .unwrap();
+
+        bpe.warm_cache(vec!["ab", "ba"]).unwrap();
+        bpe.tokenize("ab").unwrap();
+        bpe.tokenize("aab").unwrap();
+        bpe.tokenize("ab").unwrap();
+        let stats = bpe.cache_stats().unwrap();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 1);
+        // "aab" evicted "ba"
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.size, 2);
+        assert_eq!(stats.capacity, 2);
+
+        // The capacity is kept when serializing
+        let data = serde_json::to_string(&bpe).unwrap();
+        let reconstructed: BPE = serde_json::from_str(&data).unwrap();
+        assert_eq!(reconstructed.get_cache_capacity(), 2);
+
+        let bpe = BpeBuilder::default().cache_capacity(0).build().unwrap();
+        assert!(bpe.cache_stats().is_none());
+        assert_eq!(bpe.get_cache_capacity(), 0);
+    }
+
+    #[test]
+    fn test_serialize_merges_with_spaces() {
+        let vocab: Vocab = [("a".into(), 0), (" b".into(), 1), ("a b".into(), 2)]
+            .iter()
+            .cloned()
+            .collect();
+        let merges = vec![("a".to_string(), " b".to_string())];
+        let bpe = BpeBuilder::default()
+            .vocab_and_merges(vocab, merges)
+            .build()
+            .unwrap();
+
+        let data = serde_json::to_value(&bpe).unwrap();
+        assert_eq!(data["merges"], serde_json::json!([["a", " b"]]));
+        let reconstructed: BPE = serde_json::from_value(data).unwrap();
+        assert_eq!(reconstructed, bpe);
+
+        // These merges can't be saved in a merges.txt file, and nothing is written
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let err = bpe.save(tmp_dir.path(), None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Token ` b` contains a space, and can't be saved in a merges.txt file"
+        );
+        assert_eq!(std::fs::read_dir(tmp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_deserialize_merges() {
+        // The merges used to be "a b" strings
+        let legacy = r#"{"vocab":{"a":0,"b":1,"ab":2},"merges":["a b"]}"#;
+        let bpe: BPE = serde_json::from_str(legacy).unwrap();
+        assert_eq!(bpe.merges[&(0, 1)], (0, 2));
+
+        // The merges and their result must be part of the vocabulary
+        let err = serde_json::from_str::<BPE>(r#"{"vocab":{"a":0,"b":1},"merges":[["a","b"]]}"#)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Token `ab` out of vocabulary"));
+        let err = serde_json::from_str::<BPE>(r#"{"vocab":{"a":0,"ab":2},"merges":[["a","b"]]}"#)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Token `b` out of vocabulary"));
+        let err = serde_json::from_str::<BPE>(r#"{"vocab":{"a":0},"merges":[["a"]]}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid merges: invalid length 1, expected a pair of tokens, or a \"a b\" string \
+             at line 1 column 34"
+        );
+        let err =
+            serde_json::from_str::<BPE>(r#"{"vocab":{"a":0},"merges":["a b c"]}"#).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "invalid merges: invalid value: string \"a b c\", expected a pair of tokens, or a \
+             \"a b\" string"
+        ));
+
+        // Both formats can be mixed
+        let mixed = r#"{"vocab":{"a":0,"b":1,"ab":2,"aab":3},"merges":["a b",["a","ab"]]}"#;
+        let bpe: BPE = serde_json::from_str(mixed).unwrap();
+        assert_eq!(bpe.merges[&(0, 2)], (1, 3));
+    }
+
     #[test]
     // Test tokenization. With dropout set to 0 tokenization is deterministic,
     // so we know exactly what the result should be.
@@ -1,6 +1,7 @@
-use super::{super::OrderedVocabIter, convert_merges_to_hashmap, BpeBuilder, Pair, BPE};
+use super::{super::OrderedVocabIter, BpeBuilder, Pair, BPE};
+use crate::utils::tagged::with_context;
 use serde::{
-    de::{Error, MapAccess, Visitor},
+    de::{Error, IgnoredAny, MapAccess, SeqAccess, Unexpected, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -11,30 +12,33 @@ impl Serialize for BPE {
     where
         S: Serializer,
     {
-        let mut model = serializer.serialize_struct("BPE", 6)?;
+        let mut model = serializer.serialize_struct("BPE", 10)?;
 
         // Start by small fields
+        model.serialize_field("type", "BPE")?;
         model.serialize_field("dropout", &self.dropout)?;
         model.serialize_field("unk_token", &self.unk_token)?;
         model.serialize_field("continuing_subword_prefix", &self.continuing_subword_prefix)?;
         model.serialize_field("end_of_word_suffix", &self.end_of_word_suffix)?;
         model.serialize_field("fuse_unk", &self.fuse_unk)?;
+        model.serialize_field("byte_fallback", &self.byte_fallback)?;
+        model.serialize_field("cache_capacity", &self.get_cache_capacity())?;
 
         // Then the large ones
-        let mut merges: Vec<(&Pair, &u32)> = self
-            .merges
+        let (vocab_r, merges) = self.maps();
+        let mut merges: Vec<(&Pair, &u32)> = merges
             .iter()
             .map(|(pair, (rank, _))| (pair, rank))
             .collect();
         merges.sort_unstable_by_key(|k| *k.1);
-        let merges_str = merges
+        let merges = merges
             .into_iter()
-            .map(|(pair, _)| format!("{} {}", self.vocab_r[&pair.0], self.vocab_r[&pair.1]))
+            .map(|(pair, _)| (&vocab_r[&pair.0], &vocab_r[&pair.1]))
             .collect::<Vec<_>>();
-        let ordered_vocab = OrderedVocabIter::new(&self.vocab_r);
+        let ordered_vocab = OrderedVocabIter::new(&vocab_r);
 
         model.serialize_field("vocab", &ordered_vocab)?;
-        model.serialize_field("merges", &merges_str)?;
+        model.serialize_field("merges", &merges)?;
 
         model.end()
     }
@@ -48,11 +52,14 @@ impl<'de> Deserialize<'de> for BPE {
         deserializer.deserialize_struct(
             "BPE",
             &[
+                "type",
                 "dropout",
                 "unk_token",
                 "continuing_subword_prefix",
                 "end_of_word_suffix",
                 "fuse_unk",
+                "byte_fallback",
+                "cache_capacity",
                 "vocab",
                 "merges",
             ],
@@ -61,6 +68,55 @@ impl<'de> Deserialize<'de> for BPE {
     }
 }
 
+/// A merge, serialized as a pair of tokens. It used to be a `"a b"` string, which can't
+/// represent the tokens containing a space, and is still accepted.
+struct MergeRepr(String, String);
+
+impl<'de> Deserialize<'de> for MergeRepr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(MergeVisitor)
+    }
+}
+
+struct MergeVisitor;
+impl<'de> Visitor<'de> for MergeVisitor {
+    type Value = MergeRepr;
+
+    fn expecting(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmt, "a pair of tokens, or a \"a b\" string")
+    }
+
+    fn visit_str<E>(self, merge: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let mut parts = merge.split(' ');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(a), Some(b), None) => Ok(MergeRepr(a.to_owned(), b.to_owned())),
+            _ => Err(Error::invalid_value(Unexpected::Str(merge), &self)),
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let a = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(0, &self))?;
+        let b = seq
+            .next_element()?
+            .ok_or_else(|| Error::invalid_length(1, &self))?;
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(Error::invalid_length(3, &self));
+        }
+        Ok(MergeRepr(a, b))
+    }
+}
+
 struct BPEVisitor;
 impl<'de> Visitor<'de> for BPEVisitor {
     type Value = BPE;
@@ -75,9 +131,15 @@ impl<'de> Visitor<'de> for BPEVisitor {
     {
         let mut builder = BpeBuilder::new();
         let mut vocab: Option<HashMap<String, u32>> = None;
-        let mut merges: Option<Vec<String>> = None;
+        let mut merges: Option<Vec<MergeRepr>> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_ref() {
+                "type" => {
+                    let model_type: String = map.next_value()?;
+                    if model_type != "BPE" {
+                        return Err(Error::custom(format!("Expected BPE, got {}", model_type)));
+                    }
+                }
                 "dropout" => {
                     if let Some(dropout) = map.next_value()? {
                         builder = builder.dropout(dropout);
@@ -103,18 +165,33 @@ impl<'de> Visitor<'de> for BPEVisitor {
                         builder = builder.fuse_unk(suffix);
                     }
                 }
+                "byte_fallback" => {
+                    if let Some(byte_fallback) = map.next_value()? {
+                        builder = builder.byte_fallback(byte_fallback);
+                    }
+                }
+                "cache_capacity" => {
+                    if let Some(capacity) = map.next_value()? {
+                        builder = builder.cache_capacity(capacity);
+                    }
+                }
                 "vocab" => vocab = Some(map.next_value()?),
-                "merges" => merges = Some(map.next_value()?),
+                "merges" => {
+                    merges = Some(
+                        map.next_value()
+                            .map_err(|e| with_context("invalid merges", e))?,
+                    )
+                }
                 _ => {}
             }
         }
-        if let (Some(vocab), Some(merges)) = (vocab, merges) {
-            let merges =
-                convert_merges_to_hashmap(merges.into_iter(), &vocab).map_err(Error::custom)?;
-            builder = builder.vocab_and_merges(vocab, merges);
-            Ok(builder.build().map_err(Error::custom)?)
-        } else {
-            Err(Error::custom("Missing vocab/merges"))
-        }
+        let vocab = vocab.ok_or_else(|| Error::missing_field("vocab"))?;
+        let merges = merges
+            .ok_or_else(|| Error::missing_field("merges"))?
+            .into_iter()
+            .map(|MergeRepr(a, b)| (a, b))
+            .collect();
+        builder = builder.vocab_and_merges(vocab, merges);
+        builder.build().map_err(Error::custom)
     }
 }
@@ -43,6 +43,8 @@ struct Config {
     initial_alphabet: HashSet<char>,
     continuing_subword_prefix: Option<String>,
     end_of_word_suffix: Option<String>,
+    base_model: Option<BPE>,
+    max_new_tokens: Option<usize>,
 }
 
 /// A `BpeTrainerBuilder` can be used to create a `BpeTrainer` with a custom
@@ -63,6 +65,8 @@ impl Default for BpeTrainerBuilder {
                 initial_alphabet: HashSet::new(),
                 continuing_subword_prefix: None,
                 end_of_word_suffix: None,
+                base_model: None,
+                max_new_tokens: None,
             },
         }
     }
@@ -122,6 +126,22 @@ impl BpeTrainerBuilder {
         self
     }
 
+    /// Set an existing model to extend, instead of training a new one from scratch. This also
+    /// uses its `continuing_subword_prefix` and `end_of_word_suffix`.
+    pub fn base_model(mut self, model: BPE) -> Self {
+        self.config.continuing_subword_prefix = model.continuing_subword_prefix.clone();
+        self.config.end_of_word_suffix = model.end_of_word_suffix.clone();
+        self.config.base_model = Some(model);
+        self
+    }
+
+    /// Set the maximum number of new tokens to add to the vocabulary, including the special
+    /// tokens and the alphabet
+    pub fn max_new_tokens(mut self, max: usize) -> Self {
+        self.config.max_new_tokens = Some(max);
+        self
+    }
+
     /// Constructs the final BpeTrainer
     pub fn build(self) -> BpeTrainer {
         BpeTrainer {
@@ -133,6 +153,8 @@ impl BpeTrainerBuilder {
             initial_alphabet: self.config.initial_alphabet,
             continuing_subword_prefix: self.config.continuing_subword_prefix,
             end_of_word_suffix: self.config.end_of_word_suffix,
+            base_model: self.config.base_model,
+            max_new_tokens: self.config.max_new_tokens,
         }
     }
 }
@@ -153,6 +175,27 @@ impl BpeTrainerBuilder {
 /// let trainer = BpeTrainer::default();
 /// let (model, special_tokens) = trainer.train(word_counts).unwrap();
 /// ```
+///
+/// When a `base_model` is given, the training continues from it: its tokens keep their ids and
+/// its merges keep their priorities, and only the new tokens and merges are learned.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use tokenizers::tokenizer::Trainer;
+/// use tokenizers::models::bpe::{BpeTrainer, BPE};
+///
+/// # let word_counts: HashMap<String, u32> = [(String::from("Hello"), 1)].iter().cloned().collect();
+/// # let (base, _) = BpeTrainer::default().train(word_counts).unwrap();
+/// let word_counts: HashMap<String, u32> = [
+///     (String::from("Hello"), 2),
+///     (String::from("Help"), 1),
+/// ].iter().cloned().collect();
+/// let trainer = BpeTrainer::builder()
+///     .base_model(base)
+///     .max_new_tokens(100)
+///     .build();
+/// let (model, special_tokens) = trainer.train(word_counts).unwrap();
+/// ```
 pub struct BpeTrainer {
     /// The minimum frequency a pair must have to produce a merge operation
     min_frequency: u32,
@@ -171,6 +214,12 @@ pub struct BpeTrainer {
     continuing_subword_prefix: Option<String>,
     /// An optional suffix to caracterize and end-of-word subword
     end_of_word_suffix: Option<String>,
+    /// An optional model to extend, whose tokens and merges are kept as they are
+    base_model: Option<BPE>,
+    /// The maximum number of new tokens to add to the vocabulary, including the special tokens
+    /// and the alphabet, of which only the most frequent chars are kept. The training stops as
+    /// soon as this number, or `vocab_size`, is reached
+    max_new_tokens: Option<usize>,
 }
 
 impl Default for BpeTrainer {
@@ -225,6 +274,34 @@ impl BpeTrainer {
         }
     }
 
+    /// Start from the vocabulary of the base model, if any
+    fn add_base_vocab(
+        &self,
+        w2id: &mut HashMap<String, u32>,
+        id2w: &mut Vec<String>,
+    ) -> Result<()> {
+        if let Some(base) = &self.base_model {
+            if base.continuing_subword_prefix != self.continuing_subword_prefix
+                || base.end_of_word_suffix != self.end_of_word_suffix
+            {
+                return Err(
+                    "The base model must use the same continuing_subword_prefix and \
+                    end_of_word_suffix as the trainer"
+                        .into(),
+                );
+            }
+            let (vocab_r, _) = base.maps();
+            for id in 0..vocab_r.len() as u32 {
+                let token = vocab_r
+                    .get(&id)
+                    .ok_or("The ids of the base model must be contiguous")?;
+                id2w.push(token.to_owned());
+                w2id.insert(token.to_owned(), id);
+            }
+        }
+        Ok(())
+    }
+
     /// Add the provided special tokens to the initial vocabulary
     fn add_special_tokens(&self, w2id: &mut HashMap<String, u32>, id2w: &mut Vec<String>) {
         for token in &self.special_tokens {
@@ -235,12 +312,32 @@ impl BpeTrainer {
         }
     }
 
-    /// Compute the initial alphabet and limit it if relevant
+    /// The token of `c` inside a word, with the `continuing_subword_prefix` and the
+    /// `end_of_word_suffix` when relevant
+    fn char_token(&self, c: char, is_first: bool, is_last: bool) -> String {
+        let mut s = c.to_string();
+        if !is_first {
+            if let Some(prefix) = &self.continuing_subword_prefix {
+                s = format!("{}{}", prefix, s);
+            }
+        }
+        if is_last {
+            if let Some(suffix) = &self.end_of_word_suffix {
+                s = format!("{}{}", s, suffix);
+            }
+        }
+        s
+    }
+
+    /// Compute the initial alphabet and limit it if relevant. With a `max_vocab_size`, only the
+    /// most frequent new chars are kept, along with their prefixed and suffixed tokens that
+    /// are then added here instead of while tokenizing the words.
     fn compute_alphabet(
         &self,
         wc: &HashMap<String, u32>,
         w2id: &mut HashMap<String, u32>,
         id2w: &mut Vec<String>,
+        max_vocab_size: Option<usize>,
     ) {
         // Compute the alphabet from seen words
         let mut alphabet: HashMap<char, usize> = HashMap::new();
@@ -285,8 +382,42 @@ impl BpeTrainer {
 
         // Keep the initial alphabet (sorted for determinism)
         kept.sort_unstable_by_key(|k| (*k.0) as u32);
-        kept.into_iter().for_each(|(c, _)| {
-            let s = c.to_string();
+        let mut tokens = kept.iter().map(|(c, _)| c.to_string()).collect::<Vec<_>>();
+
+        if let Some(max_vocab_size) = max_vocab_size {
+            // The count of each new token, and whether it is a prefixed or suffixed char
+            let mut counts: HashMap<String, (usize, bool)> = kept
+                .iter()
+                .map(|(c, count)| (c.to_string(), (**count, false)))
+                .filter(|(s, _)| !w2id.contains_key(s))
+                .collect();
+            let kept = kept.into_iter().map(|(c, _)| *c).collect::<HashSet<_>>();
+            for (word, count) in wc {
+                for (is_first, is_last, c) in word.chars().with_first_and_last() {
+                    let s = self.char_token(c, is_first, is_last);
+                    if kept.contains(&c) && s != c.to_string() && !w2id.contains_key(&s) {
+                        counts.entry(s).or_insert((0, true)).0 += *count as usize;
+                    }
+                }
+            }
+
+            // A char is at least as frequent as its prefixed and suffixed versions, and comes
+            // before them on a tie, so these are only kept along with their char
+            let mut new_tokens = counts.into_iter().collect::<Vec<_>>();
+            new_tokens.sort_unstable_by(|(a, (count_a, affixed_a)), (b, (count_b, affixed_b))| {
+                count_b
+                    .cmp(count_a)
+                    .then(affixed_a.cmp(affixed_b))
+                    .then(a.cmp(b))
+            });
+            new_tokens.truncate(max_vocab_size.saturating_sub(id2w.len()));
+            new_tokens.sort_unstable_by(|(a, (_, affixed_a)), (b, (_, affixed_b))| {
+                affixed_a.cmp(affixed_b).then(a.cmp(b))
+            });
+            tokens = new_tokens.into_iter().map(|(s, _)| s).collect();
+        }
+
+        tokens.into_iter().for_each(|s| {
             if !w2id.contains_key(&s) {
                 id2w.push(s.clone());
                 w2id.insert(s, (id2w.len() - 1) as u32);
@@ -294,12 +425,14 @@ impl BpeTrainer {
         });
     }
 
-    /// Tokenize words and add subwords to the vocabulary when relevant
+    /// Tokenize words and add subwords to the vocabulary when relevant. With a
+    /// `max_vocab_size`, the subwords missing from the alphabet are skipped instead.
     fn tokenize_words(
         &self,
         wc: &HashMap<String, u32>,
         w2id: &mut HashMap<String, u32>,
         id2w: &mut Vec<String>,
+        max_vocab_size: Option<usize>,
         p: &Option<ProgressBar>,
     ) -> (Vec<Word>, Vec<u32>) {
         let mut words: Vec<Word> = Vec::with_capacity(wc.len());
@@ -310,25 +443,15 @@ impl BpeTrainer {
             counts.push(*count);
 
             for (is_first, is_last, c) in word.chars().with_first_and_last() {
-                let mut s = c.to_string();
-                if w2id.contains_key(&s) {
+                if w2id.contains_key(&c.to_string()) {
                     // Found the initial char in the authorized alphabet
-
-                    // Add the `continuing_subword_prefix` if relevant
-                    if !is_first {
-                        if let Some(prefix) = &self.continuing_subword_prefix {
-                            s = format!("{}{}", prefix, s);
-                        }
-                    }
-                    // Add the `end_of_word_suffix` if relevant
-                    if is_last {
-                        if let Some(suffix) = &self.end_of_word_suffix {
-                            s = format!("{}{}", s, suffix);
-                        }
-                    }
+                    let s = self.char_token(c, is_first, is_last);
 
                     // Insert the new formed string if necessary
                     if !w2id.contains_key(&s) {
+                        if max_vocab_size.is_some() {
+                            continue;
+                        }
                         id2w.push(s.clone());
                         w2id.insert(s.clone(), (id2w.len() - 1) as u32);
                     }
@@ -410,22 +533,57 @@ impl BpeTrainer {
 
         let progress = self.setup_progress();
 
+        //
+        // 0. Start from the base model, and compute the maximum size of the vocabulary
+        //
+        self.add_base_vocab(&mut word_to_id, &mut id_to_word)?;
+        let max_vocab_size = self.max_new_tokens.map(|max| id_to_word.len() + max);
+        let vocab_size = max_vocab_size.map_or(self.vocab_size, |max| self.vocab_size.min(max));
+
         //
         // 1. Add all special tokens to the vocabulary
         //
         self.add_special_tokens(&mut word_to_id, &mut id_to_word);
+        if let Some(max) = max_vocab_size {
+            if id_to_word.len() > max {
+                return Err("The special tokens exceed max_new_tokens".into());
+            }
+        }
 
         //
         // 2. Compute the initial alphabet
         //
-        self.compute_alphabet(&word_counts, &mut word_to_id, &mut id_to_word);
+        self.compute_alphabet(
+            &word_counts,
+            &mut word_to_id,
+            &mut id_to_word,
+            max_vocab_size,
+        );
 
         //
         // 3. Tokenize words
         //
         self.update_progress(&progress, word_counts.len(), "Tokenize words");
-        let (words, counts) =
-            self.tokenize_words(&word_counts, &mut word_to_id, &mut id_to_word, &progress);
+        let (mut words, counts) = self.tokenize_words(
+            &word_counts,
+            &mut word_to_id,
+            &mut id_to_word,
+            max_vocab_size,
+            &progress,
+        );
+        // Apply the merges of the base model, so that we only learn new ones on top of them
+        let mut base_merges = vec![];
+        if let Some(base) = &self.base_model {
+            let (_, merges) = base.maps();
+            words
+                .maybe_par_iter_mut()
+                .for_each(|word| word.merge_all(|pair| merges.get(pair).copied(), None));
+            base_merges = merges
+                .iter()
+                .map(|(pair, merge)| (*pair, *merge))
+                .collect::<Vec<_>>();
+            base_merges.sort_unstable_by_key(|(_, (rank, _))| *rank);
+        }
         self.finalize_progress(&progress, words.len());
 
         //
@@ -450,11 +608,14 @@ impl BpeTrainer {
         //
         // 5. Do merges
         //
-        self.update_progress(&progress, self.vocab_size, "Compute merges");
-        let mut merges: Vec<(Pair, u32)> = vec![];
+        self.update_progress(&progress, vocab_size, "Compute merges");
+        let mut merges: Vec<(Pair, u32)> = base_merges
+            .into_iter()
+            .map(|(pair, (_, new_id))| (pair, new_id))
+            .collect();
         loop {
             // Stop as soon as we have a big enough vocabulary
-            if word_to_id.len() >= self.vocab_size {
+            if word_to_id.len() >= vocab_size {
                 break;
             }
 
@@ -569,6 +730,17 @@ impl BpeTrainer {
         if let Some(suffix) = &self.end_of_word_suffix {
             builder = builder.end_of_word_suffix(suffix.to_owned());
         }
+        if let Some(base) = &self.base_model {
+            if let Some(dropout) = base.dropout {
+                builder = builder.dropout(dropout);
+            }
+            if let Some(unk_token) = &base.unk_token {
+                builder = builder.unk_token(unk_token.to_owned());
+            }
+            builder = builder
+                .fuse_unk(base.fuse_unk)
+                .byte_fallback(base.byte_fallback);
+        }
         Ok((
             builder
                 .build()
@@ -605,7 +777,7 @@ impl Trainer for BpeTrainer {
 
 #[cfg(test)]
 mod tests {
-    use super::{BpeTrainer, Pair};
+    use super::{AddedToken, BpeTrainer, Pair};
     use std::collections::HashMap;
 
     #[test]
@@ -680,4 +852,95 @@ mod tests {
         .collect();
         assert_eq!(model.merges, expected_merges);
     }
+
+    #[test]
+    fn test_train_extend() {
+        let word_counts: HashMap<String, u32> = [("are".into(), 2), ("is".into(), 2)]
+            .iter()
+            .cloned()
+            .collect();
+        let trainer = BpeTrainer::builder().show_progress(false).build();
+        let (base, _) = trainer.train(word_counts).unwrap();
+
+        let word_counts: HashMap<String, u32> = [("roses".into(), 3), ("rose".into(), 2)]
+            .iter()
+            .cloned()
+            .collect();
+        let trainer = BpeTrainer::builder()
+            .show_progress(false)
+            .base_model(base.clone())
+            .max_new_tokens(4)
+            .build();
+        let (model, _) = trainer.train(word_counts).unwrap();
+
+        // The tokens and merges of the base model are left untouched
+        for (token, id) in &base.vocab {
+            assert_eq!(model.vocab[token], *id);
+        }
+        for (pair, rank_id) in &base.merges {
+            assert_eq!(model.merges[pair], *rank_id);
+        }
+        // Only `o` is a new char, `s` and `e` are reused to learn the new merges
+        let mut new_tokens = model
+            .vocab
+            .iter()
+            .filter(|(_, id)| **id as usize >= base.vocab.len())
+            .map(|(token, id)| (token.as_str(), *id))
+            .collect::<Vec<_>>();
+        new_tokens.sort_unstable_by_key(|(_, id)| *id);
+        assert_eq!(
+            new_tokens,
+            vec![("o", 8), ("ro", 9), ("se", 10), ("rose", 11)]
+        );
+        let pair = (model.vocab["r"], model.vocab["o"]);
+        assert_eq!(model.merges[&pair], (base.merges.len() as u32, 9));
+
+        // The special tokens and the new chars count as new tokens too, and only the most
+        // frequent chars are kept, along with their prefixed versions
+        let word_counts: HashMap<String, u32> =
+            [("xz".into(), 3), ("zw".into(), 2), ("y".into(), 1)]
+                .iter()
+                .cloned()
+                .collect();
+        let trainer = BpeTrainer::builder()
+            .show_progress(false)
+            .base_model(base.clone())
+            .special_tokens(vec![AddedToken::from("<unk>", true)])
+            .max_new_tokens(3)
+            .build();
+        let (model, special_tokens) = trainer.train(word_counts.clone()).unwrap();
+        assert_eq!(special_tokens.len(), 1);
+        assert_eq!(model.vocab.len(), base.vocab.len() + 3);
+        assert_eq!(model.vocab["<unk>"], 8);
+        assert_eq!(model.vocab["x"], 9);
+        assert_eq!(model.vocab["z"], 10);
+
+        let trainer = BpeTrainer::builder()
+            .show_progress(false)
+            .continuing_subword_prefix("##".into())
+            .max_new_tokens(3)
+            .build();
+        let (model, _) = trainer.train(word_counts.clone()).unwrap();
+        let mut tokens = model.vocab.keys().map(|t| t.as_str()).collect::<Vec<_>>();
+        tokens.sort_unstable();
+        assert_eq!(tokens, vec!["##z", "x", "z"]);
+
+        let trainer = BpeTrainer::builder()
+            .show_progress(false)
+            .special_tokens(vec![
+                AddedToken::from("<unk>", true),
+                AddedToken::from("<pad>", true),
+            ])
+            .max_new_tokens(1)
+            .build();
+        assert!(trainer.train(word_counts).is_err());
+
+        // The base model must use the same prefix
+        let trainer = BpeTrainer::builder()
+            .show_progress(false)
+            .base_model(base)
+            .continuing_subword_prefix("##".into())
+            .build();
+        assert!(trainer.train(HashMap::new()).is_err());
+    }
 }
@@ -1,7 +1,7 @@
 use super::Pair;
 use rand::{thread_rng, Rng};
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::BinaryHeap;
 
 #[derive(Debug, Eq)]
 struct Merge {
@@ -147,7 +147,11 @@ impl Word {
         changes
     }
 
-    pub(super) fn merge_all(&mut self, merges: &HashMap<Pair, (u32, u32)>, dropout: Option<f32>) {
+    /// Apply the merges, `merge` giving the rank and the new id of the merge of a pair, if any.
+    pub(super) fn merge_all<M>(&mut self, merge: M, dropout: Option<f32>)
+    where
+        M: Fn(&Pair) -> Option<(u32, u32)>,
+    {
         let mut queue = BinaryHeap::with_capacity(self.symbols.len());
         let mut skip = Vec::with_capacity(queue.len());
 
@@ -157,7 +161,7 @@ impl Word {
                 .enumerate()
                 .filter_map(|(index, window)| {
                     let pair = (window[0].c, window[1].c);
-                    merges.get(&pair).map(|m| Merge {
+                    merge(&pair).map(|m| Merge {
                         pos: index,
                         rank: m.0,
                         new_id: m.1,
@@ -188,10 +192,7 @@ impl Word {
 
                 // Make sure we are not processing an expired queue entry
                 let target_new_pair = (self.symbols[top.pos].c, right.c);
-                if !merges
-                    .get(&target_new_pair)
-                    .map_or(false, |(_, new_id)| *new_id == top.new_id)
-                {
+                if !merge(&target_new_pair).map_or(false, |(_, new_id)| new_id == top.new_id) {
                     continue;
                 }
 
@@ -211,11 +212,11 @@ impl Word {
                     let prev = current.prev as usize;
                     let prev_symbol = self.symbols[prev];
                     let new_pair = (prev_symbol.c, current.c);
-                    if let Some((rank, new_id)) = merges.get(&new_pair) {
+                    if let Some((rank, new_id)) = merge(&new_pair) {
                         queue.push(Merge {
                             pos: current.prev as usize,
-                            rank: *rank,
-                            new_id: *new_id,
+                            rank,
+                            new_id,
                         });
                     }
                 }
@@ -225,11 +226,11 @@ impl Word {
                 if next < self.symbols.len() {
                     let next_symbol = self.symbols[next];
                     let new_pair = (current.c, next_symbol.c);
-                    if let Some((rank, new_id)) = merges.get(&new_pair) {
+                    if let Some((rank, new_id)) = merge(&new_pair) {
                         queue.push(Merge {
                             pos: top.pos,
-                            rank: *rank,
-                            new_id: *new_id,
+                            rank,
+                            new_id,
                         });
                     }
                 }
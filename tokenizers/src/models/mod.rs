@@ -5,15 +5,17 @@ pub mod unigram;
 pub mod wordlevel;
 pub mod wordpiece;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Serialize, Serializer};
 
 use crate::models::bpe::{BpeTrainer, BPE};
 use crate::models::unigram::{Unigram, UnigramTrainer};
-use crate::models::wordlevel::WordLevel;
+use crate::models::wordlevel::{WordLevel, WordLevelTrainer};
 use crate::models::wordpiece::{WordPiece, WordPieceTrainer};
+use crate::utils::cache::CacheStats;
+use crate::utils::registry::{Custom, Registry};
 use crate::{AddedToken, Model, Result, Token, Trainer};
 
 /// Wraps a vocab mapping (ID -> token) to a struct that will be serialized in order
@@ -38,19 +40,94 @@ impl<'a> Serialize for OrderedVocabIter<'a> {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+/// Removes the given ids from a vocabulary (ID -> token), and re-numbers the remaining tokens
+/// in the same order. Returns the new vocabulary (token -> ID), along with the mapping from the
+/// old ids to the new ones. The unknown token, if any, can't be removed.
+pub(crate) fn prune_vocab(
+    vocab_r: &HashMap<u32, String>,
+    ids: &HashSet<u32>,
+    unk_id: Option<u32>,
+) -> Result<(HashMap<String, u32>, HashMap<u32, u32>)> {
+    if let Some(unk_id) = unk_id.filter(|id| ids.contains(id)) {
+        return Err(format!("Cannot remove the unknown token `{}`", vocab_r[&unk_id]).into());
+    }
+
+    let mut old_ids = vocab_r.keys().copied().collect::<Vec<_>>();
+    old_ids.sort_unstable();
+    let mut vocab = HashMap::new();
+    let mut mapping = HashMap::new();
+    for old_id in old_ids.into_iter().filter(|id| !ids.contains(id)) {
+        let new_id = vocab.len() as u32;
+        vocab.insert(vocab_r[&old_id].clone(), new_id);
+        mapping.insert(old_id, new_id);
+    }
+
+    Ok((vocab, mapping))
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum ModelWrapper {
     WordPiece(WordPiece),
     BPE(BPE),
     WordLevel(WordLevel),
     Unigram(Unigram),
+    Custom(CustomModel),
+}
+
+impl_serde_tagged_wrapper!(ModelWrapper, "model", Custom(REGISTRY), {
+    "WordPiece" => WordPiece(WordPiece),
+    "BPE" => BPE(BPE),
+    "WordLevel" => WordLevel(WordLevel),
+    "Unigram" => Unigram(Unigram),
+});
+
+/// A user-defined model, which can be saved and loaded once registered in the
+/// [`REGISTRY`](struct.REGISTRY.html).
+pub type CustomModel = Custom<dyn Model + Send + Sync>;
+
+lazy_static! {
+    /// The user-defined models, by the `type` they are serialized with.
+    pub static ref REGISTRY: Registry<dyn Model + Send + Sync> =
+        Registry::new("model", ModelWrapper::BUILTIN_TYPES);
 }
 
 impl_enum_from!(WordLevel, ModelWrapper, WordLevel);
 impl_enum_from!(WordPiece, ModelWrapper, WordPiece);
 impl_enum_from!(BPE, ModelWrapper, BPE);
 impl_enum_from!(Unigram, ModelWrapper, Unigram);
+impl_enum_from!(CustomModel, ModelWrapper, Custom);
+
+impl ModelWrapper {
+    /// Get the statistics of the cache of the model, for the models that have one.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        match self {
+            ModelWrapper::BPE(bpe) => bpe.cache_stats(),
+            ModelWrapper::Unigram(unigram) => Some(unigram.cache_stats()),
+            ModelWrapper::WordPiece(_) | ModelWrapper::WordLevel(_) | ModelWrapper::Custom(_) => {
+                None
+            }
+        }
+    }
+
+    /// Fill the cache of the model, if any, with the given pre-tokenized words.
+    pub fn warm_cache<I, S>(&self, words: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        match self {
+            ModelWrapper::BPE(bpe) => bpe.warm_cache(words),
+            ModelWrapper::Unigram(unigram) => {
+                unigram.warm_cache(words);
+                Ok(())
+            }
+            ModelWrapper::WordPiece(_) | ModelWrapper::WordLevel(_) | ModelWrapper::Custom(_) => {
+                Ok(())
+            }
+        }
+    }
+}
 
 impl Model for ModelWrapper {
     fn tokenize(&self, tokens: &str) -> Result<Vec<Token>> {
@@ -60,6 +137,7 @@ impl Model for ModelWrapper {
             WordPiece(t) => t.tokenize(tokens),
             BPE(t) => t.tokenize(tokens),
             Unigram(t) => t.tokenize(tokens),
+            Custom(t) => t.tokenize(tokens),
         }
     }
 
@@ -70,6 +148,7 @@ impl Model for ModelWrapper {
             WordPiece(t) => t.token_to_id(token),
             BPE(t) => t.token_to_id(token),
             Unigram(t) => t.token_to_id(token),
+            Custom(t) => t.token_to_id(token),
         }
     }
 
@@ -80,6 +159,7 @@ impl Model for ModelWrapper {
             WordPiece(t) => t.id_to_token(id),
             BPE(t) => t.id_to_token(id),
             Unigram(t) => t.id_to_token(id),
+            Custom(t) => t.id_to_token(id),
         }
     }
 
@@ -90,6 +170,7 @@ impl Model for ModelWrapper {
             WordPiece(t) => t.get_vocab(),
             BPE(t) => t.get_vocab(),
             Unigram(t) => t.get_vocab(),
+            Custom(t) => t.get_vocab(),
         }
     }
 
@@ -100,6 +181,29 @@ impl Model for ModelWrapper {
             WordPiece(t) => t.get_vocab_size(),
             BPE(t) => t.get_vocab_size(),
             Unigram(t) => t.get_vocab_size(),
+            Custom(t) => t.get_vocab_size(),
+        }
+    }
+
+    fn unk_token(&self) -> Option<&str> {
+        use ModelWrapper::*;
+        match self {
+            WordLevel(t) => t.unk_token(),
+            WordPiece(t) => t.unk_token(),
+            BPE(t) => t.unk_token(),
+            Unigram(t) => t.unk_token(),
+            Custom(t) => t.unk_token(),
+        }
+    }
+
+    fn prune(&mut self, ids: &HashSet<u32>) -> Result<HashMap<u32, u32>> {
+        use ModelWrapper::*;
+        match self {
+            WordLevel(t) => t.prune(ids),
+            WordPiece(t) => t.prune(ids),
+            BPE(t) => t.prune(ids),
+            Unigram(t) => t.prune(ids),
+            Custom(_) => Err("Custom models cannot be pruned".into()),
         }
     }
 
@@ -110,6 +214,7 @@ impl Model for ModelWrapper {
             WordPiece(t) => t.save(folder, name),
             BPE(t) => t.save(folder, name),
             Unigram(t) => t.save(folder, name),
+            Custom(t) => t.save(folder, name),
         }
     }
 }
@@ -118,6 +223,7 @@ pub enum TrainerWrapper {
     BpeTrainer(BpeTrainer),
     WordPieceTrainer(WordPieceTrainer),
     UnigramTrainer(UnigramTrainer),
+    WordLevelTrainer(WordLevelTrainer),
 }
 
 impl Trainer for TrainerWrapper {
@@ -128,6 +234,7 @@ impl Trainer for TrainerWrapper {
             TrainerWrapper::BpeTrainer(bpe) => bpe.should_show_progress(),
             TrainerWrapper::WordPieceTrainer(wpt) => wpt.should_show_progress(),
             TrainerWrapper::UnigramTrainer(wpt) => wpt.should_show_progress(),
+            TrainerWrapper::WordLevelTrainer(wpt) => wpt.should_show_progress(),
         }
     }
 
@@ -136,6 +243,7 @@ impl Trainer for TrainerWrapper {
             TrainerWrapper::BpeTrainer(bpe) => bpe.train(words).map(|(m, t)| (m.into(), t)),
             TrainerWrapper::WordPieceTrainer(wpt) => wpt.train(words).map(|(m, t)| (m.into(), t)),
             TrainerWrapper::UnigramTrainer(wpt) => wpt.train(words).map(|(m, t)| (m.into(), t)),
+            TrainerWrapper::WordLevelTrainer(wpt) => wpt.train(words).map(|(m, t)| (m.into(), t)),
         }
     }
 
@@ -144,6 +252,7 @@ impl Trainer for TrainerWrapper {
             TrainerWrapper::BpeTrainer(bpe) => bpe.process_tokens(words, tokens),
             TrainerWrapper::WordPieceTrainer(wpt) => wpt.process_tokens(words, tokens),
             TrainerWrapper::UnigramTrainer(wpt) => wpt.process_tokens(words, tokens),
+            TrainerWrapper::WordLevelTrainer(wpt) => wpt.process_tokens(words, tokens),
         }
     }
 }
@@ -151,3 +260,4 @@ impl Trainer for TrainerWrapper {
 impl_enum_from!(BpeTrainer, TrainerWrapper, BpeTrainer);
 impl_enum_from!(WordPieceTrainer, TrainerWrapper, WordPieceTrainer);
 impl_enum_from!(UnigramTrainer, TrainerWrapper, UnigramTrainer);
+impl_enum_from!(WordLevelTrainer, TrainerWrapper, WordLevelTrainer);
@@ -5,7 +5,7 @@ use std::cmp::{min, Ordering};
 use std::collections::BinaryHeap;
 use std::rc::Rc;
 
-type NodeRef = Rc<RefCell<Node>>;
+pub(super) type NodeRef = Rc<RefCell<Node>>;
 type HypothesisRef = Rc<RefCell<Hypothesis>>;
 type Agenda = BinaryHeap<Hypothesis>;
 
@@ -380,6 +380,12 @@ impl<'a> Lattice<'a> {
     }
 
     pub fn sample(&self, theta: f64) -> Vec<NodeRef> {
+        self.sample_with_rng(theta, &mut thread_rng())
+    }
+
+    /// Same as `sample`, but using the given random number generator, which makes it
+    /// possible to get reproducible results.
+    pub fn sample_with_rng<R: Rng + ?Sized>(&self, theta: f64, rng: &mut R) -> Vec<NodeRef> {
         let len = self.len();
         if len == 0 {
             return vec![];
@@ -399,7 +405,6 @@ impl<'a> Lattice<'a> {
             }
         }
 
-        let mut rng = thread_rng();
         let mut results: Vec<NodeRef> = vec![];
         let mut probs: Vec<f64> = vec![];
         let mut z = alpha[self.eos_node().borrow().node_id];
@@ -412,7 +417,7 @@ impl<'a> Lattice<'a> {
                 probs.push((alpha[lid] + theta * lnode.borrow().score - z).exp())
             }
             let dist = WeightedIndex::new(&probs).unwrap();
-            let index = dist.sample(&mut rng);
+            let index = dist.sample(rng);
             node = Rc::clone(&self.end_nodes[pos][index]);
             if node == self.bos_node() {
                 break;
@@ -424,6 +429,11 @@ impl<'a> Lattice<'a> {
         results
     }
 
+    /// The total score of the given path, as the sum of the scores of its nodes.
+    pub fn path_score(&self, path: &[NodeRef]) -> f64 {
+        path.iter().map(|node| node.borrow().score).sum()
+    }
+
     pub fn sample_token(&self, theta: f64) -> Vec<String> {
         self.sample(theta)
             .iter()
@@ -1,16 +1,51 @@
-use crate::models::unigram::lattice::Lattice;
+use crate::models::prune_vocab;
+use crate::models::unigram::lattice::{Lattice, NodeRef};
 use crate::models::unigram::trie::{Trie, TrieBuilder};
-use crate::tokenizer::{Model, Result, Token};
-use crate::utils::cache::Cache;
+use crate::tokenizer::{MappedTables, Model, Result, Token};
+use crate::utils::cache::{Cache, CacheStats};
 
-use std::collections::HashMap;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 type TokenMap = HashMap<String, u32>;
 type Vocab = Vec<(String, f64)>;
 
+/// Parameters of the subword regularization, used to sample a segmentation instead of
+/// always using the best one. This follows `SampleEncode` from SentencePiece.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct UnigramSampling {
+    /// The smoothing parameter of the distribution. The lower, the more uniform.
+    pub alpha: f64,
+    /// The number of best segmentations to sample from. With `0` or `1` no sampling is
+    /// performed, and with a negative value we sample from all the possible segmentations.
+    pub nbest_size: i32,
+    /// An optional seed, to make the sampling reproducible. The model then draws from a ChaCha8
+    /// generator seeded with it, which gives the same numbers on every platform. Each word
+    /// encoded moves the generator forward, so the occurrences of a word get their own
+    /// segmentations: for a given version of this crate, encoding the same words in the same
+    /// order after `set_sampling` gives the same segmentations. This does not hold when
+    /// encoding in parallel, as the words then reach the model in a varying order.
+    pub seed: Option<u64>,
+}
+
+impl UnigramSampling {
+    fn is_active(&self) -> bool {
+        self.alpha != 0.0 && self.nbest_size != 0 && self.nbest_size != 1
+    }
+
+    /// The generator used when a seed is given, starting over from this seed
+    fn seeded_rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(self.seed.unwrap_or(0))
+    }
+}
+
 /// A `Unigram` model to encode sentences.
 pub struct Unigram {
     token_to_ids: TokenMap,
@@ -24,10 +59,20 @@ pub struct Unigram {
 
     fuse_unk: bool,
     is_optimized: bool,
+    byte_fallback: bool,
+    sampling: Option<UnigramSampling>,
+    rng: Box<Mutex<ChaCha8Rng>>,
+    /// The tables of the binary tokenizer the model was loaded from, if any. The tokens, their
+    /// scores and the trie are then looked up in these tables, and `token_to_ids`, `vocab` and
+    /// `trie` stay empty.
+    mapped: Option<Arc<MappedTables>>,
 }
 impl PartialEq for Unigram {
     fn eq(&self, other: &Self) -> bool {
-        self.unk_id == other.unk_id && self.vocab == other.vocab
+        self.unk_id == other.unk_id
+            && self.pieces() == other.pieces()
+            && self.byte_fallback == other.byte_fallback
+            && self.sampling == other.sampling
     }
 }
 
@@ -47,6 +92,10 @@ impl Clone for Unigram {
             eos_id: self.eos_id,
             fuse_unk: self.fuse_unk,
             is_optimized: self.is_optimized,
+            byte_fallback: self.byte_fallback,
+            sampling: self.sampling,
+            rng: Box::new(Mutex::new(self.rng.lock().unwrap().clone())),
+            mapped: self.mapped.clone(),
         }
     }
 }
@@ -54,8 +103,10 @@ impl Clone for Unigram {
 impl std::fmt::Debug for Unigram {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         fmt.debug_struct("BPE")
-            .field("vocab", &self.vocab.len())
+            .field("vocab", &self.len())
             .field("unk_id", &self.unk_id)
+            .field("byte_fallback", &self.byte_fallback)
+            .field("sampling", &self.sampling)
             .finish()
     }
 }
@@ -136,9 +187,120 @@ impl Unigram {
             fuse_unk,
             cache: Cache::default(),
             is_optimized,
+            byte_fallback: false,
+            sampling: None,
+            rng: Box::new(Mutex::new(ChaCha8Rng::seed_from_u64(0))),
+            mapped: None,
         })
     }
 
+    /// Create a `Unigram` model using the tables of a binary tokenizer, without copying them.
+    pub(crate) fn from_tables(tables: Arc<MappedTables>, unk_id: usize) -> Result<Self> {
+        let n = tables.len();
+        if n == 0 {
+            return Err(Box::new(UnigramError::EmptyVocabulary));
+        }
+        if unk_id >= n {
+            return Err(Box::new(UnigramError::UnkIdNotInVocabulary));
+        }
+        let min_score = (0..n as u32)
+            .filter_map(|id| tables.score(id))
+            .fold(f64::INFINITY, f64::min);
+
+        Ok(Unigram {
+            vocab: vec![],
+            token_to_ids: HashMap::new(),
+            trie: Trie::default(),
+            min_score,
+            bos_id: n + 1,
+            eos_id: n + 2,
+            unk_id,
+            fuse_unk: true,
+            cache: Cache::default(),
+            is_optimized: true,
+            byte_fallback: false,
+            sampling: None,
+            rng: Box::new(Mutex::new(ChaCha8Rng::seed_from_u64(0))),
+            mapped: Some(tables),
+        })
+    }
+
+    /// Replace the vocabulary, and what is built from it. The options of the model are kept.
+    pub(crate) fn set_vocab(&mut self, vocab: Vec<(String, f64)>, unk_id: usize) -> Result<()> {
+        let other = Unigram::from(vocab, unk_id)?;
+        self.vocab = other.vocab;
+        self.token_to_ids = other.token_to_ids;
+        self.trie = other.trie;
+        self.min_score = other.min_score;
+        self.unk_id = other.unk_id;
+        self.bos_id = other.bos_id;
+        self.eos_id = other.eos_id;
+        self.mapped = None;
+        self.cache.clear();
+        Ok(())
+    }
+
+    /// Specify whether unknown pieces should be decomposed into their `<0xNN>` byte tokens,
+    /// as long as these are part of the vocabulary, instead of using the `unk` token.
+    pub fn set_byte_fallback(&mut self, byte_fallback: bool) {
+        self.byte_fallback = byte_fallback;
+    }
+
+    /// Whether `byte_fallback` is activated
+    pub fn byte_fallback(&self) -> bool {
+        self.byte_fallback
+    }
+
+    /// Activate (or deactivate with `None`) the subword regularization. When activated,
+    /// `encode` and `tokenize` sample a segmentation each time they are called, instead of
+    /// always returning the best one. With a seed, the generator starts over from it.
+    pub fn set_sampling(&mut self, sampling: Option<UnigramSampling>) {
+        if let Some(sampling) = &sampling {
+            *self.rng = Mutex::new(sampling.seeded_rng());
+        }
+        self.sampling = sampling;
+    }
+
+    /// Set the capacity of the cache, 0 meaning no cache at all. This resets the cache.
+    pub fn set_cache_capacity(&mut self, capacity: usize) {
+        self.cache = Cache::new(capacity);
+    }
+
+    /// The capacity of the cache
+    pub fn get_cache_capacity(&self) -> usize {
+        self.cache.capacity
+    }
+
+    /// Get the statistics of the cache
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    /// Reset the cache.
+    pub fn clear_cache(&self) {
+        self.cache.clear()
+    }
+
+    /// Fill the cache with the given words, as they would be received by `tokenize` (ie. after
+    /// the normalization and pre-tokenization).
+    pub fn warm_cache<I, S>(&self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for word in words {
+            let word = word.as_ref();
+            if !word.is_empty() {
+                self.cache.set(word.to_owned(), self.encode_best(word));
+            }
+        }
+    }
+
+    /// The subword regularization parameters, if any
+    pub fn sampling(&self) -> Option<&UnigramSampling> {
+        self.sampling.as_ref()
+    }
+
     #[cfg(test)]
     pub(super) fn set_fuse_unk(&mut self, fuse_unk: bool) {
         self.fuse_unk = fuse_unk;
@@ -151,7 +313,41 @@ impl Unigram {
     }
 
     pub(super) fn len(&self) -> usize {
-        self.vocab.len()
+        match &self.mapped {
+            Some(tables) => tables.len(),
+            None => self.vocab.len(),
+        }
+    }
+
+    /// The vocabulary with the scores, copied out of the mapped tables the first time it is
+    /// needed
+    pub(super) fn pieces(&self) -> &[(String, f64)] {
+        match &self.mapped {
+            Some(tables) => tables.pieces(),
+            None => &self.vocab,
+        }
+    }
+
+    fn score(&self, id: u32) -> f64 {
+        match &self.mapped {
+            Some(tables) => tables.score(id).unwrap(),
+            None => self.vocab[id as usize].1,
+        }
+    }
+
+    /// Call `f` with the length and the id of each token the given bytes start with
+    fn for_each_prefix<F: FnMut(usize, u32)>(&self, bytes: &[u8], mut f: F) {
+        match &self.mapped {
+            Some(tables) => tables
+                .common_prefix_search(bytes)
+                .for_each(|(len, id)| f(len, id)),
+            None => {
+                for prefix in self.trie.common_prefix_search(bytes.iter().copied()) {
+                    let token = std::str::from_utf8(&prefix).unwrap();
+                    f(prefix.len(), self.token_to_ids[token]);
+                }
+            }
+        }
     }
 
     pub(super) fn populate_nodes(&self, lattice: &mut Lattice) {
@@ -169,22 +365,14 @@ impl Unigram {
 
             let mut has_single_node = false;
 
-            for bytes in self
-                .trie
-                .common_prefix_search(lattice.sentence.bytes().skip(begin_pos))
-            {
-                let n = bytes.len();
-                let tok = String::from_utf8(bytes).unwrap();
-                let id = *self.token_to_ids.get(&tok).unwrap();
-
-                let item = &self.vocab[id as usize];
-                assert_eq!(item.0, tok);
-                let score: f64 = item.1;
+            let sentence = lattice.sentence;
+            self.for_each_prefix(&sentence.as_bytes()[begin_pos..], |n, id| {
+                let score = self.score(id);
                 lattice.insert(begin_pos, n, score, id.try_into().unwrap());
                 if !has_single_node && n == mblen {
                     has_single_node = true;
                 }
-            }
+            });
 
             if !has_single_node {
                 lattice.insert(begin_pos, mblen, unk_score, self.unk_id);
@@ -213,23 +401,33 @@ impl Unigram {
     /// let result = model.encode("abcdacdxx");
     /// assert_eq!(result, vec!["abcd", "a", "cd", "xx"]);
     /// ```
+    ///
+    /// If some `UnigramSampling` has been set, the segmentation is sampled instead.
     pub fn encode(&self, sentence: &str) -> Vec<String> {
         if sentence.is_empty() {
             return vec![];
         }
+        if let Some(sampling) = self.sampling.filter(|s| s.is_active()) {
+            // The result is random, so we never use the cache here
+            return self.encode_sampled(sentence, &sampling);
+        }
         if let Some(result) = self.cache.get(sentence) {
             result.to_vec()
         } else {
-            let result = if self.is_optimized {
-                self.encode_optimized(sentence)
-            } else {
-                self.encode_unoptimized(sentence)
-            };
+            let result = self.encode_best(sentence);
             self.cache.set(sentence.to_owned(), result.clone());
             result
         }
     }
 
+    fn encode_best(&self, sentence: &str) -> Vec<String> {
+        if self.is_optimized {
+            self.encode_optimized(sentence)
+        } else {
+            self.encode_unoptimized(sentence)
+        }
+    }
+
     fn encode_optimized(&self, sentence: &str) -> Vec<String> {
         // https://github.com/google/sentencepiece/blob/d48247191a6d50e469ed1a4a36e877befffd1851/src/unigram_model.cc#L600
         #[derive(Debug, Clone)]
@@ -260,28 +458,22 @@ impl Unigram {
             let best_path_score_till_here = best_path_ends_at[starts_at].best_path_score;
             let mut has_single_node = false;
             let mblen = sentence[starts_at..].chars().next().unwrap().len_utf8();
-            for tok_bytes in self
-                .trie
-                .common_prefix_search(sentence.bytes().skip(starts_at))
-            {
-                let key_pos = starts_at + tok_bytes.len();
-                let token: String = String::from_utf8(tok_bytes).unwrap();
+            self.for_each_prefix(&sentence.as_bytes()[starts_at..], |length, id| {
+                let key_pos = starts_at + length;
                 let mut target_node = &mut best_path_ends_at[key_pos];
-                let length = key_pos - starts_at;
-                let id = self.token_to_ids.get(&token).unwrap();
-                let score = self.vocab.get(*id as usize).unwrap().1;
+                let score = self.score(id);
                 let candidate_best_path_score = score + best_path_score_till_here;
                 if target_node.starts_at.is_none()
                     || candidate_best_path_score > target_node.best_path_score
                 {
                     target_node.best_path_score = candidate_best_path_score;
                     target_node.starts_at = Some(starts_at);
-                    target_node.id = *id as usize;
+                    target_node.id = id as usize;
                 }
                 if !has_single_node && length == mblen {
                     has_single_node = true;
                 }
-            }
+            });
             if !has_single_node {
                 let mut target_node = &mut best_path_ends_at[starts_at + mblen];
                 let candidate_best_path_score = unk_score + best_path_score_till_here;
@@ -342,10 +534,94 @@ impl Unigram {
     fn encode_unoptimized(&self, sentence: &str) -> Vec<String> {
         let mut lattice = Lattice::from(sentence, self.unk_id, self.bos_id, self.eos_id);
         self.populate_nodes(&mut lattice);
+        let path = lattice.viterbi();
+        self.path_to_pieces(&lattice, &path)
+    }
+
+    fn encode_sampled(&self, sentence: &str, sampling: &UnigramSampling) -> Vec<String> {
+        // https://github.com/google/sentencepiece/blob/d48247191a6d50e469ed1a4a36e877befffd1851/src/unigram_model.cc#L346
+        let mut lattice = Lattice::from(sentence, self.unk_id, self.bos_id, self.eos_id);
+        self.populate_nodes(&mut lattice);
+        let path = if sampling.seed.is_some() {
+            Self::sample_path(&mut lattice, sampling, &mut *self.rng.lock().unwrap())
+        } else {
+            Self::sample_path(&mut lattice, sampling, &mut thread_rng())
+        };
+        self.path_to_pieces(&lattice, &path)
+    }
+
+    fn sample_path<R: Rng>(
+        lattice: &mut Lattice,
+        sampling: &UnigramSampling,
+        rng: &mut R,
+    ) -> Vec<NodeRef> {
+        if sampling.nbest_size < 0 {
+            lattice.sample_with_rng(sampling.alpha, rng)
+        } else {
+            let mut nbests = lattice.nbest(sampling.nbest_size as usize);
+            // Each segmentation has a probability proportional to exp(alpha * score). We
+            // subtract the max before exponentiating to keep this stable.
+            let scores = nbests
+                .iter()
+                .map(|path| sampling.alpha * lattice.path_score(path))
+                .collect::<Vec<_>>();
+            let max = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let weights = scores.iter().map(|s| (s - max).exp()).collect::<Vec<_>>();
+            let index = WeightedIndex::new(&weights).unwrap().sample(rng);
+            nbests.swap_remove(index)
+        }
+    }
+
+    /// Returns the `n` best segmentations of the given sentence, along with their score,
+    /// the best one first.
+    /// ```
+    /// use tokenizers::models::unigram::Unigram;
+    ///
+    /// let pieces = vec![
+    ///     ("<unk>".to_string(), 0.0),
+    ///     ("a".to_string(), -2.0),
+    ///     ("b".to_string(), -2.0),
+    ///     ("ab".to_string(), -1.0),
+    /// ];
+    /// let model = Unigram::from(pieces, 0).unwrap();
+    /// let result = model.encode_nbest("ab", 2);
+    /// assert_eq!(result, vec![(vec!["ab".to_string()], -1.0), (vec!["a".to_string(), "b".to_string()], -4.0)]);
+    /// ```
+    pub fn encode_nbest(&self, sentence: &str, n: usize) -> Vec<(Vec<String>, f64)> {
+        if sentence.is_empty() {
+            return vec![];
+        }
+        let mut lattice = Lattice::from(sentence, self.unk_id, self.bos_id, self.eos_id);
+        self.populate_nodes(&mut lattice);
+        lattice
+            .nbest(n)
+            .iter()
+            .map(|path| {
+                (
+                    self.path_to_pieces(&lattice, path),
+                    lattice.path_score(path),
+                )
+            })
+            .collect()
+    }
+
+    /// Same as `encode_nbest`, but returns the `Token`s of each segmentation, with their
+    /// ids and offsets, just like `tokenize` does.
+    pub fn tokenize_nbest(&self, sentence: &str, n: usize) -> Result<Vec<(Vec<Token>, f64)>> {
+        Ok(self
+            .encode_nbest(sentence, n)
+            .into_iter()
+            .map(|(pieces, score)| (self.pieces_to_tokens(pieces), score))
+            .collect())
+    }
+
+    /// Convert a path of the lattice to its pieces, fusing the consecutive unknown ones
+    /// if `fuse_unk` is activated.
+    fn path_to_pieces(&self, lattice: &Lattice, path: &[NodeRef]) -> Vec<String> {
         if self.fuse_unk {
             let mut results = vec![];
             let mut token = String::new();
-            for node in lattice.viterbi().iter() {
+            for node in path {
                 let item = lattice.piece(&node.borrow());
                 if node.borrow().id == self.unk_id {
                     token.push_str(&item);
@@ -362,8 +638,55 @@ impl Unigram {
             }
             results
         } else {
-            lattice.tokens()
+            path.iter()
+                .map(|node| lattice.piece(&node.borrow()))
+                .collect()
+        }
+    }
+
+    /// Convert the pieces of a segmentation to `Token`s, tracking their offsets.
+    fn pieces_to_tokens(&self, pieces: Vec<String>) -> Vec<Token> {
+        let mut offset = 0;
+        let mut tokens = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            let len = piece.len();
+            match self.token_to_id(&piece) {
+                Some(id) => tokens.push(Token::new(id, piece, (offset, offset + len))),
+                None => match self.byte_fallback_tokens(&piece, offset) {
+                    Some(byte_tokens) => tokens.extend(byte_tokens),
+                    None => tokens.push(Token::new(
+                        self.unk_id as u32,
+                        piece,
+                        (offset, offset + len),
+                    )),
+                },
+            }
+            offset += len;
         }
+        tokens
+    }
+
+    /// Decompose an unknown piece into its `<0xNN>` byte tokens, if `byte_fallback` is activated
+    /// and all of them are part of the vocabulary. Each byte token points to the entire char
+    /// it comes from.
+    fn byte_fallback_tokens(&self, piece: &str, offset: usize) -> Option<Vec<Token>> {
+        if !self.byte_fallback {
+            return None;
+        }
+        piece
+            .char_indices()
+            .flat_map(|(i, c)| {
+                let offsets = (offset + i, offset + i + c.len_utf8());
+                piece[i..i + c.len_utf8()]
+                    .bytes()
+                    .map(|b| {
+                        let token = format!("<0x{:02X}>", b);
+                        self.token_to_id(&token)
+                            .map(|id| Token::new(id, token, offsets))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
     }
 
     /// Iterate of vocabulary of the model as a pair of `(token, score)`.
@@ -397,7 +720,7 @@ impl<'a> Iterator for UnigramIterator<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let i = self.i;
         if i < self.model.len() {
-            let r = Some(&self.model.vocab[i]);
+            let r = Some(&self.model.pieces()[i]);
             self.i += 1;
             r
         } else {
@@ -408,36 +731,56 @@ impl<'a> Iterator for UnigramIterator<'a> {
 
 impl Model for Unigram {
     fn get_vocab(&self) -> &HashMap<String, u32> {
-        &self.token_to_ids
+        match &self.mapped {
+            Some(tables) => tables.vocab(),
+            None => &self.token_to_ids,
+        }
     }
 
     fn get_vocab_size(&self) -> usize {
-        self.vocab.len()
+        self.len()
     }
 
-    fn tokenize(&self, sentence: &str) -> Result<Vec<Token>> {
-        let tokens = self.encode(sentence);
-        let mut offset = 0;
-        Ok(tokens
+    fn unk_token(&self) -> Option<&str> {
+        self.id_to_token(self.unk_id as u32)
+    }
+
+    fn prune(&mut self, ids: &HashSet<u32>) -> Result<HashMap<u32, u32>> {
+        let vocab_r = self
+            .pieces()
             .iter()
-            .map(|string| {
-                let id: u32 = match self.token_to_ids.get(string) {
-                    Some(id) => *id,
-                    None => self.unk_id as u32,
-                };
-                let len = string.len();
-                let offsets = (offset, offset + len);
-                offset += len;
-                Token::new(id, string.to_string(), offsets)
-            })
-            .collect())
+            .enumerate()
+            .map(|(id, (token, _))| (id as u32, token.clone()))
+            .collect();
+        let (_, mapping) = prune_vocab(&vocab_r, ids, Some(self.unk_id as u32))?;
+        let vocab = self
+            .pieces()
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| mapping.contains_key(&(*id as u32)))
+            .map(|(_, piece)| piece.clone())
+            .collect();
+
+        self.set_vocab(vocab, mapping[&(self.unk_id as u32)] as usize)?;
+
+        Ok(mapping)
+    }
+
+    fn tokenize(&self, sentence: &str) -> Result<Vec<Token>> {
+        Ok(self.pieces_to_tokens(self.encode(sentence)))
     }
 
     fn token_to_id(&self, token: &str) -> Option<u32> {
-        self.token_to_ids.get(token).copied()
+        match &self.mapped {
+            Some(tables) => tables.token_to_id(token),
+            None => self.token_to_ids.get(token).copied(),
+        }
     }
 
     fn id_to_token(&self, id: u32) -> Option<&str> {
+        if let Some(tables) = &self.mapped {
+            return tables.id_to_token(id);
+        }
         match self.vocab.get(id as usize) {
             Some(item) => Some(&item.0),
             None => None,
@@ -532,6 +875,193 @@ mod tests {
         assert_eq!(result, vec!["abcd"]);
     }
 
+    #[test]
+    fn test_prune() {
+        let sentencepieces = vec![
+            ("a".to_string(), 0.0),
+            ("<unk>".to_string(), 0.0),
+            ("b".to_string(), 0.0),
+            ("ab".to_string(), 2.0),
+            ("abc".to_string(), 5.0),
+        ];
+
+        let mut model = Unigram::from(sentencepieces, 1).unwrap();
+        assert_eq!(model.encode("abc"), vec!["abc"]);
+        let mapping = model.prune(&[0, 4].iter().copied().collect()).unwrap();
+        assert_eq!(
+            mapping,
+            [(1, 0), (2, 1), (3, 2)]
+                .iter()
+                .copied()
+                .collect::<HashMap<_, _>>()
+        );
+        assert_eq!(model.unk_token(), Some("<unk>"));
+        assert_eq!(model.get_vocab_size(), 3);
+        assert_eq!(model.encode("abc"), vec!["ab", "c"]);
+
+        assert!(model.prune(&[0].iter().copied().collect()).is_err());
+    }
+
+    #[test]
+    fn test_byte_fallback() {
+        let sentencepieces = vec![
+            ("<unk>".to_string(), 0.0),
+            ("<0xC3>".to_string(), -0.1),
+            ("<0xA9>".to_string(), -0.1),
+            ("a".to_string(), -0.1),
+        ];
+        let mut model = Unigram::from(sentencepieces, 0).unwrap();
+
+        let tokens = model.tokenize("aé").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(3, "a".into(), (0, 1)),
+                Token::new(0, "é".into(), (1, 3)),
+            ]
+        );
+
+        model.set_byte_fallback(true);
+        let tokens = model.tokenize("aéé").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(3, "a".into(), (0, 1)),
+                Token::new(1, "<0xC3>".into(), (1, 3)),
+                Token::new(2, "<0xA9>".into(), (1, 3)),
+                Token::new(1, "<0xC3>".into(), (3, 5)),
+                Token::new(2, "<0xA9>".into(), (3, 5)),
+            ]
+        );
+
+        // Falls back on `unk` when some byte tokens are missing
+        let tokens = model.tokenize("x").unwrap();
+        assert_eq!(tokens, vec![Token::new(0, "x".into(), (0, 1))]);
+    }
+
+    fn sampling_model() -> Unigram {
+        let sentencepieces = vec![
+            ("<unk>".to_string(), 0.0),
+            ("a".to_string(), -2.0),
+            ("b".to_string(), -2.0),
+            ("c".to_string(), -2.0),
+            ("ab".to_string(), -2.5),
+            ("bc".to_string(), -2.5),
+            ("abc".to_string(), -3.0),
+        ];
+        Unigram::from(sentencepieces, 0).unwrap()
+    }
+
+    #[test]
+    fn test_encode_nbest() {
+        let model = sampling_model();
+
+        let nbest = model.encode_nbest("abc", 10);
+        assert_eq!(nbest.len(), 4);
+        assert_eq!(nbest[0], (vec!["abc".to_string()], -3.0));
+        assert_eq!(nbest[3], (vec!["a".into(), "b".into(), "c".into()], -6.0));
+        assert!(nbest.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(nbest[0].0, model.encode("abc"));
+
+        let tokens = model.tokenize_nbest("abcx", 2).unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(
+            tokens[0].0,
+            vec![
+                Token::new(6, "abc".into(), (0, 3)),
+                Token::new(0, "x".into(), (3, 4)),
+            ]
+        );
+        assert_eq!(
+            tokens[1].0,
+            vec![
+                Token::new(4, "ab".into(), (0, 2)),
+                Token::new(3, "c".into(), (2, 3)),
+                Token::new(0, "x".into(), (3, 4)),
+            ]
+        );
+        assert!(model.encode_nbest("", 2).is_empty());
+    }
+
+    #[test]
+    fn test_sampling() {
+        let mut model = sampling_model();
+        let sentence = "abcabcabc";
+
+        // Without sampling (or with `nbest_size` of 1) we always get the best segmentation
+        for nbest_size in &[0, 1] {
+            model.set_sampling(Some(UnigramSampling {
+                alpha: 0.1,
+                nbest_size: *nbest_size,
+                seed: None,
+            }));
+            assert_eq!(model.encode(sentence), vec!["abc", "abc", "abc"]);
+        }
+
+        for nbest_size in &[-1, 5] {
+            let sample = |model: &mut Unigram, seed| {
+                model.set_sampling(Some(UnigramSampling {
+                    alpha: 0.1,
+                    nbest_size: *nbest_size,
+                    seed: Some(seed),
+                }));
+                model.tokenize(sentence).unwrap()
+            };
+            let first = (0..20)
+                .map(|seed| sample(&mut model, seed))
+                .collect::<Vec<_>>();
+            // With such a low alpha, we get several segmentations
+            assert!(first.iter().any(|tokens| tokens != &first[0]));
+            for tokens in &first {
+                // Offsets always cover the entire sentence, and match the pieces
+                let mut offset = 0;
+                for token in tokens {
+                    assert_eq!(token.offsets.0, offset);
+                    assert_eq!(&sentence[token.offsets.0..token.offsets.1], token.value);
+                    offset = token.offsets.1;
+                }
+                assert_eq!(offset, sentence.len());
+            }
+
+            // The same seed gives the same results
+            let second = (0..20)
+                .map(|seed| sample(&mut model, seed))
+                .collect::<Vec<_>>();
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_sampling_occurrences() {
+        let mut model = sampling_model();
+        let sampling = Some(UnigramSampling {
+            alpha: 0.1,
+            nbest_size: -1,
+            seed: Some(42),
+        });
+        model.set_sampling(sampling);
+        let first = (0..20).map(|_| model.encode("abc")).collect::<Vec<_>>();
+        // Each occurrence of a word gets its own segmentation
+        assert!(first.iter().any(|pieces| pieces != &first[0]));
+        // The ChaCha8 generator gives these on every platform
+        assert_eq!(
+            first[..3],
+            [vec!["a", "b", "c"], vec!["a", "bc"], vec!["a", "b", "c"]]
+        );
+
+        // The generator starts over with `set_sampling`, and is cloned along with the model
+        model.set_sampling(sampling);
+        let clone = model.clone();
+        assert_eq!(
+            (0..20).map(|_| model.encode("abc")).collect::<Vec<_>>(),
+            first
+        );
+        assert_eq!(
+            (0..20).map(|_| clone.encode("abc")).collect::<Vec<_>>(),
+            first
+        );
+    }
+
     #[test]
     fn test_encode2() {
         let sentencepieces = vec![
@@ -1,4 +1,4 @@
-use super::model::Unigram;
+use super::model::{Unigram, UnigramSampling};
 use serde::{
     de::{Error, MapAccess, Visitor},
     ser::SerializeStruct,
@@ -10,10 +10,14 @@ impl Serialize for Unigram {
     where
         S: Serializer,
     {
-        let mut model = serializer.serialize_struct("Unigram", 2)?;
+        let mut model = serializer.serialize_struct("Unigram", 6)?;
 
+        model.serialize_field("type", "Unigram")?;
         model.serialize_field("unk_id", &self.unk_id)?;
-        model.serialize_field("vocab", &self.vocab)?;
+        model.serialize_field("byte_fallback", &self.byte_fallback())?;
+        model.serialize_field("sampling", &self.sampling())?;
+        model.serialize_field("cache_capacity", &self.get_cache_capacity())?;
+        model.serialize_field("vocab", self.pieces())?;
 
         model.end()
     }
@@ -24,7 +28,18 @@ impl<'de> Deserialize<'de> for Unigram {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_struct("Unigram", &["vocab", "unk_id"], UnigramVisitor)
+        deserializer.deserialize_struct(
+            "Unigram",
+            &[
+                "type",
+                "vocab",
+                "unk_id",
+                "byte_fallback",
+                "sampling",
+                "cache_capacity",
+            ],
+            UnigramVisitor,
+        )
     }
 }
 
@@ -42,21 +57,45 @@ impl<'de> Visitor<'de> for UnigramVisitor {
     {
         let mut vocab: Option<Vec<(String, f64)>> = None;
         let mut unk_id: Option<usize> = None;
+        let mut byte_fallback = false;
+        let mut sampling: Option<UnigramSampling> = None;
+        let mut cache_capacity: Option<usize> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_ref() {
+                "type" => {
+                    let model_type: String = map.next_value()?;
+                    if model_type != "Unigram" {
+                        return Err(Error::custom(format!(
+                            "Expected Unigram, got {}",
+                            model_type
+                        )));
+                    }
+                }
                 "unk_id" => {
                     unk_id = map.next_value()?;
                 }
+                "byte_fallback" => {
+                    byte_fallback = map.next_value::<Option<bool>>()?.unwrap_or(false);
+                }
+                "sampling" => sampling = map.next_value()?,
+                "cache_capacity" => cache_capacity = map.next_value()?,
                 "vocab" => vocab = Some(map.next_value()?),
                 _ => (),
             }
         }
         match (vocab, unk_id) {
-            (Some(vocab), Some(unk_id)) => Ok(Unigram::from(vocab, unk_id)
-                .map_err(|err| Error::custom(&format!("Unable to load vocab {:?}", err)))?),
-            (None, Some(_)) => Err(Error::custom("Missing vocab")),
-            (None, None) => Err(Error::custom("Missing vocab and unk_id")),
-            (Some(_), None) => Err(Error::custom("Missing unk_id")),
+            (Some(vocab), Some(unk_id)) => {
+                let mut model = Unigram::from(vocab, unk_id)
+                    .map_err(|err| Error::custom(&format!("Unable to load vocab {:?}", err)))?;
+                model.set_byte_fallback(byte_fallback);
+                model.set_sampling(sampling);
+                if let Some(capacity) = cache_capacity {
+                    model.set_cache_capacity(capacity);
+                }
+                Ok(model)
+            }
+            (None, _) => Err(Error::missing_field("vocab")),
+            (Some(_), None) => Err(Error::missing_field("unk_id")),
         }
     }
 }
@@ -76,6 +115,55 @@ mod test {
         assert_eq!(model, reconstructed);
     }
 
+    #[test]
+    fn test_serialization_byte_fallback() {
+        let vocab = vec![("<unk>".to_string(), 0.0), ("<0x41>".to_string(), -0.5)];
+        let mut model = Unigram::from(vocab, 0).unwrap();
+        model.set_byte_fallback(true);
+
+        let data = serde_json::to_string(&model).unwrap();
+        let reconstructed: Unigram = serde_json::from_str(&data).unwrap();
+
+        assert_eq!(model, reconstructed);
+        assert!(reconstructed.byte_fallback());
+    }
+
+    #[test]
+    fn test_serialization_sampling() {
+        let vocab = vec![("<unk>".to_string(), 0.0), ("a".to_string(), -0.5)];
+        let mut model = Unigram::from(vocab, 0).unwrap();
+        model.set_sampling(Some(UnigramSampling {
+            alpha: 0.1,
+            nbest_size: -1,
+            seed: Some(42),
+        }));
+
+        let data = serde_json::to_string(&model).unwrap();
+        let reconstructed: Unigram = serde_json::from_str(&data).unwrap();
+
+        assert_eq!(model, reconstructed);
+        assert_eq!(reconstructed.sampling(), model.sampling());
+    }
+
+    #[test]
+    fn test_serialization_cache_capacity() {
+        let vocab = vec![("<unk>".to_string(), 0.0), ("a".to_string(), -0.5)];
+        let mut model = Unigram::from(vocab, 0).unwrap();
+        model.set_cache_capacity(42);
+
+        let data = serde_json::to_string(&model).unwrap();
+        let reconstructed: Unigram = serde_json::from_str(&data).unwrap();
+        assert_eq!(reconstructed.get_cache_capacity(), 42);
+
+        // Older files don't specify it
+        let reconstructed: Unigram =
+            serde_json::from_str(r#"{"unk_id":0,"vocab":[["<unk>",0.0]]}"#).unwrap();
+        assert_eq!(
+            reconstructed.get_cache_capacity(),
+            crate::utils::cache::DEFAULT_CACHE_CAPACITY
+        );
+    }
+
     #[test]
     fn test_serialization_unk_id_not_zero() {
         let vocab = vec![("a".to_string(), -0.5), ("<unk>".to_string(), 0.0)];
@@ -56,6 +56,15 @@ pub struct UnigramTrainer {
     max_piece_length: usize,
     #[builder(default = "1_000_000")]
     seed_size: usize,
+
+    /// An optional model to extend, whose pieces keep their ids and scores
+    #[builder(default = "None", setter(strip_option))]
+    base_model: Option<Unigram>,
+    /// The maximum number of new pieces to add to the vocabulary, including the chars missing
+    /// from the base model. The training stops as soon as this number, or `vocab_size`, is
+    /// reached
+    #[builder(default = "None", setter(strip_option))]
+    max_new_tokens: Option<usize>,
 }
 
 impl UnigramTrainer {
@@ -77,6 +86,21 @@ impl UnigramTrainer {
         }
     }
 
+    /// The number of pieces that are always kept: the unknown token, or all the pieces of the
+    /// base model, which come first
+    fn kept_pieces(&self) -> usize {
+        self.base_model.as_ref().map_or(1, |base| base.len())
+    }
+
+    /// The size of the final vocabulary, that can't be smaller than the base model
+    fn final_vocab_size(&self) -> usize {
+        let base_size = self.base_model.as_ref().map_or(0, |base| base.len());
+        let vocab_size = self.vocab_size as usize;
+        self.max_new_tokens
+            .map_or(vocab_size, |max| vocab_size.min(base_size + max))
+            .max(base_size)
+    }
+
     fn is_valid_sentencepiece(&self, char_string: &[char]) -> bool {
         // Checks string length
         // Space not in the substring, numbers, hiragana and more should be taken
@@ -91,6 +115,10 @@ impl UnigramTrainer {
     }
 
     fn finalize(&self, model: Unigram, required_chars: HashSet<String>) -> Result<Unigram> {
+        if let Some(base) = &self.base_model {
+            return self.finalize_extension(base, model, required_chars);
+        }
+
         let mut min_score_penalty = 0.0;
         let min_score_penalty_delta = 0.0001;
 
@@ -122,6 +150,83 @@ impl UnigramTrainer {
         Unigram::from(final_pieces, 0)
     }
 
+    /// Keep all the pieces of the base model as they are, and add the best new ones after them.
+    ///
+    /// The chars of the corpus missing from the base model come first, but they count as new
+    /// pieces too: when they alone exceed the size limit, only the most likely ones are added,
+    /// and the other ones are left to the unknown token. The scores of the new pieces are
+    /// shifted by the mean difference between the original and retrained scores of the base
+    /// pieces, to bring them on the same scale as the base model.
+    fn finalize_extension(
+        &self,
+        base: &Unigram,
+        model: Unigram,
+        required_chars: HashSet<String>,
+    ) -> Result<Unigram> {
+        let mut min_score_penalty = 0.0;
+        let min_score_penalty_delta = 0.0001;
+
+        let mut pieces: Vec<SentencePiece> = base.iter().cloned().collect();
+        let mut seen: HashSet<String> = pieces.iter().map(|(token, _)| token.clone()).collect();
+        let existing_pieces: HashMap<String, f64> = model.iter().cloned().collect();
+        let max_new_pieces = self.final_vocab_size() - pieces.len();
+
+        let offsets = pieces
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| *id != base.unk_id)
+            .filter_map(|(_, (token, score))| {
+                existing_pieces
+                    .get(token)
+                    .map(|retrained| score - retrained)
+            })
+            .filter(|offset| offset.is_finite())
+            .collect::<Vec<_>>();
+        let offset = if offsets.is_empty() {
+            0.0
+        } else {
+            offsets.iter().sum::<f64>() / offsets.len() as f64
+        };
+
+        let mut required_chars = required_chars
+            .into_iter()
+            .filter(|c| !seen.contains(c))
+            .collect::<Vec<_>>();
+        required_chars.sort_unstable();
+        let mut new_pieces: Vec<SentencePiece> = vec![];
+        for c in required_chars {
+            if let Some(score) = existing_pieces.get(&c) {
+                new_pieces.push((c, *score));
+            } else {
+                new_pieces.push((c, model.min_score + min_score_penalty));
+                min_score_penalty += min_score_penalty_delta;
+            }
+        }
+        new_pieces.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+        new_pieces.truncate(max_new_pieces);
+        seen.extend(new_pieces.iter().map(|(c, _)| c.clone()));
+
+        let mut candidates = model
+            .iter()
+            .filter(|(token, _)| !seen.contains(token))
+            .cloned()
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+        let remaining = max_new_pieces - new_pieces.len();
+        new_pieces.extend(candidates.into_iter().take(remaining));
+
+        new_pieces.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+        pieces.extend(
+            new_pieces
+                .into_iter()
+                .map(|(token, score)| (token, score + offset)),
+        );
+        let mut unigram = Unigram::from(pieces, base.unk_id)?;
+        unigram.set_byte_fallback(base.byte_fallback());
+        unigram.set_sampling(base.sampling().cloned());
+        Ok(unigram)
+    }
+
     fn required_chars(&self, word_counts: &[Sentence]) -> HashSet<String> {
         word_counts
             .iter()
@@ -210,6 +315,7 @@ impl UnigramTrainer {
         pieces: &[SentencePiece],
         sentences: &[Sentence],
     ) -> Vec<SentencePiece> {
+        let kept_pieces = self.kept_pieces();
         let mut always_keep = vec![true; pieces.len()];
         let mut alternatives: Vec<Vec<usize>> = vec![Vec::new(); pieces.len()];
 
@@ -222,12 +328,12 @@ impl UnigramTrainer {
         // To do so, we take the second best segmentation of sentencepiece[i].
         // alternatives[i] stores the sequence of second best sentencepieces.
         for (id, (token, _score)) in pieces.iter().enumerate() {
-            // Always keep unk.
-            if id == 0 {
+            // Always keep unk, and the pieces of the base model.
+            if id < kept_pieces {
                 always_keep[id] = false;
                 continue;
             }
-            let mut lattice = Lattice::from(token, 0, bos_id, eos_id);
+            let mut lattice = Lattice::from(token, model.unk_id, bos_id, eos_id);
             model.populate_nodes(&mut lattice);
 
             let nbests = lattice.nbest(2);
@@ -252,7 +358,7 @@ impl UnigramTrainer {
         let mut inverted: Vec<Vec<usize>> = vec![Vec::new(); pieces.len()];
         // TODO reparallelize this
         for (i, (sentence, count)) in sentences.iter().enumerate() {
-            let mut lattice = Lattice::from(sentence, 0, bos_id, eos_id);
+            let mut lattice = Lattice::from(sentence, model.unk_id, bos_id, eos_id);
             model.populate_nodes(&mut lattice);
             vsum += *count as f64;
             for node_ref in lattice.viterbi() {
@@ -266,7 +372,7 @@ impl UnigramTrainer {
         let logsum = sum.ln();
         let mut candidates: Vec<(usize, f64)> = vec![];
         let mut new_pieces: Vec<SentencePiece> = Vec::with_capacity(self.vocab_size as usize);
-        new_pieces.push(pieces[0].clone());
+        new_pieces.extend(pieces[..kept_pieces].iter().cloned());
 
         // Finally, computes how likely the LM likelihood is reduced if
         // the sentencepiece[i] is removed from the vocabulary.
@@ -274,7 +380,7 @@ impl UnigramTrainer {
         // loss approximately by assuming that all sentencepiece[i] in the sentences
         // are replaced with alternatives[i] when sentencepiece[i] is removed.
         for (id, (token, score)) in pieces.iter().enumerate() {
-            if id == 0 {
+            if id < kept_pieces {
                 continue;
             }
             if freq[id] == 0.0 && !always_keep[id] {
@@ -320,13 +426,13 @@ impl UnigramTrainer {
                 candidates.push((id, loss));
             }
         }
-        let desired_vocab_size: usize = (self.vocab_size as usize * 11) / 10; // * 1.1
+        let desired_vocab_size: usize = (self.final_vocab_size() * 11) / 10; // * 1.1
         let pruned_size: usize = ((pieces.len() as f64) * self.shrinking_factor) as usize;
         let pruned_size = desired_vocab_size.max(pruned_size);
 
         candidates.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
         for (id, _score) in candidates {
-            if new_pieces.len() == pruned_size {
+            if new_pieces.len() >= pruned_size {
                 break;
             }
             new_pieces.push(pieces[id].clone());
@@ -388,16 +494,24 @@ impl UnigramTrainer {
 
         let mut sum = 0.0;
         let expected_frequency_threshold = 0.5;
+        let kept_pieces = self.kept_pieces();
+        let unk_id = self.base_model.as_ref().map_or(0, |base| base.unk_id);
         for (i, (freq, (piece, _))) in expected.iter().zip(pieces).enumerate() {
             // We keep unk.
-            if i == 0 {
+            if i == unk_id {
                 new_pieces.push((piece.clone(), f64::NAN));
                 continue;
             }
-            if *freq < expected_frequency_threshold {
+            // And the pieces of the base model, even when they are not used anymore
+            let freq = if i < kept_pieces {
+                freq.max(expected_frequency_threshold)
+            } else {
+                *freq
+            };
+            if freq < expected_frequency_threshold {
                 continue;
             }
-            new_pieces.push((piece.clone(), *freq));
+            new_pieces.push((piece.clone(), freq));
             sum += freq;
         }
         // // Here we do not use the original EM, but use the
@@ -419,9 +533,21 @@ impl UnigramTrainer {
         self.update_progress(&progress, sentences.len(), "Suffix array seeds");
         let mut pieces: Vec<SentencePiece> =
             Vec::with_capacity(self.vocab_size.try_into().unwrap());
-        // XXX: Make sure unk exists and are ids 0
-        pieces.push((self.unk_token.clone(), f64::NAN));
-        pieces.extend(self.make_seed_sentence_pieces(&sentences, &progress)?);
+        let unk_id = if let Some(base) = &self.base_model {
+            // Start from all the pieces of the base model, followed by the new seeds
+            pieces.extend(base.iter().cloned());
+            base.unk_id
+        } else {
+            // XXX: Make sure unk exists and are ids 0
+            pieces.push((self.unk_token.clone(), f64::NAN));
+            0
+        };
+        let existing: HashSet<String> = pieces.iter().map(|(token, _)| token.clone()).collect();
+        pieces.extend(
+            self.make_seed_sentence_pieces(&sentences, &progress)?
+                .into_iter()
+                .filter(|(token, _)| !existing.contains(token)),
+        );
         self.finalize_progress(&progress, sentences.len());
 
         // Useful to check compatibility with spm.
@@ -431,7 +557,7 @@ impl UnigramTrainer {
             sentences.len()
         );
 
-        let desired_vocab_size: usize = (self.vocab_size as usize * 11) / 10; // * 1.1
+        let desired_vocab_size: usize = (self.final_vocab_size() * 11) / 10; // * 1.1
 
         // 2. Run E-M Loops to fine grain the pieces.
         // We will shrink the vocab by shrinking_factor every loop on average
@@ -444,7 +570,7 @@ impl UnigramTrainer {
         let expected_updates = expected_loops as usize * self.n_sub_iterations as usize;
         self.update_progress(&progress, expected_updates, "EM training");
         let required_chars = self.required_chars(&sentences);
-        let mut model = Unigram::from(pieces.clone(), 0)?;
+        let mut model = Unigram::from(pieces.clone(), unk_id)?;
         loop {
             // Sub-EM iteration.
             for _iter in 0..self.n_sub_iterations {
@@ -453,7 +579,7 @@ impl UnigramTrainer {
 
                 // Executes M step.
                 pieces = self.run_m_step(&pieces, &expected);
-                model = Unigram::from(pieces.clone(), 0)?;
+                model = Unigram::from(pieces.clone(), unk_id)?;
 
                 // Useful comment for checking compatibility with spm
                 debug!(
@@ -477,7 +603,7 @@ impl UnigramTrainer {
 
             // Prunes pieces.
             pieces = self.prune_sentence_pieces(&model, &pieces, &sentences);
-            model = Unigram::from(pieces.clone(), 0)?;
+            model = Unigram::from(pieces.clone(), unk_id)?;
         }
         self.finalize_progress(&progress, expected_updates);
 
@@ -579,4 +705,91 @@ mod tests {
         // ln(2) - ln(3)
         assert_approx_eq!(scores[1], -0.405, 0.01);
     }
+
+    #[test]
+    fn test_train_extend() {
+        let trainer = UnigramTrainerBuilder::default()
+            .show_progress(false)
+            .build()
+            .unwrap();
+        let sentences = vec![
+            ("the".to_string(), 10),
+            ("cat".to_string(), 5),
+            ("hat".to_string(), 4),
+        ];
+        let (base, _) = trainer._train(sentences).unwrap();
+
+        let trainer = UnigramTrainerBuilder::default()
+            .show_progress(false)
+            .base_model(base.clone())
+            .max_new_tokens(5)
+            .build()
+            .unwrap();
+        let sentences = vec![
+            ("dog".to_string(), 10),
+            ("dogs".to_string(), 6),
+            ("the".to_string(), 3),
+        ];
+        let (model, _) = trainer._train(sentences).unwrap();
+
+        // The pieces of the base model keep their ids and scores
+        let pieces = model.iter().cloned().collect::<Vec<_>>();
+        let base_pieces = base.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(&pieces[..base.len()], &base_pieces[..]);
+        assert_eq!(model.unk_id, base.unk_id);
+        // The new chars are always added, and count as new pieces
+        let new_pieces = pieces[base.len()..]
+            .iter()
+            .map(|(token, _)| token.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(new_pieces, vec!["dog", "s", "o", "g", "d"]);
+    }
+
+    #[test]
+    fn test_finalize_extension() {
+        let base = Unigram::from(
+            vec![
+                ("<unk>".to_string(), 0.0),
+                ("a".to_string(), -1.0),
+                ("b".to_string(), -2.0),
+            ],
+            0,
+        )
+        .unwrap();
+        // The retrained scores of the base pieces are 10 lower than the original ones
+        let model = Unigram::from(
+            vec![
+                ("<unk>".to_string(), f64::NAN),
+                ("a".to_string(), -11.0),
+                ("b".to_string(), -12.0),
+                ("ab".to_string(), -10.5),
+                ("c".to_string(), -13.0),
+            ],
+            0,
+        )
+        .unwrap();
+        let required_chars: HashSet<String> =
+            vec!["a", "b", "c"].into_iter().map(String::from).collect();
+
+        let pieces = |max_new_tokens| {
+            UnigramTrainerBuilder::default()
+                .base_model(base.clone())
+                .max_new_tokens(max_new_tokens)
+                .build()
+                .unwrap()
+                .finalize(model.clone(), required_chars.clone())
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        // The new pieces are brought on the scale of the base model
+        assert_eq!(
+            pieces(2)[3..],
+            [("ab".to_string(), -0.5), ("c".to_string(), -3.0)]
+        );
+        // The missing chars are kept first, but count as new pieces
+        assert_eq!(pieces(1)[3..], [("c".to_string(), -3.0)]);
+        assert_eq!(pieces(0).len(), 3);
+    }
 }
@@ -1,13 +1,16 @@
-use super::OrderedVocabIter;
+use super::{prune_vocab, OrderedVocabIter};
 use crate::tokenizer::{Model, Result, Token};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fs::File;
 use std::io::{BufReader, Read, Write};
 use std::path::{Path, PathBuf};
 
 mod serialization;
+mod trainer;
+
+pub use trainer::*;
 
 type Vocab = HashMap<String, u32>;
 
@@ -176,6 +179,19 @@ impl Model for WordLevel {
         self.vocab.keys().len()
     }
 
+    fn unk_token(&self) -> Option<&str> {
+        Some(&self.unk_token)
+    }
+
+    fn prune(&mut self, ids: &HashSet<u32>) -> Result<HashMap<u32, u32>> {
+        let unk_id = self.vocab.get(&self.unk_token).copied();
+        let (vocab, mapping) = prune_vocab(&self.vocab_r, ids, unk_id)?;
+        self.vocab_r = vocab.iter().map(|(t, id)| (*id, t.clone())).collect();
+        self.vocab = vocab;
+
+        Ok(mapping)
+    }
+
     fn save(&self, folder: &Path, name: Option<&str>) -> Result<Vec<PathBuf>> {
         let vocab_file_name = match name {
             Some(name) => format!("{}-vocab.json", name),
@@ -1,6 +1,6 @@
 use super::{super::OrderedVocabIter, WordLevel, WordLevelBuilder};
 use serde::{
-    de::{MapAccess, Visitor},
+    de::{Error, MapAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -10,7 +10,8 @@ impl Serialize for WordLevel {
     where
         S: Serializer,
     {
-        let mut model = serializer.serialize_struct("WordLevel", 2)?;
+        let mut model = serializer.serialize_struct("WordLevel", 3)?;
+        model.serialize_field("type", "WordLevel")?;
         let ordered_vocab = OrderedVocabIter::new(&self.vocab_r);
         model.serialize_field("vocab", &ordered_vocab)?;
         model.serialize_field("unk_token", &self.unk_token)?;
@@ -23,7 +24,11 @@ impl<'de> Deserialize<'de> for WordLevel {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_struct("WordLevel", &["vocab", "unk_token"], WordLevelVisitor)
+        deserializer.deserialize_struct(
+            "WordLevel",
+            &["type", "vocab", "unk_token"],
+            WordLevelVisitor,
+        )
     }
 }
 
@@ -42,6 +47,15 @@ impl<'de> Visitor<'de> for WordLevelVisitor {
         let mut builder = WordLevelBuilder::new();
         while let Some(key) = map.next_key::<String>()? {
             match key.as_ref() {
+                "type" => {
+                    let model_type: String = map.next_value()?;
+                    if model_type != "WordLevel" {
+                        return Err(Error::custom(format!(
+                            "Expected WordLevel, got {}",
+                            model_type
+                        )));
+                    }
+                }
                 "vocab" => builder = builder.vocab(map.next_value()?),
                 "unk_token" => builder = builder.unk_token(map.next_value()?),
                 _ => {}
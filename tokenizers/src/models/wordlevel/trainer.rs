@@ -0,0 +1,215 @@
+use super::WordLevel;
+use crate::tokenizer::{AddedToken, Result, Trainer};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// A `WordLevelTrainer` can train a `WordLevel` model from `word_counts`.
+#[derive(Builder, Debug, Clone)]
+pub struct WordLevelTrainer {
+    /// The minimum frequency a word must have to be part of the vocabulary
+    #[builder(default = "0")]
+    min_frequency: u32,
+    /// The target vocabulary size
+    #[builder(default = "30_000")]
+    vocab_size: usize,
+    /// Whether to show progress while training
+    #[builder(default = "true")]
+    show_progress: bool,
+    /// A list of special tokens that the model should know of
+    #[builder(default = "vec![]")]
+    special_tokens: Vec<AddedToken>,
+    /// The token used for unknown words. It is always part of the vocabulary, as a special
+    /// token, even when missing from `special_tokens`.
+    #[builder(default = "String::from(\"<unk>\")")]
+    unk_token: String,
+}
+
+impl Default for WordLevelTrainer {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
+}
+
+impl WordLevelTrainer {
+    pub fn builder() -> WordLevelTrainerBuilder {
+        WordLevelTrainerBuilder::default()
+    }
+
+    /// The special tokens, starting with the unknown token if it was not already part of them
+    fn special_tokens(&self) -> Vec<AddedToken> {
+        let mut special_tokens = self.special_tokens.clone();
+        if !special_tokens.iter().any(|t| t.content == self.unk_token) {
+            special_tokens.insert(0, AddedToken::from(self.unk_token.clone(), true));
+        }
+        special_tokens
+    }
+
+    fn do_train(
+        &self,
+        word_counts: &HashMap<String, u32>,
+        special_tokens: &[AddedToken],
+    ) -> Result<WordLevel> {
+        // Most frequent words first. Words with the same frequency are sorted alphabetically
+        // to keep the result deterministic.
+        let mut ordered_counts = word_counts.iter().collect::<Vec<_>>();
+        ordered_counts.sort_by(|(wa, ca), (wb, cb)| match cb.cmp(ca) {
+            Ordering::Equal => wa.cmp(wb),
+            o => o,
+        });
+
+        let mut vocab = HashMap::with_capacity(self.vocab_size);
+        for token in special_tokens.iter().map(|t| &t.content).chain(
+            ordered_counts
+                .into_iter()
+                .filter(|(_, count)| **count >= self.min_frequency)
+                .map(|(word, _)| word),
+        ) {
+            if vocab.len() >= self.vocab_size {
+                break;
+            }
+            if !vocab.contains_key(token) {
+                vocab.insert(token.to_owned(), vocab.len() as u32);
+            }
+        }
+
+        Ok(WordLevel::builder()
+            .vocab(vocab)
+            .unk_token(self.unk_token.clone())
+            .build())
+    }
+}
+
+impl Trainer for WordLevelTrainer {
+    type Model = WordLevel;
+
+    /// Train a WordLevel model
+    fn train(&self, word_counts: HashMap<String, u32>) -> Result<(WordLevel, Vec<AddedToken>)> {
+        let special_tokens = self.special_tokens();
+        let model = self.do_train(&word_counts, &special_tokens)?;
+        Ok((model, special_tokens))
+    }
+
+    /// Process a bunch of tokens, counting them
+    fn process_tokens(&self, words: &mut HashMap<String, u32>, tokens: Vec<String>) {
+        for token in tokens {
+            words
+                .entry(token.clone())
+                .and_modify(|c| *c += 1)
+                .or_insert(1);
+        }
+    }
+
+    /// Whether we should show progress
+    fn should_show_progress(&self) -> bool {
+        self.show_progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Model;
+
+    #[test]
+    fn test_train() {
+        let word_counts: HashMap<String, u32> = [
+            ("the".into(), 25),
+            ("roses".into(), 22),
+            ("are".into(), 24),
+            ("red".into(), 12),
+            ("voilets".into(), 10),
+            ("blue".into(), 16),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let trainer = WordLevelTrainer::builder().vocab_size(5).build().unwrap();
+        let (model, _) = trainer.train(word_counts.clone()).unwrap();
+        let expected_vocab: HashMap<String, u32> = [
+            ("<unk>".into(), 0),
+            ("the".into(), 1),
+            ("are".into(), 2),
+            ("roses".into(), 3),
+            ("blue".into(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(model.get_vocab(), &expected_vocab);
+
+        // If we specify a min_frequency
+        let trainer = WordLevelTrainer::builder()
+            .min_frequency(15)
+            .build()
+            .unwrap();
+        let (model, _) = trainer.train(word_counts).unwrap();
+        let expected_vocab: HashMap<String, u32> = [
+            ("<unk>".into(), 0),
+            ("the".into(), 1),
+            ("are".into(), 2),
+            ("roses".into(), 3),
+            ("blue".into(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(model.get_vocab(), &expected_vocab);
+    }
+
+    #[test]
+    fn test_train_special_tokens_and_ties() {
+        let word_counts: HashMap<String, u32> = [
+            ("b".into(), 2),
+            ("c".into(), 2),
+            ("a".into(), 2),
+            ("<unk>".into(), 1),
+            ("d".into(), 3),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let trainer = WordLevelTrainer::builder()
+            .special_tokens(vec![
+                AddedToken::from("<unk>", true),
+                AddedToken::from("<pad>", true),
+            ])
+            .vocab_size(5)
+            .build()
+            .unwrap();
+        let (model, special_tokens) = trainer.train(word_counts).unwrap();
+        assert_eq!(special_tokens.len(), 2);
+        let expected_vocab: HashMap<String, u32> = [
+            ("<unk>".into(), 0),
+            ("<pad>".into(), 1),
+            ("d".into(), 2),
+            ("a".into(), 3),
+            ("b".into(), 4),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        assert_eq!(model.get_vocab(), &expected_vocab);
+    }
+
+    #[test]
+    fn test_train_unk_token() {
+        let word_counts: HashMap<String, u32> = [("hello".into(), 2), ("world".into(), 1)]
+            .iter()
+            .cloned()
+            .collect();
+
+        let trainer = WordLevelTrainer::builder()
+            .unk_token("[UNK]".into())
+            .build()
+            .unwrap();
+        let (model, special_tokens) = trainer.train(word_counts).unwrap();
+        assert_eq!(special_tokens, vec![AddedToken::from("[UNK]", true)]);
+        assert_eq!(model.token_to_id("[UNK]"), Some(0));
+
+        // Unknown words use the unknown token instead of failing
+        let tokens = model.tokenize("goodbye").unwrap();
+        assert_eq!(tokens[0].id, 0);
+    }
+}
@@ -1,11 +1,11 @@
 //! [WordPiece](https://static.googleusercontent.com/media/research.google.com/en//pubs/archive/37842.pdf)
 //! model.
 
-use crate::models::bpe::BPE;
+use crate::models::{bpe::BPE, prune_vocab};
 use crate::tokenizer::{Model, Result, Token};
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     fs::File,
     io::prelude::*,
@@ -208,6 +208,19 @@ impl Model for WordPiece {
         self.vocab.len()
     }
 
+    fn unk_token(&self) -> Option<&str> {
+        Some(&self.unk_token)
+    }
+
+    fn prune(&mut self, ids: &HashSet<u32>) -> Result<HashMap<u32, u32>> {
+        let unk_id = self.vocab.get(&self.unk_token).copied();
+        let (vocab, mapping) = prune_vocab(&self.vocab_r, ids, unk_id)?;
+        self.vocab_r = vocab.iter().map(|(t, id)| (*id, t.clone())).collect();
+        self.vocab = vocab;
+
+        Ok(mapping)
+    }
+
     fn tokenize(&self, sequence: &str) -> Result<Vec<Token>> {
         let char_len = sequence.chars().count();
         if char_len > self.max_input_chars_per_word {
@@ -1,6 +1,6 @@
 use super::{super::OrderedVocabIter, WordPiece, WordPieceBuilder};
 use serde::{
-    de::{MapAccess, Visitor},
+    de::{Error, MapAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -10,9 +10,10 @@ impl Serialize for WordPiece {
     where
         S: Serializer,
     {
-        let mut model = serializer.serialize_struct("WordPiece", 4)?;
+        let mut model = serializer.serialize_struct("WordPiece", 5)?;
 
         // Small fields first
+        model.serialize_field("type", "WordPiece")?;
         model.serialize_field("unk_token", &self.unk_token)?;
         model.serialize_field("continuing_subword_prefix", &self.continuing_subword_prefix)?;
         model.serialize_field("max_input_chars_per_word", &self.max_input_chars_per_word)?;
@@ -33,6 +34,7 @@ impl<'de> Deserialize<'de> for WordPiece {
         deserializer.deserialize_struct(
             "WordPiece",
             &[
+                "type",
                 "unk_token",
                 "continuing_subword_prefix",
                 "max_input_chars_per_word",
@@ -58,6 +60,15 @@ impl<'de> Visitor<'de> for WordPieceVisitor {
         let mut builder = WordPieceBuilder::new();
         while let Some(key) = map.next_key::<String>()? {
             match key.as_ref() {
+                "type" => {
+                    let model_type: String = map.next_value()?;
+                    if model_type != "WordPiece" {
+                        return Err(Error::custom(format!(
+                            "Expected WordPiece, got {}",
+                            model_type
+                        )));
+                    }
+                }
                 "unk_token" => builder = builder.unk_token(map.next_value()?),
                 "continuing_subword_prefix" => {
                     builder = builder.continuing_subword_prefix(map.next_value()?)
@@ -69,6 +80,6 @@ impl<'de> Visitor<'de> for WordPieceVisitor {
                 _ => {}
             }
         }
-        Ok(builder.build().map_err(serde::de::Error::custom)?)
+        Ok(builder.build().map_err(Error::custom)?)
     }
 }
@@ -12,12 +12,13 @@ pub use crate::normalizers::strip::{Strip, StripAccents};
 pub use crate::normalizers::unicode::{Nmt, NFC, NFD, NFKC, NFKD};
 pub use crate::normalizers::utils::{Lowercase, Sequence};
 
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
+use crate::utils::registry::{Custom, Registry};
 use crate::{NormalizedString, Normalizer};
 
 /// Wrapper for known Normalizers.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[serde(untagged)]
 pub enum NormalizerWrapper {
     BertNormalizer(BertNormalizer),
@@ -32,6 +33,32 @@ pub enum NormalizerWrapper {
     Nmt(Nmt),
     Precompiled(Precompiled),
     Replace(Replace),
+    Custom(CustomNormalizer),
+}
+
+impl_serde_tagged_wrapper!(NormalizerWrapper, "normalizer", Custom(REGISTRY), {
+    "BertNormalizer" => BertNormalizer(BertNormalizer),
+    "Strip" => StripNormalizer(Strip),
+    "StripAccents" => StripAccents(StripAccents),
+    "NFC" => NFC(NFC),
+    "NFD" => NFD(NFD),
+    "NFKC" => NFKC(NFKC),
+    "NFKD" => NFKD(NFKD),
+    "Sequence" => Sequence(Sequence),
+    "Lowercase" => Lowercase(Lowercase),
+    "Nmt" => Nmt(Nmt),
+    "Precompiled" => Precompiled(Precompiled),
+    "Replace" => Replace(Replace),
+});
+
+/// A user-defined normalizer, which can be saved and loaded once registered in the
+/// [`REGISTRY`](struct.REGISTRY.html).
+pub type CustomNormalizer = Custom<dyn Normalizer + Send + Sync>;
+
+lazy_static! {
+    /// The user-defined normalizers, by the `type` they are serialized with.
+    pub static ref REGISTRY: Registry<dyn Normalizer + Send + Sync> =
+        Registry::new("normalizer", NormalizerWrapper::BUILTIN_TYPES);
 }
 
 impl Normalizer for NormalizerWrapper {
@@ -49,6 +76,7 @@ impl Normalizer for NormalizerWrapper {
             NormalizerWrapper::Nmt(lc) => lc.normalize(normalized),
             NormalizerWrapper::Precompiled(lc) => lc.normalize(normalized),
             NormalizerWrapper::Replace(lc) => lc.normalize(normalized),
+            NormalizerWrapper::Custom(custom) => custom.normalize(normalized),
         }
     }
 }
@@ -65,3 +93,4 @@ impl_enum_from!(Lowercase, NormalizerWrapper, Lowercase);
 impl_enum_from!(Nmt, NormalizerWrapper, Nmt);
 impl_enum_from!(Precompiled, NormalizerWrapper, Precompiled);
 impl_enum_from!(Replace, NormalizerWrapper, Replace);
+impl_enum_from!(CustomNormalizer, NormalizerWrapper, Custom);
@@ -1,4 +1,4 @@
-use crate::tokenizer::{NormalizedString, Normalizer, Result};
+use crate::tokenizer::{Decoder, NormalizedString, Normalizer, Result};
 use onig::Regex;
 use serde::{Deserialize, Serialize};
 
@@ -40,6 +40,7 @@ impl std::convert::TryFrom<ReplaceDeserializer> for Replace {
 
 /// This normalizer will take a `pattern` (for now only a String)
 /// and replace every occurrence with `content`.
+/// As a `Decoder`, it does the same on each token.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", try_from = "ReplaceDeserializer")]
 pub struct Replace {
@@ -83,6 +84,15 @@ impl Normalizer for Replace {
     }
 }
 
+impl Decoder for Replace {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
+        Ok(tokens
+            .into_iter()
+            .map(|token| self.regex.replace_all(&token, self.content.as_str()))
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +122,15 @@ mod tests {
         assert_eq!(&n.get(), &normalized);
     }
 
+    #[test]
+    fn decode() {
+        let decoder = Replace::new("▁", " ").unwrap();
+        let res = decoder
+            .decode_chain(vec!["▁Hey".into(), "▁friend▁".into()])
+            .unwrap();
+        assert_eq!(res, vec![" Hey", " friend "]);
+    }
+
     #[test]
     fn serialization() {
         let replace = Replace::new("Hello", "Hey").unwrap();
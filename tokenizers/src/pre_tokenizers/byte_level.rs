@@ -119,8 +119,10 @@ impl PreTokenizer for ByteLevel {
 
 /// As a `Decoder`, `ByteLevel` is in charge of converting any byte-level characters to their
 /// unicode counterpart, before merging everything back into a single String.
+/// The bytes of a character can be spread over several tokens, so the result is always a
+/// single token.
 impl Decoder for ByteLevel {
-    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
         let toks = tokens
             .into_iter()
             .flat_map(|t| {
@@ -134,7 +136,7 @@ impl Decoder for ByteLevel {
                     .unwrap_or_else(|| t.as_bytes().to_vec())
             })
             .collect::<Vec<_>>();
-        Ok(String::from_utf8_lossy(&toks).into_owned())
+        Ok(vec![String::from_utf8_lossy(&toks).into_owned()])
     }
 }
 
@@ -168,6 +170,24 @@ impl PostProcessor for ByteLevel {
 
         PostProcessor::default_process(encoding, pair_encoding, add_special_tokens)
     }
+
+    fn process_sequences(
+        &self,
+        mut encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        if self.trim_offsets {
+            for encoding in encodings.iter_mut() {
+                process_offsets(encoding, self.add_prefix_space);
+                encoding
+                    .get_overflowing_mut()
+                    .iter_mut()
+                    .for_each(|encoding| process_offsets(encoding, self.add_prefix_space));
+            }
+        }
+
+        <dyn PostProcessor>::default_process_sequences(encodings, add_special_tokens)
+    }
 }
 
 pub fn process_offsets(encoding: &mut Encoding, add_prefix_space: bool) {
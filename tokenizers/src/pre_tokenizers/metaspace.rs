@@ -42,23 +42,28 @@ impl PreTokenizer for Metaspace {
 }
 
 impl Decoder for Metaspace {
-    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>> {
         Ok(tokens
             .iter()
-            .flat_map(|t| t.chars())
             .enumerate()
-            .filter_map(|(i, c)| {
-                if c == self.replacement {
-                    if i == 0 && self.add_prefix_space {
-                        None
-                    } else {
-                        Some(' ')
-                    }
-                } else {
-                    Some(c)
-                }
+            .map(|(i, token)| {
+                token
+                    .chars()
+                    .enumerate()
+                    .filter_map(|(j, c)| {
+                        if c == self.replacement {
+                            if i == 0 && j == 0 && self.add_prefix_space {
+                                None
+                            } else {
+                                Some(' ')
+                            }
+                        } else {
+                            Some(c)
+                        }
+                    })
+                    .collect::<String>()
             })
-            .collect::<String>())
+            .collect())
     }
 }
 
@@ -5,9 +5,11 @@ pub mod digits;
 pub mod metaspace;
 pub mod punctuation;
 pub mod sequence;
+pub mod split;
+pub mod unicode_scripts;
 pub mod whitespace;
 
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
 use crate::pre_tokenizers::bert::BertPreTokenizer;
 use crate::pre_tokenizers::byte_level::ByteLevel;
@@ -16,10 +18,13 @@ use crate::pre_tokenizers::digits::Digits;
 use crate::pre_tokenizers::metaspace::Metaspace;
 use crate::pre_tokenizers::punctuation::Punctuation;
 use crate::pre_tokenizers::sequence::Sequence;
+use crate::pre_tokenizers::split::Split;
+use crate::pre_tokenizers::unicode_scripts::UnicodeScripts;
 use crate::pre_tokenizers::whitespace::{Whitespace, WhitespaceSplit};
+use crate::utils::registry::{Custom, Registry};
 use crate::{PreTokenizedString, PreTokenizer};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Clone, Debug)]
 #[serde(untagged)]
 pub enum PreTokenizerWrapper {
     BertPreTokenizer(BertPreTokenizer),
@@ -28,9 +33,36 @@ pub enum PreTokenizerWrapper {
     Metaspace(Metaspace),
     Whitespace(Whitespace),
     Sequence(Sequence),
+    Split(Split),
     Punctuation(Punctuation),
     WhitespaceSplit(WhitespaceSplit),
     Digits(Digits),
+    UnicodeScripts(UnicodeScripts),
+    Custom(CustomPreTokenizer),
+}
+
+impl_serde_tagged_wrapper!(PreTokenizerWrapper, "pre-tokenizer", Custom(REGISTRY), {
+    "BertPreTokenizer" => BertPreTokenizer(BertPreTokenizer),
+    "ByteLevel" => ByteLevel(ByteLevel),
+    "CharDelimiterSplit" => Delimiter(CharDelimiterSplit),
+    "Metaspace" => Metaspace(Metaspace),
+    "Whitespace" => Whitespace(Whitespace),
+    "Sequence" => Sequence(Sequence),
+    "Split" => Split(Split),
+    "Punctuation" => Punctuation(Punctuation),
+    "WhitespaceSplit" => WhitespaceSplit(WhitespaceSplit),
+    "Digits" => Digits(Digits),
+    "UnicodeScripts" => UnicodeScripts(UnicodeScripts),
+});
+
+/// A user-defined pre-tokenizer, which can be saved and loaded once registered in the
+/// [`REGISTRY`](struct.REGISTRY.html).
+pub type CustomPreTokenizer = Custom<dyn PreTokenizer + Send + Sync>;
+
+lazy_static! {
+    /// The user-defined pre-tokenizers, by the `type` they are serialized with.
+    pub static ref REGISTRY: Registry<dyn PreTokenizer + Send + Sync> =
+        Registry::new("pre-tokenizer", PreTokenizerWrapper::BUILTIN_TYPES);
 }
 
 impl PreTokenizer for PreTokenizerWrapper {
@@ -43,8 +75,11 @@ impl PreTokenizer for PreTokenizerWrapper {
             PreTokenizerWrapper::Whitespace(wspt) => wspt.pre_tokenize(normalized),
             PreTokenizerWrapper::Punctuation(tok) => tok.pre_tokenize(normalized),
             PreTokenizerWrapper::Sequence(tok) => tok.pre_tokenize(normalized),
+            PreTokenizerWrapper::Split(tok) => tok.pre_tokenize(normalized),
             PreTokenizerWrapper::WhitespaceSplit(wspt) => wspt.pre_tokenize(normalized),
             PreTokenizerWrapper::Digits(wspt) => wspt.pre_tokenize(normalized),
+            PreTokenizerWrapper::UnicodeScripts(us) => us.pre_tokenize(normalized),
+            PreTokenizerWrapper::Custom(custom) => custom.pre_tokenize(normalized),
         }
     }
 }
@@ -55,6 +90,9 @@ impl_enum_from!(CharDelimiterSplit, PreTokenizerWrapper, Delimiter);
 impl_enum_from!(Whitespace, PreTokenizerWrapper, Whitespace);
 impl_enum_from!(Punctuation, PreTokenizerWrapper, Punctuation);
 impl_enum_from!(Sequence, PreTokenizerWrapper, Sequence);
+impl_enum_from!(Split, PreTokenizerWrapper, Split);
 impl_enum_from!(Metaspace, PreTokenizerWrapper, Metaspace);
 impl_enum_from!(WhitespaceSplit, PreTokenizerWrapper, WhitespaceSplit);
 impl_enum_from!(Digits, PreTokenizerWrapper, Digits);
+impl_enum_from!(UnicodeScripts, PreTokenizerWrapper, UnicodeScripts);
+impl_enum_from!(CustomPreTokenizer, PreTokenizerWrapper, Custom);
@@ -0,0 +1,5 @@
+mod pre_tokenizer;
+mod scripts;
+
+pub use pre_tokenizer::UnicodeScripts;
+pub use scripts::{get_script, Script};
@@ -1,38 +1,74 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
 use crate::pre_tokenizers::unicode_scripts::scripts::{get_script, Script};
 use crate::tokenizer::{normalizer::Range, PreTokenizedString, PreTokenizer, Result};
 
-#[derive(Clone, Debug)]
-pub struct UnicodeScripts;
-impl_serde_unit_struct!(UnicodeScriptsVisitor, UnicodeScripts);
+/// Splits on the boundaries between Unicode scripts.
+///
+/// The default mappings are taken from the script rules of SentencePiece, but this doesn't
+/// reproduce the pieces of `spm_train`, which depend on its other options too (digits,
+/// whitespaces, maximum length, ...).
+///
+/// Some scripts can be merged into another one, and some chars can be given a specific script.
+/// Chars with the `Any` script never start a new split, and are added to the current one.
+/// By default, Hiragana and Katakana are merged with Han, the prolonged sound mark `ー` (U+30FC)
+/// is Han, and spaces are part of any script.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", default)]
+pub struct UnicodeScripts {
+    script_mappings: BTreeMap<Script, Script>,
+    char_mappings: BTreeMap<char, Script>,
+}
 
 impl UnicodeScripts {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(
+        script_mappings: BTreeMap<Script, Script>,
+        char_mappings: BTreeMap<char, Script>,
+    ) -> Self {
+        Self {
+            script_mappings,
+            char_mappings,
+        }
     }
-}
 
-impl Default for UnicodeScripts {
-    fn default() -> Self {
-        Self::new()
+    /// Replace the mappings between scripts
+    pub fn script_mappings(mut self, v: BTreeMap<Script, Script>) -> Self {
+        self.script_mappings = v;
+        self
+    }
+
+    /// Replace the scripts given to specific chars
+    pub fn char_mappings(mut self, v: BTreeMap<char, Script>) -> Self {
+        self.char_mappings = v;
+        self
     }
-}
 
-// This code exists in the Unigram default IsValidSentencePiece.
-// It could be integrated directly within `get_script` but I
-// think it's kind of tricky to see those modifications later
-// I am guessing release mode will optimize this away anyway.
-fn fixed_script(c: char) -> Script {
-    let raw_script = get_script(c);
-    if c as u32 == 0x30FC {
-        Script::Han
-    } else if c == ' ' {
-        Script::Any
-    } else {
-        match raw_script {
-            Script::Hiragana => Script::Han,
-            Script::Katakana => Script::Han,
-            script => script,
+    /// The script of the given char, once the mappings are applied
+    pub fn script(&self, c: char) -> Script {
+        if let Some(script) = self.char_mappings.get(&c) {
+            return *script;
         }
+        let script = get_script(c);
+        *self.script_mappings.get(&script).unwrap_or(&script)
+    }
+}
+
+impl Default for UnicodeScripts {
+    fn default() -> Self {
+        // The script rules of SentencePiece `IsValidSentencePiece`
+        Self::new(
+            vec![
+                (Script::Hiragana, Script::Han),
+                (Script::Katakana, Script::Han),
+            ]
+            .into_iter()
+            .collect(),
+            vec![('\u{30FC}', Script::Han), (' ', Script::Any)]
+                .into_iter()
+                .collect(),
+        )
     }
 }
 
@@ -41,27 +77,25 @@ impl PreTokenizer for UnicodeScripts {
         pretokenized.split(|_, normalized| {
             let mut last_script = None;
             let mut offset = 0;
-            let mut ranges: Vec<_> = normalized
-                .get()
-                .chars()
-                .filter_map(|c| {
-                    let script = Some(fixed_script(c));
-                    let result = if script != Some(Script::Any)
-                        && last_script != Some(Script::Any)
-                        && last_script != script
-                    {
-                        Some(offset)
-                    } else {
-                        None
-                    };
-                    offset += c.len_utf8();
-                    if script != Some(Script::Any) {
-                        last_script = script;
-                    }
-
-                    result
-                })
-                .collect();
+            // The chars with the `Any` script at the beginning are part of the first split
+            let mut ranges = vec![0];
+            ranges.extend(normalized.get().chars().filter_map(|c| {
+                let script = Some(self.script(c));
+                let result = if script != Some(Script::Any)
+                    && last_script.is_some()
+                    && last_script != script
+                {
+                    Some(offset)
+                } else {
+                    None
+                };
+                offset += c.len_utf8();
+                if script != Some(Script::Any) {
+                    last_script = script;
+                }
+
+                result
+            }));
             ranges.push(normalized.get().len());
             Ok(ranges
                 .windows(2)
@@ -78,7 +112,7 @@ impl PreTokenizer for UnicodeScripts {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::OffsetReferential;
+    use crate::{OffsetReferential, OffsetType};
 
     #[test]
     fn basic() {
@@ -87,7 +121,7 @@ mod tests {
         pretok.pre_tokenize(&mut pretokenized).unwrap();
         assert_eq!(
             pretokenized
-                .get_splits(OffsetReferential::Normalized)
+                .get_splits(OffsetReferential::Normalized, OffsetType::Byte)
                 .into_iter()
                 .map(|(s, o, _)| (s, o))
                 .collect::<Vec<_>>(),
@@ -95,7 +129,7 @@ mod tests {
         );
         assert_eq!(
             pretokenized
-                .get_splits(OffsetReferential::Original)
+                .get_splits(OffsetReferential::Original, OffsetType::Byte)
                 .into_iter()
                 .map(|(s, o, _)| (s, o))
                 .collect::<Vec<_>>(),
@@ -110,7 +144,7 @@ mod tests {
         pretok.pre_tokenize(&mut pretokenized).unwrap();
         assert_eq!(
             pretokenized
-                .get_splits(OffsetReferential::Normalized)
+                .get_splits(OffsetReferential::Normalized, OffsetType::Byte)
                 .into_iter()
                 .map(|(s, o, _)| (s, o))
                 .collect::<Vec<_>>(),
@@ -118,7 +152,7 @@ mod tests {
         );
         assert_eq!(
             pretokenized
-                .get_splits(OffsetReferential::Original)
+                .get_splits(OffsetReferential::Original, OffsetType::Byte)
                 .into_iter()
                 .map(|(s, o, _)| (s, o))
                 .collect::<Vec<_>>(),
@@ -128,17 +162,83 @@ mod tests {
 
     #[test]
     fn test_unicode_script() {
-        assert_eq!(Script::Han, fixed_script('京'));
-        assert_eq!(Script::Han, fixed_script('太'));
-        assert_eq!(Script::Han, fixed_script('い'));
-        assert_eq!(Script::Han, fixed_script('グ'));
-        assert_eq!(Script::Han, fixed_script('ー'));
-        assert_eq!(Script::Latin, fixed_script('a'));
-        assert_eq!(Script::Latin, fixed_script('A'));
-        assert_eq!(Script::Common, fixed_script('0'));
-        assert_eq!(Script::Common, fixed_script('$'));
-        assert_eq!(Script::Common, fixed_script('@'));
-        assert_eq!(Script::Common, fixed_script('-'));
-        assert_eq!(Script::Any, fixed_script(' '));
+        let pretok = UnicodeScripts::default();
+        assert_eq!(Script::Han, pretok.script('京'));
+        assert_eq!(Script::Han, pretok.script('太'));
+        assert_eq!(Script::Han, pretok.script('い'));
+        assert_eq!(Script::Han, pretok.script('グ'));
+        assert_eq!(Script::Han, pretok.script('ー'));
+        assert_eq!(Script::Latin, pretok.script('a'));
+        assert_eq!(Script::Latin, pretok.script('A'));
+        assert_eq!(Script::Common, pretok.script('0'));
+        assert_eq!(Script::Common, pretok.script('$'));
+        assert_eq!(Script::Common, pretok.script('@'));
+        assert_eq!(Script::Common, pretok.script('-'));
+        assert_eq!(Script::Any, pretok.script(' '));
+    }
+
+    fn splits(pretok: &UnicodeScripts, s: &str) -> Vec<String> {
+        let mut pretokenized = PreTokenizedString::from(s);
+        pretok.pre_tokenize(&mut pretokenized).unwrap();
+        pretokenized
+            .get_splits(OffsetReferential::Original, OffsetType::Byte)
+            .into_iter()
+            .map(|(s, _, _)| s.to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn default_mappings() {
+        // Hiragana, Katakana and `ー` are merged with Han, spaces stick to their neighbours, and
+        // the other common chars (punctuation, digits) are a script on their own
+        let pretok = UnicodeScripts::default();
+        assert_eq!(
+            splits(&pretok, "東京タワーはTokyo Towerです"),
+            vec!["東京タワーは", "Tokyo Tower", "です"]
+        );
+        assert_eq!(
+            splits(&pretok, "ヴァイオリン、violin"),
+            vec!["ヴァイオリン", "、", "violin"]
+        );
+        assert_eq!(splits(&pretok, "Hello世界!"), vec!["Hello", "世界", "!"]);
+        assert_eq!(splits(&pretok, " Hello 世界"), vec![" Hello ", "世界"]);
+        assert_eq!(splits(&pretok, "3.14π"), vec!["3.14", "π"]);
+        assert_eq!(
+            splits(&pretok, "Привет, world"),
+            vec!["Привет", ", ", "world"]
+        );
+    }
+
+    #[test]
+    fn custom_mappings() {
+        let pretok = UnicodeScripts::new(BTreeMap::new(), BTreeMap::new());
+        assert_eq!(splits(&pretok, "どこで生れ"), vec!["どこで", "生", "れ"]);
+        assert_eq!(splits(&pretok, "Apples are"), vec!["Apples", " ", "are"]);
+
+        let pretok = UnicodeScripts::new(
+            vec![(Script::Common, Script::Any)].into_iter().collect(),
+            vec![('ー', Script::Katakana)].into_iter().collect(),
+        );
+        assert_eq!(splits(&pretok, "3 apples"), vec!["3 apples"]);
+        assert_eq!(splits(&pretok, "タワーは"), vec!["タワー", "は"]);
+    }
+
+    #[test]
+    fn serialization() {
+        let pretok = UnicodeScripts::default();
+        let serialized = serde_json::to_string(&pretok).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"type":"UnicodeScripts","script_mappings":{"Hiragana":"Han","Katakana":"Han"},"char_mappings":{" ":"Any","ー":"Han"}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<UnicodeScripts>(&serialized).unwrap(),
+            pretok
+        );
+        // The missing mappings use the default ones
+        assert_eq!(
+            serde_json::from_str::<UnicodeScripts>(r#"{"type":"UnicodeScripts"}"#).unwrap(),
+            pretok
+        );
     }
 }
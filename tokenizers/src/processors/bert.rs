@@ -72,7 +72,7 @@ impl PostProcessor for BertProcessing {
                         [&[1u32], &vec![0; encoding.get_ids().len()][..], &[1]].concat();
                     let attention_mask = vec![1; ids.len()];
 
-                    Encoding::new(
+                    let mut new_encoding = Encoding::new(
                         ids,
                         type_ids,
                         tokens,
@@ -81,10 +81,13 @@ impl PostProcessor for BertProcessing {
                         special_tokens,
                         attention_mask,
                         vec![],
-                    )
+                    );
+                    new_encoding.set_sequence_range(0, 1..1 + encoding.len());
+                    new_encoding
                 })
                 .collect(),
         );
+        new_encoding.set_sequence_range(0, 1..1 + encoding.len());
 
         if let Some(mut encoding) = pair_encoding {
             let pair_ids = [&encoding.get_ids()[..], &[self.sep.1]].concat();
@@ -96,7 +99,7 @@ impl PostProcessor for BertProcessing {
                 [&vec![0u32; encoding.get_type_ids().len()][..], &[1]].concat();
             let pair_attention_mask = vec![1; pair_ids.len()];
 
-            let new_pair_encoding = Encoding::new(
+            let mut new_pair_encoding = Encoding::new(
                 pair_ids,
                 pair_type_ids,
                 pair_tokens,
@@ -118,7 +121,7 @@ impl PostProcessor for BertProcessing {
                             [&vec![0u32; encoding.get_type_ids().len()][..], &[1]].concat();
                         let pair_attention_mask = vec![1; pair_ids.len()];
 
-                        Encoding::new(
+                        let mut new_encoding = Encoding::new(
                             pair_ids,
                             pair_type_ids,
                             pair_tokens,
@@ -127,10 +130,13 @@ impl PostProcessor for BertProcessing {
                             pair_special_tokens,
                             pair_attention_mask,
                             vec![],
-                        )
+                        );
+                        new_encoding.set_sequence_range(1, 0..encoding.len());
+                        new_encoding
                     })
                     .collect(),
             );
+            new_pair_encoding.set_sequence_range(1, 0..encoding.len());
 
             new_encoding.merge_with(new_pair_encoding, false);
         }
@@ -5,21 +5,40 @@ pub mod template;
 // Re-export these as processors
 pub use super::pre_tokenizers::byte_level;
 
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 
 use crate::pre_tokenizers::byte_level::ByteLevel;
 use crate::processors::bert::BertProcessing;
 use crate::processors::roberta::RobertaProcessing;
 use crate::processors::template::TemplateProcessing;
+use crate::utils::registry::{Custom, Registry};
 use crate::{Encoding, PostProcessor, Result};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum PostProcessorWrapper {
     Bert(BertProcessing),
     ByteLevel(ByteLevel),
     Roberta(RobertaProcessing),
     Template(TemplateProcessing),
+    Custom(CustomPostProcessor),
+}
+
+impl_serde_tagged_wrapper!(PostProcessorWrapper, "post-processor", Custom(REGISTRY), {
+    "BertProcessing" => Bert(BertProcessing),
+    "ByteLevel" => ByteLevel(ByteLevel),
+    "RobertaProcessing" => Roberta(RobertaProcessing),
+    "TemplateProcessing" => Template(TemplateProcessing),
+});
+
+/// A user-defined post-processor, which can be saved and loaded once registered in the
+/// [`REGISTRY`](struct.REGISTRY.html).
+pub type CustomPostProcessor = Custom<dyn PostProcessor + Send + Sync>;
+
+lazy_static! {
+    /// The user-defined post-processors, by the `type` they are serialized with.
+    pub static ref REGISTRY: Registry<dyn PostProcessor + Send + Sync> =
+        Registry::new("post-processor", PostProcessorWrapper::BUILTIN_TYPES);
 }
 
 impl PostProcessor for PostProcessorWrapper {
@@ -29,6 +48,21 @@ impl PostProcessor for PostProcessorWrapper {
             PostProcessorWrapper::ByteLevel(bl) => bl.added_tokens(is_pair),
             PostProcessorWrapper::Roberta(roberta) => roberta.added_tokens(is_pair),
             PostProcessorWrapper::Template(template) => template.added_tokens(is_pair),
+            PostProcessorWrapper::Custom(custom) => custom.added_tokens(is_pair),
+        }
+    }
+
+    fn added_tokens_for_sequences(&self, n_sequences: usize) -> usize {
+        match self {
+            PostProcessorWrapper::Bert(bert) => bert.added_tokens_for_sequences(n_sequences),
+            PostProcessorWrapper::ByteLevel(bl) => bl.added_tokens_for_sequences(n_sequences),
+            PostProcessorWrapper::Roberta(roberta) => {
+                roberta.added_tokens_for_sequences(n_sequences)
+            }
+            PostProcessorWrapper::Template(template) => {
+                template.added_tokens_for_sequences(n_sequences)
+            }
+            PostProcessorWrapper::Custom(custom) => custom.added_tokens_for_sequences(n_sequences),
         }
     }
 
@@ -51,6 +85,33 @@ impl PostProcessor for PostProcessorWrapper {
             PostProcessorWrapper::Template(template) => {
                 template.process(encoding, pair_encoding, add_special_tokens)
             }
+            PostProcessorWrapper::Custom(custom) => {
+                custom.process(encoding, pair_encoding, add_special_tokens)
+            }
+        }
+    }
+
+    fn process_sequences(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        match self {
+            PostProcessorWrapper::Bert(bert) => {
+                bert.process_sequences(encodings, add_special_tokens)
+            }
+            PostProcessorWrapper::ByteLevel(bl) => {
+                bl.process_sequences(encodings, add_special_tokens)
+            }
+            PostProcessorWrapper::Roberta(roberta) => {
+                roberta.process_sequences(encodings, add_special_tokens)
+            }
+            PostProcessorWrapper::Template(template) => {
+                template.process_sequences(encodings, add_special_tokens)
+            }
+            PostProcessorWrapper::Custom(custom) => {
+                custom.process_sequences(encodings, add_special_tokens)
+            }
         }
     }
 }
@@ -59,3 +120,4 @@ impl_enum_from!(BertProcessing, PostProcessorWrapper, Bert);
 impl_enum_from!(ByteLevel, PostProcessorWrapper, ByteLevel);
 impl_enum_from!(RobertaProcessing, PostProcessorWrapper, Roberta);
 impl_enum_from!(TemplateProcessing, PostProcessorWrapper, Template);
+impl_enum_from!(CustomPostProcessor, PostProcessorWrapper, Custom);
@@ -57,18 +57,73 @@
 //!
 use crate::{Encoding, PostProcessor, Result};
 use itertools::Itertools;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::result::Result as StdResult;
 
-/// Represents both sequences received as input of the PostProcessor
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum Sequence {
+/// Represents one of the sequences received as input of the PostProcessor, identified by its
+/// index. In a template, each sequence is identified by a letter: `$A` for the first one, `$B`
+/// for the second one, `$C` for the third one, and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sequence(usize);
+
+impl Sequence {
     /// This is the first sequence, the one that is always specified
-    A,
+    pub const A: Sequence = Sequence(0);
     /// This is the pair sequence, that is optional
-    B,
+    pub const B: Sequence = Sequence(1);
+    /// The maximum number of sequences that can be identified in a template
+    pub const MAX: usize = 26;
+
+    /// Build the `Sequence` with the given index, if it can be identified in a template
+    pub fn new(index: usize) -> Option<Self> {
+        if index < Self::MAX {
+            Some(Self(index))
+        } else {
+            None
+        }
+    }
+
+    /// The index of this sequence in the input of the PostProcessor
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    fn from_letter(letter: &str) -> Option<Self> {
+        let mut chars = letter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphabetic() => {
+                Self::new((c.to_ascii_uppercase() as u8 - b'A') as usize)
+            }
+            _ => None,
+        }
+    }
+
+    fn letter(&self) -> char {
+        (b'A' + self.0 as u8) as char
+    }
+}
+
+impl Serialize for Sequence {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_char(self.letter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Sequence {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let letter = String::deserialize(deserializer)?;
+        Sequence::from_letter(&letter)
+            .filter(|_| letter.chars().all(|c| c.is_ascii_uppercase()))
+            .ok_or_else(|| de::Error::custom(format!("Invalid sequence identifier {}", letter)))
+    }
 }
 
 /// Represents the different kind of pieces that constitute a template.
@@ -108,14 +163,6 @@ impl Piece {
                     id: Sequence::A,
                     type_id: 0,
                 }),
-                "A" | "a" => Some(Self::Sequence {
-                    id: Sequence::A,
-                    type_id: 0,
-                }),
-                "B" | "b" => Some(Self::Sequence {
-                    id: Sequence::B,
-                    type_id: 0,
-                }),
                 n => {
                     if let Ok(type_id) = n.parse::<u32>() {
                         Some(Self::Sequence {
@@ -123,7 +170,7 @@ impl Piece {
                             type_id,
                         })
                     } else {
-                        None
+                        Sequence::from_letter(n).map(|id| Self::Sequence { id, type_id: 0 })
                     }
                 }
             }
@@ -338,35 +385,49 @@ pub struct TemplateProcessing {
     single: Template,
     #[builder(try_setter, default = "\"$A:0 $B:1\".try_into().unwrap()")]
     pair: Template,
+    #[builder(setter(custom), default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    multiple: Option<Template>,
     #[builder(setter(skip), default = "self.default_added(true)")]
     #[serde(skip)]
     added_single: usize,
     #[builder(setter(skip), default = "self.default_added(false)")]
     #[serde(skip)]
     added_pair: usize,
+    #[builder(setter(skip), default = "self.default_added_multiple()")]
+    #[serde(skip)]
+    added_multiple: usize,
     #[builder(setter(into), default)]
     special_tokens: Tokens,
 }
 
-/// We use this custom deserializer to provided the values for `added_single`
-/// and `added_pair` during deserialization, while not having to serialize them
+/// We use this custom deserializer to provided the values for `added_single`,
+/// `added_pair` and `added_multiple` during deserialization, while not having to serialize them
 #[doc(hidden)]
 #[derive(Deserialize)]
 #[serde(tag = "type")]
 struct TemplateProcessingDeserializer {
     single: Template,
     pair: Template,
+    #[serde(default)]
+    multiple: Option<Template>,
     special_tokens: Tokens,
 }
 impl From<TemplateProcessingDeserializer> for TemplateProcessing {
     fn from(t: TemplateProcessingDeserializer) -> Self {
         let added_single = count_added(&t.single, Some(&t.special_tokens));
         let added_pair = count_added(&t.pair, Some(&t.special_tokens));
+        let added_multiple = t
+            .multiple
+            .as_ref()
+            .map_or(0, |multiple| count_added(multiple, Some(&t.special_tokens)));
         Self {
             single: t.single,
             pair: t.pair,
+            multiple: t.multiple,
             added_single,
             added_pair,
+            added_multiple,
             special_tokens: t.special_tokens,
         }
     }
@@ -386,7 +447,43 @@ fn count_added(container: &Template, special_tokens: Option<&Tokens>) -> usize {
         .sum()
 }
 
+/// Count the number of sequences used in the given template
+fn count_sequences(container: &Template) -> usize {
+    container
+        .0
+        .iter()
+        .filter_map(|p| match p {
+            Piece::Sequence { id, .. } => Some(id.index() + 1),
+            Piece::SpecialToken { .. } => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
 impl TemplateProcessingBuilder {
+    /// Set the template used when processing more than two sequences
+    pub fn multiple(&mut self, multiple: Template) -> &mut Self {
+        self.multiple = Some(Some(multiple));
+        self
+    }
+
+    /// Try to set the template used when processing more than two sequences
+    pub fn try_multiple<T>(&mut self, multiple: T) -> StdResult<&mut Self, String>
+    where
+        T: TryInto<Template, Error = String>,
+    {
+        Ok(self.multiple(multiple.try_into()?))
+    }
+
+    fn default_added_multiple(&self) -> usize {
+        self.multiple
+            .as_ref()
+            .and_then(|m| m.as_ref())
+            .map_or(0, |pieces| {
+                count_added(pieces, self.special_tokens.as_ref())
+            })
+    }
+
     fn default_added(&self, is_single: bool) -> usize {
         let container = if is_single {
             self.single.as_ref()
@@ -422,6 +519,25 @@ impl TemplateProcessingBuilder {
             return Err("Template for `pair` must use both sequences".into());
         }
 
+        let multiple = self.multiple.as_ref().and_then(|m| m.as_ref());
+        if let Some(multiple) = multiple {
+            let used = multiple
+                .0
+                .iter()
+                .filter_map(|piece| match piece {
+                    Piece::Sequence { id, .. } => Some(id.index()),
+                    Piece::SpecialToken { .. } => None,
+                })
+                .collect::<HashSet<_>>();
+            let n_sequences = count_sequences(multiple);
+            if n_sequences < 3 || used.len() != n_sequences {
+                return Err(
+                    "Template for `multiple` must use at least three sequences, without gaps"
+                        .into(),
+                );
+            }
+        }
+
         let check = |sp| {
             let exist = self
                 .special_tokens
@@ -440,6 +556,7 @@ impl TemplateProcessingBuilder {
             .as_ref()
             .map_or(empty.iter(), |s| s.0.iter())
             .chain(self.pair.as_ref().map_or(empty.iter(), |s| s.0.iter()))
+            .chain(multiple.map_or(empty.iter(), |s| s.0.iter()))
             .filter_map(|piece| match piece {
                 Piece::Sequence { .. } => None,
                 Piece::SpecialToken { id, .. } => check(id.as_ref()),
@@ -462,8 +579,10 @@ impl Default for TemplateProcessing {
         Self {
             single: "$0".try_into().unwrap(),
             pair: "$1".try_into().unwrap(),
+            multiple: None,
             added_single: 0,
             added_pair: 0,
+            added_multiple: 0,
             special_tokens: Tokens::default(),
         }
     }
@@ -477,22 +596,25 @@ impl TemplateProcessing {
     fn apply_template(
         &self,
         template: &[Piece],
-        mut encoding: Encoding,
-        mut pair: Option<Encoding>,
+        mut encodings: Vec<Encoding>,
         add_special_tokens: bool,
     ) -> Result<Encoding> {
         // Compute the new size
         let mut new_len = 0;
         for piece in template {
             new_len += match piece {
-                Piece::Sequence {
-                    id: Sequence::A, ..
-                } => encoding.len(),
-                Piece::Sequence {
-                    id: Sequence::B, ..
-                } => pair
-                    .as_ref()
-                    .ok_or("Template expected a pair sequence, but none provided")?
+                Piece::Sequence { id, .. } => encodings
+                    .get(id.index())
+                    .ok_or_else(|| {
+                        if *id == Sequence::B {
+                            "Template expected a pair sequence, but none provided".into()
+                        } else {
+                            format!(
+                                "Template expected a sequence ${}, but none provided",
+                                id.letter()
+                            )
+                        }
+                    })?
                     .len(),
                 Piece::SpecialToken { id, .. } => {
                     if add_special_tokens {
@@ -517,51 +639,44 @@ impl TemplateProcessing {
         let mut offsets = Vec::with_capacity(new_len);
         let mut special_tokens_mask = Vec::with_capacity(new_len);
         let mut attention_mask = Vec::with_capacity(new_len);
+        let mut sequence_ranges = vec![];
 
-        let pair_overflowing = pair.as_mut().map_or(vec![], |e| e.take_overflowing());
-        let mut overflowing = encoding
-            .take_overflowing()
-            .into_iter()
-            .flat_map(|encoding| {
-                let mut overflowings = vec![];
-
-                // 1. The pair itself
-                overflowings.push(self.apply_template(
-                    template,
-                    encoding.clone(),
-                    pair.clone(),
-                    add_special_tokens,
-                ));
-
-                // 2. Its overflowings
-                for other_o in &pair_overflowing {
-                    overflowings.push(self.apply_template(
-                        template,
-                        encoding.clone(),
-                        Some(other_o.clone()),
-                        add_special_tokens,
-                    ));
+        // Each overflowing part is combined with all the other sequences and their own
+        // overflowing parts. The first sequence varies the slowest, starting with its
+        // overflowing parts, while the others start with their main part.
+        let overflowings = encodings
+            .iter_mut()
+            .map(|encoding| encoding.take_overflowing())
+            .collect::<Vec<_>>();
+        let overflowing = overflowings
+            .iter()
+            .enumerate()
+            .map(|(i, parts)| {
+                let mut choices = parts.iter().map(Some).collect::<Vec<_>>();
+                if i == 0 {
+                    choices.push(None);
+                } else {
+                    choices.insert(0, None);
                 }
-
-                overflowings
+                choices
+            })
+            .multi_cartesian_product()
+            .filter(|combination| combination.iter().any(Option::is_some))
+            .map(|combination| {
+                let sequences = combination
+                    .into_iter()
+                    .zip(&encodings)
+                    .map(|(part, encoding)| part.unwrap_or(encoding).clone())
+                    .collect();
+                self.apply_template(template, sequences, add_special_tokens)
             })
             .collect::<Result<Vec<_>>>()?;
-        // We also need to combine the first sequence with all other overflowings
-        overflowing.extend(
-            pair_overflowing
-                .into_iter()
-                .map(|pair| {
-                    self.apply_template(template, encoding.clone(), Some(pair), add_special_tokens)
-                })
-                .collect::<Result<Vec<_>>>()?,
-        );
 
         for piece in template {
             match piece {
-                Piece::Sequence {
-                    id: Sequence::A,
-                    type_id,
-                } => {
+                Piece::Sequence { id, type_id } => {
+                    let encoding = &encodings[id.index()]; // We already checked existance above
+                    sequence_ranges.push((id.index(), ids.len()..ids.len() + encoding.len()));
                     ids.extend(encoding.get_ids());
                     type_ids.extend(std::iter::repeat(type_id).take(encoding.len()));
                     tokens.extend(encoding.get_tokens().iter().map(|s| s.to_owned()));
@@ -570,19 +685,6 @@ impl TemplateProcessing {
                     special_tokens_mask.extend(encoding.get_special_tokens_mask());
                     attention_mask.extend(encoding.get_attention_mask());
                 }
-                Piece::Sequence {
-                    id: Sequence::B,
-                    type_id,
-                } => {
-                    let pair = pair.as_ref().expect("Missing pair sequence, checked above");
-                    ids.extend(pair.get_ids());
-                    type_ids.extend(std::iter::repeat(type_id).take(pair.len()));
-                    tokens.extend(pair.get_tokens().iter().map(|s| s.to_owned()));
-                    words.extend(pair.get_words());
-                    offsets.extend(pair.get_offsets());
-                    special_tokens_mask.extend(pair.get_special_tokens_mask());
-                    attention_mask.extend(pair.get_attention_mask());
-                }
                 Piece::SpecialToken { id, type_id } => {
                     if add_special_tokens {
                         let tok = &self.special_tokens.0[id]; // We already checked existance above
@@ -600,7 +702,7 @@ impl TemplateProcessing {
             }
         }
 
-        Ok(Encoding::new(
+        let mut encoding = Encoding::new(
             ids,
             type_ids,
             tokens,
@@ -609,7 +711,11 @@ impl TemplateProcessing {
             special_tokens_mask,
             attention_mask,
             overflowing,
-        ))
+        );
+        for (sequence_id, range) in sequence_ranges {
+            encoding.set_sequence_range(sequence_id, range);
+        }
+        Ok(encoding)
     }
 }
 
@@ -622,23 +728,52 @@ impl PostProcessor for TemplateProcessing {
         }
     }
 
+    fn added_tokens_for_sequences(&self, n_sequences: usize) -> usize {
+        match n_sequences {
+            0 | 1 => self.added_single,
+            2 => self.added_pair,
+            _ => self.added_multiple,
+        }
+    }
+
     fn process(
         &self,
         encoding: Encoding,
         pair: Option<Encoding>,
         add_special_tokens: bool,
     ) -> Result<Encoding> {
-        self.apply_template(
-            if pair.is_some() {
-                &self.pair.0
-            } else {
-                &self.single.0
-            },
-            encoding,
-            pair,
+        self.process_sequences(
+            std::iter::once(encoding).chain(pair).collect(),
             add_special_tokens,
         )
     }
+
+    fn process_sequences(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        let template = match encodings.len() {
+            1 => &self.single,
+            2 => &self.pair,
+            n => {
+                let multiple = self
+                    .multiple
+                    .as_ref()
+                    .ok_or_else(|| format!("No template provided to process {} sequences", n))?;
+                let expected = count_sequences(multiple);
+                if expected != n {
+                    return Err(format!(
+                        "Template for `multiple` expects {} sequences, but {} provided",
+                        expected, n
+                    )
+                    .into());
+                }
+                multiple
+            }
+        };
+        self.apply_template(&template.0, encodings, add_special_tokens)
+    }
 }
 
 #[cfg(test)]
@@ -711,7 +846,14 @@ mod tests {
             }),
             "$:1".try_into()
         );
-        assert!(Piece::try_from("$C:1").is_err());
+        assert_eq!(
+            Ok(Piece::Sequence {
+                id: Sequence::new(2).unwrap(),
+                type_id: 1
+            }),
+            "$C:1".try_into()
+        );
+        assert!(Piece::try_from("$AB").is_err());
         assert!(Piece::try_from("$A:").is_err());
     }
 
@@ -886,43 +1028,114 @@ mod tests {
             0,
         );
         let pair = Encoding::from_tokens(vec![Token::new(15, "pair".into(), (0, 4))], 0);
+        let mut single_encoding = Encoding::new(
+            vec![1, 12, 14, 0],
+            vec![0, 0, 0, 0],
+            vec![
+                "[CLS]".into(),
+                "Hello".into(),
+                "there".into(),
+                "[SEP]".into(),
+            ],
+            vec![None, None, None, None],
+            vec![(0, 0), (0, 5), (6, 11), (0, 0)],
+            vec![1, 0, 0, 1],
+            vec![1, 1, 1, 1],
+            vec![],
+        );
+        single_encoding.set_sequence_range(0, 1..3);
         assert_eq!(
             processor.process(encoding.clone(), None, true).unwrap(),
-            Encoding::new(
-                vec![1, 12, 14, 0],
-                vec![0, 0, 0, 0],
-                vec![
-                    "[CLS]".into(),
-                    "Hello".into(),
-                    "there".into(),
-                    "[SEP]".into()
-                ],
-                vec![None, None, None, None],
-                vec![(0, 0), (0, 5), (6, 11), (0, 0)],
-                vec![1, 0, 0, 1],
-                vec![1, 1, 1, 1],
-                vec![]
-            )
+            single_encoding
+        );
+        let mut pair_encoding = Encoding::new(
+            vec![1, 12, 14, 0, 15, 0],
+            vec![0, 0, 0, 0, 1, 1],
+            vec![
+                "[CLS]".into(),
+                "Hello".into(),
+                "there".into(),
+                "[SEP]".into(),
+                "pair".into(),
+                "[SEP]".into(),
+            ],
+            vec![None, None, None, None, None, None],
+            vec![(0, 0), (0, 5), (6, 11), (0, 0), (0, 4), (0, 0)],
+            vec![1, 0, 0, 1, 0, 1],
+            vec![1, 1, 1, 1, 1, 1],
+            vec![],
         );
+        pair_encoding.set_sequence_range(0, 1..3);
+        pair_encoding.set_sequence_range(1, 4..5);
         assert_eq!(
             processor.process(encoding, Some(pair), true).unwrap(),
-            Encoding::new(
-                vec![1, 12, 14, 0, 15, 0],
-                vec![0, 0, 0, 0, 1, 1],
-                vec![
-                    "[CLS]".into(),
-                    "Hello".into(),
-                    "there".into(),
-                    "[SEP]".into(),
-                    "pair".into(),
-                    "[SEP]".into()
-                ],
-                vec![None, None, None, None, None, None],
-                vec![(0, 0), (0, 5), (6, 11), (0, 0), (0, 4), (0, 0)],
-                vec![1, 0, 0, 1, 0, 1],
-                vec![1, 1, 1, 1, 1, 1],
-                vec![]
-            )
+            pair_encoding
+        );
+    }
+
+    #[test]
+    fn template_processing_multiple() {
+        let processor = TemplateProcessing::builder()
+            .try_single("[CLS] $A [SEP]")
+            .unwrap()
+            .try_pair("[CLS] $A [SEP] $B:1 [SEP]:1")
+            .unwrap()
+            .try_multiple("[CLS] $A [SEP] $B:1 [SEP]:1 $C:2 [SEP]:2")
+            .unwrap()
+            .special_tokens(vec![("[CLS]", 1), ("[SEP]", 0)])
+            .build()
+            .unwrap();
+        assert_eq!(processor.added_tokens_for_sequences(3), 4);
+
+        use crate::Token;
+        let query = Encoding::from_tokens(vec![Token::new(12, "query".into(), (0, 5))], 0);
+        let mut passage = Encoding::from_tokens(
+            vec![
+                Token::new(13, "long".into(), (0, 4)),
+                Token::new(14, "passage".into(), (5, 12)),
+            ],
+            0,
+        );
+        let title = Encoding::from_tokens(vec![Token::new(15, "title".into(), (0, 5))], 0);
+        passage.truncate(1, 0, crate::TruncationDirection::Right);
+
+        let encoding = processor
+            .process_sequences(vec![query.clone(), passage, title.clone()], true)
+            .unwrap();
+        assert_eq!(encoding.get_ids(), &[1, 12, 0, 13, 0, 15, 0]);
+        assert_eq!(encoding.get_type_ids(), &[0, 0, 0, 1, 1, 2, 2]);
+        assert_eq!(encoding.n_sequences(), 3);
+        assert_eq!(
+            (0..encoding.len())
+                .map(|i| encoding.token_to_sequence(i))
+                .collect::<Vec<_>>(),
+            vec![None, Some(0), None, Some(1), None, Some(2), None]
+        );
+        assert_eq!(encoding.get_overflowing().len(), 1);
+        assert_eq!(
+            encoding.get_overflowing()[0].get_ids(),
+            &[1, 12, 0, 14, 0, 15, 0]
+        );
+
+        // The number of sequences must match the template
+        assert!(processor
+            .process_sequences(vec![query.clone(), title.clone(), query, title], true)
+            .is_err());
+        let processor = tests::get_bert_template();
+        assert!(processor
+            .process_sequences(vec![Encoding::default(); 3], true)
+            .is_err());
+    }
+
+    #[test]
+    fn multiple_must_use_all_sequences() {
+        let processor = TemplateProcessing::builder()
+            .try_multiple("$A $B:1 $D:2")
+            .unwrap()
+            .build();
+        assert_eq!(
+            processor,
+            Err("Template for `multiple` must use at least three sequences, without gaps".into())
         );
     }
 
@@ -1,7 +1,9 @@
 use super::{
     normalizer::Range, Model, NormalizedString, Normalizer, Offsets, PreTokenizedString, Token,
 };
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
 use serde::{ser::SerializeSeq, Deserialize, Serialize, Serializer};
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 
 /// Represent a token added by the user on top of the existing Model vocabulary.
@@ -57,52 +59,6 @@ impl AddedToken {
         self.normalized = normalized;
         self
     }
-    /// Retrive the pattern built for this token, according to all the specified parameters.
-    pub fn get_pattern<N: Normalizer>(&self, normalizer: Option<&N>) -> String {
-        let mut r = if self.single_word {
-            let first_b = self
-                .content
-                .chars()
-                .next()
-                .map(|c| {
-                    if regex_syntax::is_word_character(c) {
-                        r"\b"
-                    } else {
-                        ""
-                    }
-                })
-                .unwrap();
-            let last_b = self
-                .content
-                .chars()
-                .last()
-                .map(|c| {
-                    if regex_syntax::is_word_character(c) {
-                        r"\b"
-                    } else {
-                        ""
-                    }
-                })
-                .unwrap();
-
-            // Normalize the content
-            let mut content = NormalizedString::from(self.content.as_ref());
-            normalizer.map(|n| n.normalize(&mut content));
-            format!(r"{}{}{}", first_b, regex::escape(content.get()), last_b)
-        } else {
-            regex::escape(&self.content)
-        };
-
-        if self.lstrip && self.rstrip {
-            r = format!(r"(\s)?{}(\s)?", r);
-        } else if self.lstrip {
-            r = format!(r"(\s)?{}", r);
-        } else if self.rstrip {
-            r = format!(r"{}(\s)?", r);
-        }
-
-        r
-    }
 }
 impl Default for AddedToken {
     fn default() -> Self {
@@ -129,7 +85,40 @@ impl std::cmp::PartialEq for AddedToken {
 }
 impl std::cmp::Eq for AddedToken {}
 
-type MatchingSet = (regex::RegexSet, Vec<u32>);
+type MatchingSet = (AhoCorasick, Vec<u32>);
+
+/// Build a `MatchingSet` from the given patterns and their associated ids. We use the
+/// standard semantics to be able to find all the overlapping matches, since a `single_word`
+/// token that is not on word boundaries must not hide the other matches, and then keep the
+/// leftmost-longest ones in `find_matches`.
+fn build_matching_set<I, P>(patterns: I, ids: Vec<u32>) -> MatchingSet
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<[u8]>,
+{
+    let trie = AhoCorasickBuilder::new()
+        .match_kind(MatchKind::Standard)
+        .build(patterns);
+    (trie, ids)
+}
+
+/// The pattern matched for the given token. Only the content of the `single_word` tokens is
+/// normalized, the others are matched as they are.
+fn pattern<N: Normalizer>(token: &AddedToken, normalizer: Option<&N>) -> String {
+    if token.single_word {
+        let mut content = NormalizedString::from(token.content.as_ref());
+        normalizer.map(|n| n.normalize(&mut content));
+        content.get().to_owned()
+    } else {
+        token.content.clone()
+    }
+}
+
+/// Whether the given char is considered to be part of a word, in which case it cannot be
+/// adjacent to a `single_word` token.
+fn is_word_char(c: Option<char>) -> bool {
+    matches!(c, Some(c) if regex_syntax::is_word_character(c))
+}
 
 ///
 /// A vocabulary built on top of the Model
@@ -164,10 +153,10 @@ pub(super) struct AddedVocabulary {
     /// us remove them easily with an O(1) complexity.
     special_tokens_set: HashSet<String>,
 
-    /// A RegexSet containing all the non-normalized patterns used to split on AddedTokens
-    split_re: MatchingSet,
-    /// A RegexSet containing all the normalized patterns used to split on AddedTokens
-    split_normalized_re: MatchingSet,
+    /// A trie containing all the non-normalized patterns used to split on AddedTokens
+    split_trie: MatchingSet,
+    /// A trie containing all the normalized patterns used to split on AddedTokens
+    split_normalized_trie: MatchingSet,
 }
 
 impl AddedVocabulary {
@@ -178,8 +167,8 @@ impl AddedVocabulary {
             added_tokens: vec![],
             special_tokens: vec![],
             special_tokens_set: HashSet::new(),
-            split_re: (regex::RegexSet::new::<_, &&str>(&[]).unwrap(), vec![]),
-            split_normalized_re: (regex::RegexSet::new::<_, &&str>(&[]).unwrap(), vec![]),
+            split_trie: build_matching_set::<_, &str>(vec![], vec![]),
+            split_normalized_trie: build_matching_set::<_, &str>(vec![], vec![]),
         }
     }
 
@@ -209,6 +198,11 @@ impl AddedVocabulary {
             .or_else(|| model.id_to_token(id))
     }
 
+    /// The ids of all the added tokens, including those that are part of the model
+    pub(crate) fn ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.added_tokens_map_r.keys().copied()
+    }
+
     /// Check if a token is a special token
     pub fn is_special_token(&self, token: &str) -> bool {
         self.special_tokens_set.contains(token)
@@ -272,9 +266,43 @@ impl AddedVocabulary {
         tokens.len() - ignored
     }
 
-    /// Reconstruct our internal RegexSet when new tokens are added to the vocabulary.
+    /// Update the ids of the added tokens once the vocabulary of the model has been pruned,
+    /// using the mapping from its old ids to the new ones. The added tokens that are not part of
+    /// the model get the ids that directly follow the new vocabulary, in the same order.
+    /// Returns the mapping from their old ids to the new ones.
+    pub(crate) fn remap_ids<N: Normalizer>(
+        &mut self,
+        model_mapping: &HashMap<u32, u32>,
+        model: &impl Model,
+        normalizer: Option<&N>,
+    ) -> HashMap<u32, u32> {
+        let mut ids = self.added_tokens_map.values().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        let mapping = ids
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| (id, (model.get_vocab_size() + i) as u32))
+            .collect::<HashMap<_, _>>();
+
+        for id in self.added_tokens_map.values_mut() {
+            *id = mapping[id];
+        }
+        self.added_tokens_map_r = self
+            .added_tokens_map_r
+            .drain()
+            .filter_map(|(id, token)| {
+                let id = mapping.get(&id).or_else(|| model_mapping.get(&id))?;
+                Some((*id, token))
+            })
+            .collect();
+        self.refresh_added_tokens(model, normalizer);
+
+        mapping
+    }
+
+    /// Reconstruct our internal tries when new tokens are added to the vocabulary.
     ///
-    /// We keep two different RegexSet, one that will take care of matching against the
+    /// We keep two different tries, one that will take care of matching against the
     /// non-normalized string, and one matching against the normalized one.
     fn refresh_added_tokens<N: Normalizer>(&mut self, model: &impl Model, normalizer: Option<&N>) {
         type TupleTokenId<'a> = (&'a AddedToken, u32);
@@ -292,106 +320,94 @@ impl AddedVocabulary {
             .partition(|(token, _)| token.normalized);
 
         let (tokens, ids): (Vec<&AddedToken>, Vec<u32>) = non_normalized.into_iter().unzip();
-        self.split_re = (
-            regex::RegexSet::new(tokens.iter().map(|t| t.get_pattern(normalizer))).unwrap(),
-            ids,
-        );
+        self.split_trie = build_matching_set(tokens.iter().map(|t| pattern(t, normalizer)), ids);
 
         let (tokens, ids): (Vec<&AddedToken>, Vec<u32>) = normalized.into_iter().unzip();
-        self.split_normalized_re = (
-            regex::RegexSet::new(tokens.iter().map(|t| t.get_pattern(normalizer))).unwrap(),
-            ids,
-        );
+        self.split_normalized_trie =
+            build_matching_set(tokens.iter().map(|t| pattern(t, normalizer)), ids);
     }
 
     /// Find any AddedToken in the given sentence, using the provided MatchingSet.
     /// This method returns a list "splits", each of them being a pair of Offsets
     /// and an optional ID if it is an AddedToken.
     /// The list of splits cover the entire input string.
-    fn find_matches<'a>(
+    fn find_matches(
         &self,
         sentence: &str,
-        split_re: &'a MatchingSet,
+        split_trie: &MatchingSet,
     ) -> Vec<(Option<u32>, Offsets)> {
         if sentence.is_empty() {
             return vec![(None, (0, 0))];
         }
 
-        let mut matches = split_re
+        // Find all the matches, even the overlapping ones, except the single word tokens
+        // that are not on word boundaries
+        let mut matches = split_trie
             .0
-            .matches(sentence)
-            .into_iter()
-            .flat_map(|idx| {
-                regex::Regex::new(&split_re.0.patterns()[idx])
-                    .unwrap()
-                    .find_iter(sentence)
-                    .map(|m| (idx, (m.start(), m.end())))
-                    .collect::<Vec<_>>()
+            .find_overlapping_iter(sentence)
+            .map(|mat| (mat.start(), mat.pattern(), mat.end()))
+            .filter(|(start, pattern, end)| {
+                let token = &self.added_tokens_map_r[&split_trie.1[*pattern]];
+                if !token.single_word {
+                    return true;
+                }
+                let content = &sentence[*start..*end];
+                // A word boundary is required on each side of the match, but only when
+                // the token itself starts (or ends) with a word character.
+                let bad_start = is_word_char(content.chars().next())
+                    && is_word_char(sentence[..*start].chars().next_back());
+                let bad_end = is_word_char(content.chars().next_back())
+                    && is_word_char(sentence[*end..].chars().next());
+                !(bad_start || bad_end)
             })
             .collect::<Vec<_>>();
 
-        // We sort all the matches by their start and then by their pattern id
-        matches.sort_by(
-            |(idxa, (sa, _)), (idxb, (sb, _))| {
-                if sa != sb {
-                    sa.cmp(sb)
-                } else {
-                    idxa.cmp(idxb)
-                }
-            },
-        );
-
-        // Select the matches (if some are overlapping) we want to keep
-        let mut i = 0;
+        // Keep the leftmost-longest matches: we sort them by their start, the longest first,
+        // and skip those overlapping the ones already selected
+        matches.sort_unstable_by_key(|(start, pattern, end)| (*start, Reverse(*end), *pattern));
         let mut current_offset = 0;
-        let mut splits = Vec::with_capacity(matches.len());
-        while i < matches.len() {
-            let (idx, (start, end)) = matches[i];
-
-            // current match is before the currentt offset, let's skip it
-            if start < current_offset {
-                i += 1;
-                continue;
-            }
-
-            // Find out if we have overlapping neighbors. If so, we keep the one with the lowest
-            // idx, and apply it, then continue. All others will be skipped since `current_offset`
-            // will have been increased
-            if i + 1 < matches.len() {
-                if let Some((idx, (s, e))) = matches[i..]
-                    .iter()
-                    .take_while(|(_, (s, e))| *s < end && start < *e)
-                    .min() // Order on idx first
-                    .copied()
-                {
-                    splits.push((idx, (s, e)));
-                    current_offset = e;
-                    i += 1;
-                    continue;
-                }
+        let mut selected = Vec::with_capacity(matches.len());
+        for (start, pattern, end) in matches {
+            if start >= current_offset {
+                selected.push((start, pattern, end));
+                current_offset = end;
             }
-
-            // We didn't find overlapping neighbors, apply ourself
-            splits.push((idx, (start, end)));
-            current_offset = end;
-            i += 1;
         }
 
-        // We also insert the splits that are inbetween the added tokens, to split the entire string
+        // Apply lstrip and rstrip, without overlapping the neighbors, and insert the splits that
+        // are inbetween the added tokens, to split the entire string
         let mut start_offset = 0;
-        let mut splits = splits
-            .into_iter()
-            .flat_map(|(idx, (start, end))| {
-                let mut splits = vec![];
-                if start_offset < start {
-                    splits.push((None, (start_offset, start)));
+        let mut splits = vec![];
+        for (i, (mut start, pattern, mut end)) in selected.iter().copied().enumerate() {
+            let id = split_trie.1[pattern];
+            let token = &self.added_tokens_map_r[&id];
+            let next_start = selected.get(i + 1).map_or(sentence.len(), |(s, _, _)| *s);
+
+            if token.lstrip {
+                // Include at most one whitespace on the left, as long as it has not
+                // already been used by the previous match
+                if let Some(c) = sentence[start_offset..start].chars().next_back() {
+                    if c.is_whitespace() {
+                        start -= c.len_utf8();
+                    }
+                }
+            }
+            if token.rstrip {
+                // Include at most one whitespace on the right, as long as it is not part of
+                // the next match
+                if let Some(c) = sentence[end..next_start].chars().next() {
+                    if c.is_whitespace() {
+                        end += c.len_utf8();
+                    }
                 }
-                splits.push((Some(split_re.1[idx] as u32), (start, end)));
-                start_offset = end;
+            }
 
-                splits
-            })
-            .collect::<Vec<_>>();
+            if start_offset < start {
+                splits.push((None, (start_offset, start)));
+            }
+            splits.push((Some(id), (start, end)));
+            start_offset = end;
+        }
 
         let total_byte_len = sentence.len();
         if start_offset != total_byte_len {
@@ -407,9 +423,9 @@ impl AddedVocabulary {
     fn split_with_indices(
         &self,
         sentence: NormalizedString,
-        split_re: &MatchingSet,
+        split_trie: &MatchingSet,
     ) -> Vec<(NormalizedString, Option<Vec<Token>>)> {
-        self.find_matches(sentence.get(), split_re)
+        self.find_matches(sentence.get(), split_trie)
             .into_iter()
             .map(|(id, byte_offsets)| {
                 let slice = sentence
@@ -441,14 +457,14 @@ impl AddedVocabulary {
 
         // 1. We extract all the non-normalized tokens from the non-normalized string
         pretokenized
-            .split(|_, sequence| Ok(self.split_with_indices(sequence, &self.split_re)))
+            .split(|_, sequence| Ok(self.split_with_indices(sequence, &self.split_trie)))
             .expect("AddedVocabulary bad split");
 
         // 2. Then extract the normalized tokens from the normalized pieces of the string
         pretokenized
             .split(|_, mut sequence| {
                 normalizer.map(|n| n.normalize(&mut sequence));
-                Ok(self.split_with_indices(sequence, &self.split_normalized_re))
+                Ok(self.split_with_indices(sequence, &self.split_normalized_trie))
             })
             .expect("AddedVocabulary bad split");
 
@@ -725,10 +741,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalized_content_of_single_words() {
+        let model = ModelMock::new(&[]);
+        let normalizer = Lowercase;
+        let mut vocab = AddedVocabulary::new();
+
+        vocab.add_tokens(
+            &[
+                AddedToken::from("Hello", false),
+                AddedToken::from("World", false).single_word(true),
+            ],
+            &model,
+            Some(&normalizer),
+        );
+
+        // Only the content of the `single_word` tokens is normalized to be matched against the
+        // normalized input, `Hello` never matches after lowercasing
+        let result = vocab.extract_and_normalize(Some(&normalizer), "Hello World");
+        assert_eq!(
+            result
+                .get_splits(OffsetReferential::Original, OffsetType::Byte)
+                .into_iter()
+                .map(|(s, _, tokens)| (s, tokens.as_ref().map(|t| t[0].id)))
+                .collect::<Vec<_>>(),
+            vec![("hello ", None), ("world", Some(1))]
+        );
+    }
+
+    #[test]
+    fn longest_match_wins() {
+        let model = ModelMock::new(&[]);
+        let normalizer: Option<&NormalizerWrapper> = None;
+        let mut vocab = AddedVocabulary::new();
+
+        vocab.add_tokens(
+            &[
+                AddedToken::from("hello", false),
+                AddedToken::from("hello world", false),
+                AddedToken::from("wor", false),
+            ],
+            &model,
+            normalizer,
+        );
+
+        // The insertion order doesn't matter: the leftmost match is kept, and the longest one
+        // amongst those starting at the same position
+        let matches = vocab.find_matches("hello world, hello wor", &vocab.split_normalized_trie);
+        assert_eq!(
+            matches,
+            vec![
+                (Some(1), (0, 11)),
+                (None, (11, 13)),
+                (Some(0), (13, 18)),
+                (None, (18, 19)),
+                (Some(2), (19, 22)),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_word_boundaries() {
+        let model = ModelMock::new(&[]);
+        let normalizer: Option<&NormalizerWrapper> = None;
+        let mut vocab = AddedVocabulary::new();
+
+        vocab.add_tokens(
+            &[
+                AddedToken::from("ony", false).single_word(true),
+                AddedToken::from("<br>", false).single_word(true),
+            ],
+            &model,
+            normalizer,
+        );
+
+        // `ony` only matches as a whole word, while `<br>` doesn't need any boundary since
+        // it neither starts nor ends with a word character
+        let matches = vocab.find_matches("Anthony ony a<br>b", &vocab.split_normalized_trie);
+        assert_eq!(
+            matches,
+            vec![
+                (None, (0, 8)),
+                (Some(0), (8, 11)),
+                (None, (11, 13)),
+                (Some(1), (13, 17)),
+                (None, (17, 18)),
+            ]
+        );
+    }
+
+    #[test]
+    fn strip_spaces_once() {
+        let model = ModelMock::new(&[]);
+        let normalizer: Option<&NormalizerWrapper> = None;
+        let mut vocab = AddedVocabulary::new();
+
+        vocab.add_tokens(
+            &[
+                AddedToken::from("[A]", false).rstrip(true),
+                AddedToken::from("[B]", false).lstrip(true),
+            ],
+            &model,
+            normalizer,
+        );
+
+        // The space between both tokens can only be used by the first one
+        let matches = vocab.find_matches("x  [A] [B]", &vocab.split_normalized_trie);
+        assert_eq!(
+            matches,
+            vec![(None, (0, 3)), (Some(0), (3, 7)), (Some(1), (7, 10))]
+        );
+    }
+
+    #[test]
+    fn rejected_single_word_keeps_overlapping_matches() {
+        let model = ModelMock::new(&[]);
+        let normalizer: Option<&NormalizerWrapper> = None;
+        let mut vocab = AddedVocabulary::new();
+
+        vocab.add_tokens(
+            &[
+                AddedToken::from("thony", false).single_word(true),
+                AddedToken::from("ony", false),
+            ],
+            &model,
+            normalizer,
+        );
+
+        // `thony` is not a whole word, but `ony` still matches inside of it
+        let matches = vocab.find_matches("Anthony", &vocab.split_normalized_trie);
+        assert_eq!(matches, vec![(None, (0, 4)), (Some(1), (4, 7))]);
+    }
+
+    #[test]
+    fn strip_spaces_before_next_match() {
+        let model = ModelMock::new(&[]);
+        let normalizer: Option<&NormalizerWrapper> = None;
+        let mut vocab = AddedVocabulary::new();
+
+        vocab.add_tokens(
+            &[
+                AddedToken::from("[A]", false).rstrip(true),
+                AddedToken::from(" b", false).lstrip(true),
+            ],
+            &model,
+            normalizer,
+        );
+
+        // The space after `[A]` is part of the next match, so it can't be stripped
+        let matches = vocab.find_matches("[A] b", &vocab.split_normalized_trie);
+        assert_eq!(matches, vec![(Some(0), (0, 3)), (Some(1), (3, 5))]);
+    }
+
     #[test]
     fn empty_matches() {
         let vocab = AddedVocabulary::new();
-        let matches = vocab.find_matches("", &vocab.split_re);
+        let matches = vocab.find_matches("", &vocab.split_trie);
         assert_eq!(matches, vec![(None, (0, 0))]);
     }
 }
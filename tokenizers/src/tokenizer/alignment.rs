@@ -0,0 +1,346 @@
+//! Renders the alignments between an original string and its normalized version.
+//!
+//! An `AlignmentReport` can be built from a `NormalizedString`, a `PreTokenizedString`, or
+//! directly by a `Tokenizer` with `TokenizerImpl::alignment_report`. It can be serialized to
+//! JSON, and its `Display` implementation renders it as a table, with a row per normalized char.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::normalizer::Range;
+use crate::{Model, NormalizedString, Normalizer, Offsets, PreTokenizer, Result, TokenizerImpl};
+
+/// The alignment of a part of the normalized string with the original one. All the offsets are
+/// in bytes.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CharAlignment {
+    /// A char of the normalized string, or an empty string for a removed part of the original
+    pub normalized: String,
+    pub normalized_offsets: Offsets,
+    /// The part of the original string this char is aligned with
+    pub original: String,
+    pub original_offsets: Offsets,
+}
+
+/// A token produced by the `Model` for a split
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TokenAlignment {
+    pub id: u32,
+    pub value: String,
+    pub normalized_offsets: Offsets,
+    pub original_offsets: Offsets,
+}
+
+/// A split produced by the `PreTokenizer`, or extracted as an added token
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitAlignment {
+    pub normalized: String,
+    pub normalized_offsets: Offsets,
+    pub original: String,
+    pub original_offsets: Offsets,
+    /// The tokens of this split, if it has been tokenized
+    pub tokens: Option<Vec<TokenAlignment>>,
+}
+
+/// A structured view of the alignments between an original string, and its normalized,
+/// pre-tokenized and tokenized versions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlignmentReport {
+    pub original: String,
+    /// The normalized string. For a `PreTokenizedString`, this is the concatenation of its splits
+    pub normalized: String,
+    /// The alignment of each normalized char, including the removed parts of the original
+    pub alignments: Vec<CharAlignment>,
+    /// The splits, empty when built from a `NormalizedString`
+    pub splits: Vec<SplitAlignment>,
+}
+
+impl AlignmentReport {
+    pub(crate) fn new(original: &str) -> Self {
+        Self {
+            original: original.to_owned(),
+            normalized: String::new(),
+            alignments: vec![],
+            splits: vec![],
+        }
+    }
+
+    /// Append the given `NormalizedString` to the report, and return its offsets in the
+    /// normalized string of the report
+    pub(crate) fn push_normalized(&mut self, normalized: &NormalizedString) -> Offsets {
+        let shift = normalized.offsets_original().0;
+        let start = self.normalized.len();
+        let mut original_end = self.original_end();
+        for (i, c) in normalized.get().char_indices() {
+            let end = i + c.len_utf8();
+            let original = normalized
+                .convert_offsets(Range::Normalized(i..end))
+                .map_or((shift, shift), |r| (shift + r.start, shift + r.end));
+            if original.0 > original_end {
+                self.push_removed((original_end, original.0), start + i);
+            }
+            original_end = original_end.max(original.1);
+            self.alignments.push(CharAlignment {
+                normalized: c.to_string(),
+                normalized_offsets: (start + i, start + end),
+                original: self.original[original.0..original.1].to_owned(),
+                original_offsets: original,
+            });
+        }
+        self.normalized.push_str(normalized.get());
+
+        (start, self.normalized.len())
+    }
+
+    /// Append a split to the report, with its optional tokens
+    pub(crate) fn push_split(
+        &mut self,
+        normalized: &NormalizedString,
+        tokens: Option<&[crate::Token]>,
+    ) {
+        let normalized_offsets = self.push_normalized(normalized);
+        let original_offsets = normalized.offsets_original();
+        let shift = original_offsets.0;
+        let tokens = tokens.map(|tokens| {
+            tokens
+                .iter()
+                .map(|token| TokenAlignment {
+                    id: token.id,
+                    value: token.value.clone(),
+                    normalized_offsets: (
+                        normalized_offsets.0 + token.offsets.0,
+                        normalized_offsets.0 + token.offsets.1,
+                    ),
+                    original_offsets: normalized
+                        .convert_offsets(Range::Normalized(token.offsets.0..token.offsets.1))
+                        .map_or(original_offsets, |r| (shift + r.start, shift + r.end)),
+                })
+                .collect()
+        });
+
+        self.splits.push(SplitAlignment {
+            normalized: normalized.get().to_owned(),
+            normalized_offsets,
+            original: self.original[original_offsets.0..original_offsets.1].to_owned(),
+            original_offsets,
+            tokens,
+        });
+    }
+
+    /// Add the end of the original string that is not aligned with anything, if any
+    pub(crate) fn finish(mut self) -> Self {
+        let original_end = self.original_end();
+        if original_end < self.original.len() {
+            let end = self.normalized.len();
+            self.push_removed((original_end, self.original.len()), end);
+        }
+        self
+    }
+
+    fn original_end(&self) -> usize {
+        self.alignments
+            .iter()
+            .map(|a| a.original_offsets.1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn push_removed(&mut self, original: Offsets, normalized: usize) {
+        self.alignments.push(CharAlignment {
+            normalized: String::new(),
+            normalized_offsets: (normalized, normalized),
+            original: self.original[original.0..original.1].to_owned(),
+            original_offsets: original,
+        });
+    }
+}
+
+impl fmt::Display for AlignmentReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show_offsets = |(start, end): Offsets| format!("{}..{}", start, end);
+
+        let mut rows = vec![[
+            "Normalized".to_owned(),
+            "Bytes".to_owned(),
+            "Original".to_owned(),
+            "Bytes".to_owned(),
+            "Split".to_owned(),
+            "Tokens".to_owned(),
+        ]];
+        for alignment in &self.alignments {
+            let start = alignment.normalized_offsets.0;
+            let is_char = !alignment.normalized.is_empty();
+            // Splits and tokens are only shown on the row of their first char
+            let split = self
+                .splits
+                .iter()
+                .position(|s| is_char && s.normalized_offsets.0 == start)
+                .map_or_else(String::new, |i| i.to_string());
+            let tokens = self
+                .splits
+                .iter()
+                .filter_map(|s| s.tokens.as_ref())
+                .flatten()
+                .filter(|t| is_char && t.normalized_offsets.0 == start)
+                .map(|t| format!("{:?} ({})", t.value, t.id))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            rows.push([
+                if is_char {
+                    format!("{:?}", alignment.normalized)
+                } else {
+                    String::new()
+                },
+                if is_char {
+                    show_offsets(alignment.normalized_offsets)
+                } else {
+                    String::new()
+                },
+                format!("{:?}", alignment.original),
+                show_offsets(alignment.original_offsets),
+                split,
+                tokens,
+            ]);
+        }
+
+        let mut widths = [0; 6];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for row in rows {
+            let line = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
+where
+    M: Model,
+    N: Normalizer,
+    PT: PreTokenizer,
+{
+    /// Normalize, pre-tokenize and tokenize the given sequence, and report the alignments of
+    /// each step with the original sequence. No special tokens are added.
+    pub fn alignment_report(&self, sequence: &str) -> Result<AlignmentReport> {
+        let pretokenized = self
+            .added_vocabulary
+            .extract_and_normalize(self.normalizer.as_ref(), sequence);
+        let mut pretokenized = self.do_pre_tokenize(pretokenized)?;
+        pretokenized.tokenize(|normalized| self.model.tokenize(normalized.get()))?;
+
+        Ok(pretokenized.alignment_report())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::wordlevel::WordLevel;
+    use crate::normalizers::unicode::NFD;
+    use crate::normalizers::utils::Lowercase;
+    use crate::normalizers::{NormalizerWrapper, Sequence};
+    use crate::pre_tokenizers::whitespace::WhitespaceSplit;
+    use crate::{AddedToken, Tokenizer};
+
+    fn alignment(normalized: &str, n: Offsets, original: &str, o: Offsets) -> CharAlignment {
+        CharAlignment {
+            normalized: normalized.into(),
+            normalized_offsets: n,
+            original: original.into(),
+            original_offsets: o,
+        }
+    }
+
+    #[test]
+    fn normalized_string() {
+        let mut n = NormalizedString::from(" Hé ");
+        n.nfd().filter(|c| c != ' ');
+        let report = n.alignment_report();
+
+        assert_eq!(report.original, " Hé ");
+        assert_eq!(report.normalized, "He\u{301}");
+        assert_eq!(
+            report.alignments,
+            vec![
+                alignment("", (0, 0), " ", (0, 1)),
+                alignment("H", (0, 1), "H", (1, 2)),
+                alignment("e", (1, 2), "é", (2, 4)),
+                alignment("\u{301}", (2, 4), "é", (2, 4)),
+                alignment("", (4, 4), " ", (4, 5)),
+            ]
+        );
+        assert!(report.splits.is_empty());
+    }
+
+    #[test]
+    fn tokenizer() {
+        let vocab = ["[UNK]", "hello", "world", "[SEP]"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.to_string(), i as u32))
+            .collect();
+        let mut tokenizer = Tokenizer::new(
+            WordLevel::builder()
+                .vocab(vocab)
+                .unk_token("[UNK]".into())
+                .build(),
+        );
+        tokenizer.with_normalizer(Sequence::new(vec![
+            NormalizerWrapper::from(NFD),
+            NormalizerWrapper::from(Lowercase),
+        ]));
+        tokenizer.with_pre_tokenizer(WhitespaceSplit);
+        tokenizer.add_special_tokens(&[AddedToken::from("[SEP]", true)]);
+
+        let report = tokenizer.alignment_report("Hello [SEP] wörld").unwrap();
+        assert_eq!(report.normalized, "hello[SEP]wo\u{308}rld");
+        assert_eq!(
+            report
+                .splits
+                .iter()
+                .map(|s| (s.original.as_str(), s.normalized_offsets))
+                .collect::<Vec<_>>(),
+            vec![("Hello", (0, 5)), ("[SEP]", (5, 10)), ("wörld", (10, 17))]
+        );
+        let tokens = report
+            .splits
+            .iter()
+            .flat_map(|s| s.tokens.clone().unwrap())
+            .map(|t| (t.id, t.original_offsets))
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, vec![(1, (0, 5)), (3, (6, 11)), (0, (12, 18))]);
+        // The spaces removed by the pre-tokenizer are kept in the alignments
+        assert_eq!(report.alignments[5], alignment("", (5, 5), " ", (5, 6)));
+        assert_eq!(
+            report.alignments[14],
+            alignment("\u{308}", (12, 14), "ö", (13, 15))
+        );
+
+        let table = report.to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), report.alignments.len() + 1);
+        assert_eq!(
+            lines[0],
+            "Normalized | Bytes  | Original | Bytes  | Split | Tokens"
+        );
+        assert_eq!(
+            lines[1],
+            "\"h\"        | 0..1   | \"H\"      | 0..1   | 0     | \"hello\" (1)"
+        );
+        assert_eq!(
+            lines[6],
+            "           |        | \" \"      | 5..6   |       |"
+        );
+    }
+}
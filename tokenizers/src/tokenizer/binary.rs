@@ -0,0 +1,876 @@
+//! A compact binary format for the tokenizers, meant to be loaded quickly.
+//!
+//! The vocabulary and the merges of a `BPE`, and the vocabulary, scores and trie of a `Unigram`,
+//! are stored as tables that are used directly from a memory-mapped file: the tokens are in a
+//! string table ordered by id, along with an index sorted by token, the merges are triples of
+//! ids along with an index sorted by pair, and the trie is flattened into arrays of nodes and
+//! edges. Everything else is kept as in the tokenizer.json, so that both formats can be
+//! converted into each other without losing anything.
+//!
+//! The models loaded from a binary tokenizer share its tables, and look the tokens, the merges
+//! and the prefixes up in place instead of copying them. The maps returned by
+//! `Model::get_vocab` and `Unigram::iter` are only built the first time they are needed, and a
+//! model copies the tables out before being modified, by `Model::prune` for example. The
+//! `binary_benchmark` compares the loading of both formats.
+//!
+//! All the integers are little-endian:
+//! ```text
+//! magic           b"HFTKBIN\0"
+//! version         u32
+//! tables          u32 kind of model: 0 for none, 1 for BPE, 2 for Unigram
+//! config          u32 length, followed by the tokenizer.json without the tables
+//! tokens          u32 count n, (n + 1) u32 offsets, u32 length, the strings
+//! sorted          u32 count, the u32 ids ordered by their token, the last one of a duplicate
+//! merges          u32 count m, m * (u32 a, u32 b, u32 new id) in their order of priority
+//! merge index     m u32 ranks, ordered by the pair of ids of their merge
+//! scores          u32 count s (0 or n), s f64
+//! trie            u32 count t of nodes, (t + 1) u32 offsets of their edges, t u32 ids
+//!                 (u32::MAX for none), u32 count e of edges, e u32 children, e u8 labels
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+use memmap2::Mmap;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::serialization::deserialize_tokenizer;
+use super::Tokenizer;
+use crate::models::bpe::BPE;
+use crate::models::unigram::{Unigram, UnigramSampling};
+use crate::models::ModelWrapper;
+use crate::Result;
+
+const MAGIC: &[u8; 8] = b"HFTKBIN\0";
+const FORMAT_VERSION: u32 = 2;
+
+const BPE_TABLES: u32 = 1;
+const UNIGRAM_TABLES: u32 = 2;
+
+/// The id of the trie nodes that don't end a token
+const NO_ID: u32 = u32::MAX;
+
+/// The tables extracted from a model
+#[derive(Default)]
+struct Tables {
+    kind: u32,
+    tokens: Vec<String>,
+    merges: Vec<(u32, u32, u32)>,
+    scores: Vec<f64>,
+}
+
+/// Take the vocabulary and the merges out of the serialized `BPE`. The ids must go from 0 to
+/// the size of the vocabulary, otherwise the model is kept as is.
+fn take_bpe_tables(model: &mut Map<String, Value>) -> Option<Tables> {
+    let vocab = model.get("vocab")?.as_object()?;
+    let mut tokens = vec![None; vocab.len()];
+    for (token, id) in vocab {
+        let slot = tokens.get_mut(id.as_u64()? as usize)?;
+        if slot.replace(token.clone()).is_some() {
+            return None;
+        }
+    }
+    let tokens = tokens.into_iter().collect::<Option<Vec<_>>>()?;
+
+    let prefix = model
+        .get("continuing_subword_prefix")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let id = |token: &str| Some(vocab.get(token)?.as_u64()? as u32);
+    let merges = model
+        .get("merges")?
+        .as_array()?
+        .iter()
+        .map(|merge| match merge.as_array()?.as_slice() {
+            [a, b] => {
+                let (a, b) = (a.as_str()?, b.as_str()?);
+                let new_token = format!("{}{}", a, b.strip_prefix(prefix).unwrap_or(b));
+                Some((id(a)?, id(b)?, id(&new_token)?))
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    model.remove("vocab");
+    model.remove("merges");
+    Some(Tables {
+        kind: BPE_TABLES,
+        tokens,
+        merges,
+        scores: vec![],
+    })
+}
+
+/// Take the vocabulary, with its scores, out of the serialized `Unigram`
+fn take_unigram_tables(model: &mut Map<String, Value>) -> Option<Tables> {
+    let (tokens, scores) = model
+        .get("vocab")?
+        .as_array()?
+        .iter()
+        .map(|piece| match piece.as_array()?.as_slice() {
+            [token, score] => Some((token.as_str()?.to_owned(), score.as_f64()?)),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    model.remove("vocab");
+    Some(Tables {
+        kind: UNIGRAM_TABLES,
+        tokens,
+        merges: vec![],
+        scores,
+    })
+}
+
+/// A trie of the tokens, flattened so that the edges of each node are contiguous and sorted by
+/// their label
+#[derive(Default)]
+struct FlatTrie {
+    edges: Vec<usize>,
+    ids: Vec<u32>,
+    children: Vec<usize>,
+    labels: Vec<u8>,
+}
+
+fn flatten_trie(tokens: &[String]) -> FlatTrie {
+    let mut nodes = vec![(BTreeMap::new(), NO_ID)];
+    for (id, token) in tokens.iter().enumerate() {
+        let mut node = 0;
+        for label in token.bytes() {
+            let next = nodes.len();
+            node = *nodes[node].0.entry(label).or_insert(next);
+            if node == next {
+                nodes.push((BTreeMap::new(), NO_ID));
+            }
+        }
+        // As in the maps of the models, the last id of a duplicate token is used
+        nodes[node].1 = id as u32;
+    }
+
+    let mut trie = FlatTrie::default();
+    for (children, id) in nodes {
+        trie.edges.push(trie.children.len());
+        trie.ids.push(id);
+        for (label, child) in children {
+            trie.labels.push(label);
+            trie.children.push(child);
+        }
+    }
+    trie.edges.push(trie.children.len());
+    trie
+}
+
+fn push_u32(buffer: &mut Vec<u8>, value: usize) -> Result<()> {
+    if value > u32::MAX as usize {
+        return Err(format!("{} is too large for the binary format", value).into());
+    }
+    buffer.extend_from_slice(&(value as u32).to_le_bytes());
+    Ok(())
+}
+
+/// Serialize a tokenizer in the binary format
+pub(crate) fn to_binary<T: Serialize>(tokenizer: &T) -> Result<Vec<u8>> {
+    let mut config = serde_json::to_value(tokenizer)?;
+    let tables = match config.get_mut("model") {
+        Some(Value::Object(model)) => match model.get("type").and_then(Value::as_str) {
+            Some("BPE") => take_bpe_tables(model),
+            Some("Unigram") => take_unigram_tables(model),
+            _ => None,
+        },
+        _ => None,
+    }
+    .unwrap_or_default();
+    let config = serde_json::to_vec(&config)?;
+
+    let mut buffer = Vec::with_capacity(config.len());
+    buffer.extend_from_slice(MAGIC);
+    push_u32(&mut buffer, FORMAT_VERSION as usize)?;
+    push_u32(&mut buffer, tables.kind as usize)?;
+    push_u32(&mut buffer, config.len())?;
+    buffer.extend_from_slice(&config);
+
+    let tokens = &tables.tokens;
+    push_u32(&mut buffer, tokens.len())?;
+    let mut offset = 0;
+    push_u32(&mut buffer, offset)?;
+    for token in tokens {
+        offset += token.len();
+        push_u32(&mut buffer, offset)?;
+    }
+    push_u32(&mut buffer, offset)?;
+    for token in tokens {
+        buffer.extend_from_slice(token.as_bytes());
+    }
+    let mut sorted = (0..tokens.len()).collect::<Vec<_>>();
+    sorted.sort_by(|a, b| tokens[*a].cmp(&tokens[*b]).then(b.cmp(a)));
+    sorted.dedup_by(|a, b| tokens[*a] == tokens[*b]);
+    push_u32(&mut buffer, sorted.len())?;
+    for id in sorted {
+        push_u32(&mut buffer, id)?;
+    }
+
+    let merges = &tables.merges;
+    push_u32(&mut buffer, merges.len())?;
+    for (a, b, new_id) in merges {
+        push_u32(&mut buffer, *a as usize)?;
+        push_u32(&mut buffer, *b as usize)?;
+        push_u32(&mut buffer, *new_id as usize)?;
+    }
+    let mut index = (0..merges.len()).collect::<Vec<_>>();
+    index.sort_by_key(|rank| (merges[*rank].0, merges[*rank].1));
+    for rank in index {
+        push_u32(&mut buffer, rank)?;
+    }
+
+    push_u32(&mut buffer, tables.scores.len())?;
+    for score in &tables.scores {
+        buffer.extend_from_slice(&score.to_le_bytes());
+    }
+
+    let trie = if tables.kind == UNIGRAM_TABLES {
+        flatten_trie(tokens)
+    } else {
+        FlatTrie {
+            edges: vec![0],
+            ..Default::default()
+        }
+    };
+    push_u32(&mut buffer, trie.ids.len())?;
+    for offset in trie.edges {
+        push_u32(&mut buffer, offset)?;
+    }
+    for id in trie.ids {
+        buffer.extend_from_slice(&id.to_le_bytes());
+    }
+    push_u32(&mut buffer, trie.children.len())?;
+    for child in trie.children {
+        push_u32(&mut buffer, child)?;
+    }
+    buffer.extend_from_slice(&trie.labels);
+
+    Ok(buffer)
+}
+
+enum Bytes {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Bytes::Mapped(mmap) => mmap,
+            Bytes::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// Reads the sections of a binary tokenizer, checking their bounds
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn section(&mut self, len: usize) -> Result<Range<usize>> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("Invalid binary tokenizer: the file is truncated")?;
+        let range = self.pos..end;
+        self.pos = end;
+        Ok(range)
+    }
+
+    fn u32(&mut self) -> Result<usize> {
+        let range = self.section(4)?;
+        Ok(read_u32(&self.bytes[range], 0) as usize)
+    }
+
+    fn u32_array(&mut self, len: usize) -> Result<Range<usize>> {
+        let len = len
+            .checked_mul(4)
+            .ok_or("Invalid binary tokenizer: the file is truncated")?;
+        self.section(len)
+    }
+}
+
+fn read_u32(bytes: &[u8], index: usize) -> u32 {
+    let mut value = [0; 4];
+    value.copy_from_slice(&bytes[index * 4..index * 4 + 4]);
+    u32::from_le_bytes(value)
+}
+
+fn read_u32s(bytes: &[u8]) -> impl Iterator<Item = u32> + '_ {
+    bytes
+        .chunks_exact(4)
+        .map(|value| u32::from_le_bytes([value[0], value[1], value[2], value[3]]))
+}
+
+/// The tables of a binary tokenizer, shared by the `BinaryTokenizer` and the models loaded
+/// from it. They are looked up in place, only the maps some methods return a reference to are
+/// built, on demand.
+pub(crate) struct MappedTables {
+    bytes: Bytes,
+    kind: u32,
+    config: Range<usize>,
+    offsets: Range<usize>,
+    strings: Range<usize>,
+    sorted: Range<usize>,
+    merges: Range<usize>,
+    merge_index: Range<usize>,
+    scores: Range<usize>,
+    trie_edges: Range<usize>,
+    trie_ids: Range<usize>,
+    trie_children: Range<usize>,
+    trie_labels: Range<usize>,
+    vocab: OnceCell<HashMap<String, u32>>,
+    pieces: OnceCell<Vec<(String, f64)>>,
+}
+
+impl MappedTables {
+    fn new(bytes: Bytes) -> Result<Self> {
+        let mut reader = Reader {
+            bytes: bytes.as_ref(),
+            pos: 0,
+        };
+        if reader.section(MAGIC.len()).ok().map(|r| &reader.bytes[r]) != Some(&MAGIC[..]) {
+            return Err("Invalid binary tokenizer: wrong magic number".into());
+        }
+        let version = reader.u32()?;
+        if version != FORMAT_VERSION as usize {
+            return Err(format!(
+                "Unknown binary tokenizer version {}, expected {}",
+                version, FORMAT_VERSION
+            )
+            .into());
+        }
+        let kind = reader.u32()? as u32;
+        if kind > UNIGRAM_TABLES {
+            return Err(format!("Invalid binary tokenizer: unknown tables {}", kind).into());
+        }
+        let config_len = reader.u32()?;
+        let config = reader.section(config_len)?;
+        let n_tokens = reader.u32()?;
+        let offsets = reader.u32_array(n_tokens + 1)?;
+        let strings_len = reader.u32()?;
+        let strings = reader.section(strings_len)?;
+        let n_sorted = reader.u32()?;
+        let sorted = reader.u32_array(n_sorted)?;
+        let n_merges = reader.u32()?;
+        let merges = reader.u32_array(n_merges * 3)?;
+        let merge_index = reader.u32_array(n_merges)?;
+        let n_scores = reader.u32()?;
+        let expected_scores = if kind == UNIGRAM_TABLES { n_tokens } else { 0 };
+        if n_scores != expected_scores {
+            return Err("Invalid binary tokenizer: wrong number of scores".into());
+        }
+        let scores = reader.section(n_scores * 8)?;
+        let n_nodes = reader.u32()?;
+        let trie_edges = reader.u32_array(n_nodes + 1)?;
+        let trie_ids = reader.u32_array(n_nodes)?;
+        let n_edges = reader.u32()?;
+        let trie_children = reader.u32_array(n_edges)?;
+        let trie_labels = reader.section(n_edges)?;
+
+        let tables = Self {
+            kind,
+            config,
+            offsets,
+            strings,
+            sorted,
+            merges,
+            merge_index,
+            scores,
+            trie_edges,
+            trie_ids,
+            trie_children,
+            trie_labels,
+            vocab: OnceCell::new(),
+            pieces: OnceCell::new(),
+            bytes,
+        };
+        if !tables.is_valid() {
+            return Err("Invalid binary tokenizer: the tables are corrupted".into());
+        }
+        Ok(tables)
+    }
+
+    /// Check once that all the offsets and ids are in bounds, so that the lookups can rely on it
+    fn is_valid(&self) -> bool {
+        let strings = match std::str::from_utf8(self.bytes(&self.strings)) {
+            Ok(strings) => strings,
+            Err(_) => return false,
+        };
+        let mut start = 0;
+        let offsets = read_u32s(self.bytes(&self.offsets)).all(|end| {
+            let end = end as usize;
+            let valid = start <= end && strings.is_char_boundary(end);
+            start = end;
+            valid
+        });
+
+        let n_tokens = self.len() as u32;
+        let are_tokens =
+            |range: &Range<usize>| read_u32s(self.bytes(range)).all(|id| id < n_tokens);
+        let n_merges = self.merges_len() as u32;
+        let ranks = read_u32s(self.bytes(&self.merge_index)).all(|rank| rank < n_merges);
+
+        let (n_nodes, n_edges) = (
+            self.trie_ids.len() as u32 / 4,
+            self.trie_labels.len() as u32,
+        );
+        let mut start = 0;
+        let edges = read_u32s(self.bytes(&self.trie_edges)).all(|end| {
+            let valid = start <= end && end <= n_edges;
+            start = end;
+            valid
+        });
+        let ids = read_u32s(self.bytes(&self.trie_ids)).all(|id| id < n_tokens || id == NO_ID);
+        let children = read_u32s(self.bytes(&self.trie_children)).all(|node| node < n_nodes);
+
+        offsets
+            && are_tokens(&self.sorted)
+            && are_tokens(&self.merges)
+            && ranks
+            && edges
+            && ids
+            && children
+    }
+
+    fn bytes(&self, range: &Range<usize>) -> &[u8] {
+        &self.bytes.as_ref()[range.clone()]
+    }
+
+    /// The number of tokens
+    pub(crate) fn len(&self) -> usize {
+        self.offsets.len() / 4 - 1
+    }
+
+    pub(crate) fn id_to_token(&self, id: u32) -> Option<&str> {
+        let id = id as usize;
+        if id >= self.len() {
+            return None;
+        }
+        let offsets = self.bytes(&self.offsets);
+        let (start, end) = (
+            read_u32(offsets, id) as usize,
+            read_u32(offsets, id + 1) as usize,
+        );
+        std::str::from_utf8(self.bytes(&self.strings).get(start..end)?).ok()
+    }
+
+    pub(crate) fn token_to_id(&self, token: &str) -> Option<u32> {
+        let sorted = self.bytes(&self.sorted);
+        let (mut low, mut high) = (0, sorted.len() / 4);
+        while low < high {
+            let middle = (low + high) / 2;
+            let id = read_u32(sorted, middle);
+            match self.id_to_token(id)?.cmp(token) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Some(id),
+            }
+        }
+        None
+    }
+
+    /// The vocabulary as a map, built the first time it is needed
+    pub(crate) fn vocab(&self) -> &HashMap<String, u32> {
+        self.vocab.get_or_init(|| {
+            (0..self.len() as u32)
+                .filter_map(|id| Some((self.id_to_token(id)?.to_owned(), id)))
+                .collect()
+        })
+    }
+
+    pub(crate) fn merges_len(&self) -> usize {
+        self.merge_index.len() / 4
+    }
+
+    /// The merges, as the pair of ids they merge and the id they produce, in their order of
+    /// priority
+    pub(crate) fn merges(&self) -> impl Iterator<Item = (u32, u32, u32)> + '_ {
+        self.bytes(&self.merges).chunks_exact(12).map(|merge| {
+            let mut ids = read_u32s(merge);
+            (
+                ids.next().unwrap(),
+                ids.next().unwrap(),
+                ids.next().unwrap(),
+            )
+        })
+    }
+
+    /// Find the merge of the given pair of ids, as its rank and the id it produces
+    pub(crate) fn merge(&self, pair: (u32, u32)) -> Option<(u32, u32)> {
+        let (merges, index) = (self.bytes(&self.merges), self.bytes(&self.merge_index));
+        let (mut low, mut high) = (0, self.merges_len());
+        while low < high {
+            let middle = (low + high) / 2;
+            let rank = read_u32(index, middle);
+            let merge = rank as usize * 3;
+            let merged = (read_u32(merges, merge), read_u32(merges, merge + 1));
+            match merged.cmp(&pair) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Some((rank, read_u32(merges, merge + 2))),
+            }
+        }
+        None
+    }
+
+    pub(crate) fn score(&self, id: u32) -> Option<f64> {
+        let start = id as usize * 8;
+        let bytes = self.bytes(&self.scores).get(start..start + 8)?;
+        let mut score = [0; 8];
+        score.copy_from_slice(bytes);
+        Some(f64::from_le_bytes(score))
+    }
+
+    /// The vocabulary with its scores, built the first time it is needed
+    pub(crate) fn pieces(&self) -> &[(String, f64)] {
+        self.pieces.get_or_init(|| {
+            (0..self.len() as u32)
+                .filter_map(|id| Some((self.id_to_token(id)?.to_owned(), self.score(id)?)))
+                .collect()
+        })
+    }
+
+    /// The tokens the given bytes start with, found in the trie, as their length and their id
+    pub(crate) fn common_prefix_search<'a>(
+        &'a self,
+        bytes: &'a [u8],
+    ) -> impl Iterator<Item = (usize, u32)> + 'a {
+        let mut node = if self.trie_ids.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        let mut len = 0;
+        std::iter::from_fn(move || loop {
+            let label = *bytes.get(len)?;
+            len += 1;
+            node = self.trie_child(node?, label);
+            let id = read_u32(self.bytes(&self.trie_ids), node? as usize);
+            if id != NO_ID {
+                return Some((len, id));
+            }
+        })
+    }
+
+    fn trie_child(&self, node: u32, label: u8) -> Option<u32> {
+        let edges = self.bytes(&self.trie_edges);
+        let (start, end) = (
+            read_u32(edges, node as usize) as usize,
+            read_u32(edges, node as usize + 1) as usize,
+        );
+        let labels = self.bytes(&self.trie_labels).get(start..end)?;
+        let edge = start + labels.binary_search(&label).ok()?;
+        Some(read_u32(self.bytes(&self.trie_children), edge))
+    }
+}
+
+/// The `model` of the tokenizer.json, to read the options of a model stored in the tables
+#[derive(Deserialize)]
+struct ModelField<T> {
+    model: T,
+}
+
+#[derive(Deserialize)]
+struct BpeOptions {
+    dropout: Option<f32>,
+    unk_token: Option<String>,
+    continuing_subword_prefix: Option<String>,
+    end_of_word_suffix: Option<String>,
+    #[serde(default)]
+    fuse_unk: bool,
+    #[serde(default)]
+    byte_fallback: bool,
+    cache_capacity: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct UnigramOptions {
+    unk_id: usize,
+    #[serde(default)]
+    byte_fallback: bool,
+    sampling: Option<UnigramSampling>,
+    cache_capacity: Option<usize>,
+}
+
+/// A tokenizer in the binary format, usually memory-mapped. Its tables can be looked up without
+/// building anything, and [`into_tokenizer`](#method.into_tokenizer) gives them to the model,
+/// which keeps using them in place.
+pub struct BinaryTokenizer {
+    tables: Arc<MappedTables>,
+}
+
+impl BinaryTokenizer {
+    /// Memory-map the given file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path)?;
+        // The file must not be modified while it is mapped, as for any memory-mapped file
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::new(Bytes::Mapped(mmap))
+    }
+
+    /// Whether the given bytes, usually the beginning of a file, are a binary tokenizer
+    pub fn is_binary(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC)
+    }
+
+    /// Use the given bytes, as produced by `TokenizerImpl::to_binary`
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        Self::new(Bytes::Owned(bytes))
+    }
+
+    fn new(bytes: Bytes) -> Result<Self> {
+        Ok(Self {
+            tables: Arc::new(MappedTables::new(bytes)?),
+        })
+    }
+
+    /// The number of tokens in the tables of the model
+    pub fn vocab_size(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Get the token with the given id, from the tables of the model
+    pub fn id_to_token(&self, id: u32) -> Option<&str> {
+        self.tables.id_to_token(id)
+    }
+
+    /// Get the id of the given token, from the tables of the model
+    pub fn token_to_id(&self, token: &str) -> Option<u32> {
+        self.tables.token_to_id(token)
+    }
+
+    /// The merges of the model, as pairs of ids in their order of priority
+    pub fn merges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.tables.merges().map(|(a, b, _)| (a, b))
+    }
+
+    /// Get the score of the token with the given id, if the model has scores
+    pub fn score(&self, id: u32) -> Option<f64> {
+        self.tables.score(id)
+    }
+
+    fn bpe(&self) -> Result<ModelWrapper> {
+        let config = self.tables.bytes(&self.tables.config);
+        let options = serde_json::from_slice::<ModelField<BpeOptions>>(config)?.model;
+        let mut builder = BPE::builder()
+            .fuse_unk(options.fuse_unk)
+            .byte_fallback(options.byte_fallback)
+            .mapped_tables(self.tables.clone());
+        if let Some(dropout) = options.dropout {
+            builder = builder.dropout(dropout);
+        }
+        if let Some(unk_token) = options.unk_token {
+            builder = builder.unk_token(unk_token);
+        }
+        if let Some(prefix) = options.continuing_subword_prefix {
+            builder = builder.continuing_subword_prefix(prefix);
+        }
+        if let Some(suffix) = options.end_of_word_suffix {
+            builder = builder.end_of_word_suffix(suffix);
+        }
+        if let Some(capacity) = options.cache_capacity {
+            builder = builder.cache_capacity(capacity);
+        }
+        Ok(builder.build()?.into())
+    }
+
+    fn unigram(&self) -> Result<ModelWrapper> {
+        let config = self.tables.bytes(&self.tables.config);
+        let options = serde_json::from_slice::<ModelField<UnigramOptions>>(config)?.model;
+        let mut unigram = Unigram::from_tables(self.tables.clone(), options.unk_id)?;
+        unigram.set_byte_fallback(options.byte_fallback);
+        unigram.set_sampling(options.sampling);
+        if let Some(capacity) = options.cache_capacity {
+            unigram.set_cache_capacity(capacity);
+        }
+        Ok(unigram.into())
+    }
+
+    /// Build the `Tokenizer`. Its model uses the tables in place, without copying them.
+    pub fn into_tokenizer(self) -> Result<Tokenizer> {
+        let model = match self.tables.kind {
+            BPE_TABLES => Some(self.bpe()?),
+            UNIGRAM_TABLES => Some(self.unigram()?),
+            // The model was kept in the tokenizer.json
+            _ => None,
+        };
+        let config = self.tables.bytes(&self.tables.config);
+        let mut deserializer = serde_json::Deserializer::from_slice(config);
+        let tokenizer = deserialize_tokenizer(&mut deserializer, model)?;
+        deserializer.end()?;
+        Ok(Tokenizer(tokenizer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bpe::BpeBuilder;
+    use crate::models::wordpiece::WordPiece;
+    use crate::normalizers::Lowercase;
+    use crate::pre_tokenizers::whitespace::Whitespace;
+    use crate::{AddedToken, Model};
+
+    fn bpe_tokenizer() -> Tokenizer {
+        let vocab = [("<unk>", 0), ("##a", 1), ("b", 2), ("##ab", 3), ("c", 4)]
+            .iter()
+            .map(|(token, id)| (token.to_string(), *id))
+            .collect();
+        let merges = vec![("##a".to_string(), "b".to_string())];
+        let bpe = BpeBuilder::default()
+            .vocab_and_merges(vocab, merges)
+            .unk_token("<unk>".into())
+            .continuing_subword_prefix("##".into())
+            .build()
+            .unwrap();
+        let mut tokenizer = Tokenizer::new(bpe);
+        tokenizer.with_normalizer(Lowercase);
+        tokenizer.with_pre_tokenizer(Whitespace::default());
+        tokenizer.add_special_tokens(&[AddedToken::from("[CLS]", true)]);
+        tokenizer
+    }
+
+    #[test]
+    fn bpe_round_trip() {
+        let tokenizer = bpe_tokenizer();
+        let binary = tokenizer.to_binary().unwrap();
+
+        let view = BinaryTokenizer::from_bytes(binary.clone()).unwrap();
+        assert_eq!(view.vocab_size(), 5);
+        assert_eq!(view.token_to_id("##ab"), Some(3));
+        assert_eq!(view.token_to_id("d"), None);
+        assert_eq!(view.id_to_token(4), Some("c"));
+        assert_eq!(view.id_to_token(5), None);
+        assert_eq!(view.merges().collect::<Vec<_>>(), vec![(1, 2)]);
+        assert_eq!(view.tables.merge((1, 2)), Some((0, 3)));
+        assert_eq!(view.tables.merge((2, 1)), None);
+        assert_eq!(view.score(0), None);
+
+        let loaded = Tokenizer::from_binary(binary).unwrap();
+        assert_eq!(
+            loaded.to_string(false).unwrap(),
+            tokenizer.to_string(false).unwrap()
+        );
+        let encoding = loaded.encode("[CLS] AB d", false).unwrap();
+        assert_eq!(encoding.get_tokens(), &["[CLS]", "##ab", "<unk>"]);
+
+        // The model uses the tables, and only copies them out to be modified
+        let mut bpe = match loaded.get_model() {
+            ModelWrapper::BPE(bpe) => bpe.clone(),
+            _ => unreachable!(),
+        };
+        assert!(bpe.vocab.is_empty() && bpe.merges.is_empty());
+        assert_eq!(loaded.get_model(), tokenizer.get_model());
+        assert_eq!(bpe.get_vocab(), tokenizer.get_model().get_vocab());
+        bpe.prune(&[4].iter().copied().collect()).unwrap();
+        assert_eq!(bpe.vocab.len(), 4);
+        assert_eq!(bpe.merges.len(), 1);
+    }
+
+    #[test]
+    fn unigram_round_trip() {
+        let vocab = vec![
+            ("<unk>".to_string(), 0.0),
+            ("a".to_string(), -1.0),
+            ("b".to_string(), -2.0),
+            ("ab".to_string(), -0.5),
+        ];
+        let tokenizer = Tokenizer::new(Unigram::from(vocab, 0).unwrap());
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        tokenizer.save_binary(file.path()).unwrap();
+        let view = BinaryTokenizer::from_file(file.path()).unwrap();
+        assert_eq!(view.token_to_id("ab"), Some(3));
+        assert_eq!(view.score(3), Some(-0.5));
+        assert_eq!(view.merges().count(), 0);
+        let prefixes = view.tables.common_prefix_search(b"abc").collect::<Vec<_>>();
+        assert_eq!(prefixes, vec![(1, 1), (2, 3)]);
+        assert_eq!(view.tables.common_prefix_search(b"ba").count(), 1);
+
+        let loaded = Tokenizer::from_binary_file(file.path()).unwrap();
+        assert_eq!(
+            loaded.to_string(false).unwrap(),
+            tokenizer.to_string(false).unwrap()
+        );
+        let encoding = loaded.encode("abc", false).unwrap();
+        assert_eq!(encoding.get_ids(), &[3, 0]);
+
+        let unigram = match loaded.get_model() {
+            ModelWrapper::Unigram(unigram) => unigram,
+            _ => unreachable!(),
+        };
+        assert!(unigram.vocab.is_empty());
+        assert_eq!(loaded.get_model(), tokenizer.get_model());
+        assert_eq!(unigram.get_vocab(), tokenizer.get_model().get_vocab());
+    }
+
+    #[test]
+    fn duplicate_tokens() {
+        // The last id of a duplicate token is used, as in the maps of the model
+        let vocab = vec![
+            ("<unk>".to_string(), 0.0),
+            ("a".to_string(), -1.0),
+            ("a".to_string(), -2.0),
+        ];
+        let tokenizer = Tokenizer::new(Unigram::from(vocab, 0).unwrap());
+        let loaded = Tokenizer::from_binary(tokenizer.to_binary().unwrap()).unwrap();
+        assert_eq!(tokenizer.token_to_id("a"), Some(2));
+        assert_eq!(loaded.token_to_id("a"), Some(2));
+        assert_eq!(loaded.encode("a", false).unwrap().get_ids(), &[2]);
+        assert_eq!(loaded.get_vocab_size(false), 3);
+    }
+
+    #[test]
+    fn other_models_stay_in_json() {
+        let tokenizer = Tokenizer::new(WordPiece::default());
+        let view = BinaryTokenizer::from_bytes(tokenizer.to_binary().unwrap()).unwrap();
+        assert_eq!(view.vocab_size(), 0);
+        let loaded = view.into_tokenizer().unwrap();
+        assert_eq!(
+            loaded.to_string(false).unwrap(),
+            tokenizer.to_string(false).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_files() {
+        let err = Tokenizer::from_binary(b"{}".to_vec()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid binary tokenizer: wrong magic number"
+        );
+
+        let mut binary = bpe_tokenizer().to_binary().unwrap();
+        binary.truncate(binary.len() - 1);
+        let err = Tokenizer::from_binary(binary).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid binary tokenizer: the file is truncated"
+        );
+
+        // The end of the first token, past the string table
+        let mut binary = bpe_tokenizer().to_binary().unwrap();
+        let config_len = read_u32(&binary[16..20], 0) as usize;
+        let offset = 20 + config_len + 8;
+        binary[offset..offset + 4].copy_from_slice(&1000u32.to_le_bytes());
+        let err = Tokenizer::from_binary(binary).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid binary tokenizer: the tables are corrupted"
+        );
+    }
+}
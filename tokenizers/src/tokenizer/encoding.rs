@@ -1,7 +1,10 @@
 use crate::parallelism::*;
 use crate::tokenizer::{Offsets, Token};
 use crate::utils::padding::PaddingDirection;
+use crate::utils::truncation::TruncationDirection;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
 
 /// Represents the output of a `Tokenizer`.
 #[derive(Default, PartialEq, Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +25,9 @@ pub struct Encoding {
     attention_mask: Vec<u32>,
     /// A list of overflowing Encoding generated when we got truncated
     overflowing: Vec<Encoding>,
+    /// Ranges of tokens covered by each input sequence, indexed by the sequence index
+    #[serde(default)]
+    sequence_ranges: HashMap<usize, Range<usize>>,
 }
 impl Encoding {
     #[allow(clippy::too_many_arguments)]
@@ -44,6 +50,7 @@ impl Encoding {
             special_tokens_mask,
             attention_mask,
             overflowing,
+            sequence_ranges: HashMap::new(),
         }
     }
 
@@ -57,6 +64,7 @@ impl Encoding {
             special_tokens_mask: Vec::with_capacity(len),
             attention_mask: Vec::with_capacity(len),
             overflowing: vec![],
+            sequence_ranges: HashMap::new(),
         }
     }
 
@@ -85,6 +93,7 @@ impl Encoding {
             attention_mask: vec![1; length],
             special_tokens_mask: vec![0; length],
             overflowing: vec![],
+            sequence_ranges: HashMap::new(),
         }
     }
 
@@ -96,6 +105,73 @@ impl Encoding {
         self.ids.len()
     }
 
+    /// Return the number of input sequences that were merged in this `Encoding`
+    pub fn n_sequences(&self) -> usize {
+        if self.sequence_ranges.is_empty() {
+            1
+        } else {
+            self.sequence_ranges.len()
+        }
+    }
+
+    /// Mark all the tokens of this `Encoding`, and of its overflowing parts, as part of the
+    /// input sequence at the given index
+    pub fn set_sequence_id(&mut self, sequence_id: usize) {
+        self.sequence_ranges.clear();
+        self.sequence_ranges.insert(sequence_id, 0..self.len());
+        for overflowing in self.overflowing.iter_mut() {
+            overflowing.set_sequence_id(sequence_id);
+        }
+    }
+
+    /// Get the index of the input sequence of each token, or `None` for the tokens that
+    /// were added to the input sequences, like special tokens and padding.
+    pub fn sequence_ids(&self) -> Vec<Option<usize>> {
+        if self.sequence_ranges.is_empty() {
+            return vec![Some(0); self.len()];
+        }
+
+        let mut sequences = vec![None; self.len()];
+        for (sequence_id, range) in &self.sequence_ranges {
+            for sequence in &mut sequences[range.clone()] {
+                *sequence = Some(*sequence_id);
+            }
+        }
+        sequences
+    }
+
+    /// Get the range of tokens covered by the input sequence at the given index
+    fn sequence_range(&self, sequence_id: usize) -> Option<Range<usize>> {
+        if self.sequence_ranges.is_empty() {
+            if sequence_id == 0 {
+                Some(0..self.len())
+            } else {
+                None
+            }
+        } else {
+            self.sequence_ranges.get(&sequence_id).cloned()
+        }
+    }
+
+    /// Set the range of tokens covered by the input sequence at the given index
+    pub(crate) fn set_sequence_range(&mut self, sequence_id: usize, range: Range<usize>) {
+        self.sequence_ranges.insert(sequence_id, range);
+    }
+
+    /// Get the index of the input sequence that contains the token at the given index.
+    pub fn token_to_sequence(&self, token: usize) -> Option<usize> {
+        if token >= self.len() {
+            None
+        } else if self.sequence_ranges.is_empty() {
+            Some(0)
+        } else {
+            self.sequence_ranges
+                .iter()
+                .find(|(_, range)| range.contains(&token))
+                .map(|(sequence_id, _)| *sequence_id)
+        }
+    }
+
     pub fn get_tokens(&self) -> &[String] {
         &self.tokens[..]
     }
@@ -158,13 +234,29 @@ impl Encoding {
     /// Get the encoded tokens corresponding to the word at the given index in the input sequence,
     /// with the form (start_token, end_token + 1)
     pub fn word_to_tokens(&self, word: u32) -> Option<(usize, usize)> {
+        self.word_to_tokens_in_range(word, 0..self.words.len())
+    }
+
+    /// Get the encoded tokens corresponding to the word at the given index in the input sequence
+    /// at the given index, with the form (start_token, end_token + 1)
+    pub fn word_to_tokens_in_sequence(
+        &self,
+        word: u32,
+        sequence_id: usize,
+    ) -> Option<(usize, usize)> {
+        self.word_to_tokens_in_range(word, self.sequence_range(sequence_id)?)
+    }
+
+    fn word_to_tokens_in_range(&self, word: u32, range: Range<usize>) -> Option<(usize, usize)> {
         let (mut start, mut end) = (None, None);
-        self.words
+        let offset = range.start;
+        self.words[range]
             .iter()
             .enumerate()
             .take_while(|(_, w)| **w <= Some(word))
             .filter(|(_, w)| **w == Some(word))
             .for_each(|(i, _)| {
+                let i = i + offset;
                 if start.is_none() || Some(i) < start {
                     start = Some(i);
                 }
@@ -182,15 +274,22 @@ impl Encoding {
 
     /// Get the offsets of the word at the given index in the input sequence.
     pub fn word_to_chars(&self, word: u32) -> Option<Offsets> {
-        self.word_to_tokens(word)
-            .map(|(start, end)| {
-                if end == 0 {
-                    None
-                } else {
-                    Some((self.offsets[start].0, self.offsets[end - 1].1))
-                }
-            })
-            .flatten()
+        self.tokens_to_chars(self.word_to_tokens(word))
+    }
+
+    /// Get the offsets of the word at the given index in the input sequence at the given index.
+    pub fn word_to_chars_in_sequence(&self, word: u32, sequence_id: usize) -> Option<Offsets> {
+        self.tokens_to_chars(self.word_to_tokens_in_sequence(word, sequence_id))
+    }
+
+    fn tokens_to_chars(&self, tokens: Option<(usize, usize)>) -> Option<Offsets> {
+        tokens.and_then(|(start, end)| {
+            if end == 0 {
+                None
+            } else {
+                Some((self.offsets[start].0, self.offsets[end - 1].1))
+            }
+        })
     }
 
     /// Get the offsets of the token at the given index.
@@ -210,6 +309,16 @@ impl Encoding {
             .position(|(start, end)| pos >= *start && pos < *end)
     }
 
+    /// Get the token that contains the given char, in the input sequence at the given index.
+    pub fn char_to_token_in_sequence(&self, pos: usize, sequence_id: usize) -> Option<usize> {
+        let range = self.sequence_range(sequence_id)?;
+        let offset = range.start;
+        self.offsets[range]
+            .iter()
+            .position(|(start, end)| pos >= *start && pos < *end)
+            .map(|i| i + offset)
+    }
+
     /// Get the word that contains the given char.
     pub fn char_to_word(&self, pos: usize) -> Option<u32> {
         self.char_to_token(pos)
@@ -217,11 +326,22 @@ impl Encoding {
             .flatten()
     }
 
-    /// Truncate the current `Encoding`.
+    /// Get the word that contains the given char, in the input sequence at the given index.
+    pub fn char_to_word_in_sequence(&self, pos: usize, sequence_id: usize) -> Option<u32> {
+        self.char_to_token_in_sequence(pos, sequence_id)
+            .and_then(|token| self.token_to_word(token))
+    }
+
+    /// Truncate the current `Encoding`, removing tokens from the given direction.
+    ///
+    /// The removed tokens are kept in the overflowing `Encoding`s, each of them having at most
+    /// `max_len` tokens, and sharing `stride` tokens with the previous one. The overflowing parts
+    /// are ordered from the closest to the furthest of the kept part.
     ///
     /// Panic if `stride >= max_len`
-    pub fn truncate(&mut self, max_len: usize, stride: usize) {
-        if max_len >= self.ids.len() {
+    pub fn truncate(&mut self, max_len: usize, stride: usize, direction: TruncationDirection) {
+        let encoding_len = self.ids.len();
+        if max_len >= encoding_len {
             return;
         }
 
@@ -231,74 +351,62 @@ impl Encoding {
             return;
         }
 
-        // Get the main overflowing part
-        let o_ids = self.ids.split_off(max_len);
-        let o_type_ids = self.type_ids.split_off(max_len);
-        let o_tokens = self.tokens.split_off(max_len);
-        let o_words = self.words.split_off(max_len);
-        let o_offsets = self.offsets.split_off(max_len);
-        let o_spe_toks = self.special_tokens_mask.split_off(max_len);
-        let o_attent = self.attention_mask.split_off(max_len);
-
-        // Now we need to separate the overflowing part into as many Encoding as needed
         assert!(stride < max_len);
         let part_size = max_len - stride;
-        let mut overflowing = vec![];
-        let mut part_id = 0;
-        let mut prev_encoding: &Encoding = self;
 
-        loop {
-            if part_size * part_id >= o_ids.len() {
-                break;
+        // Compute the ranges of each part, starting with the one we keep
+        let mut parts_ranges = vec![];
+        match direction {
+            TruncationDirection::Right => {
+                let mut start = 0;
+                loop {
+                    let stop = (start + max_len).min(encoding_len);
+                    parts_ranges.push(start..stop);
+                    if stop == encoding_len {
+                        break;
+                    }
+                    start += part_size;
+                }
+            }
+            TruncationDirection::Left => {
+                let mut stop = encoding_len;
+                loop {
+                    let start = stop.saturating_sub(max_len);
+                    parts_ranges.push(start..stop);
+                    if start == 0 {
+                        break;
+                    }
+                    stop -= part_size;
+                }
             }
-
-            let o = Encoding {
-                ids: get_current_part(&prev_encoding.ids, &o_ids, part_size, part_id, stride),
-                type_ids: get_current_part(
-                    &prev_encoding.type_ids,
-                    &o_type_ids,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                tokens: get_current_part(
-                    &prev_encoding.tokens,
-                    &o_tokens,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                words: get_current_part(&prev_encoding.words, &o_words, part_size, part_id, stride),
-                offsets: get_current_part(
-                    &prev_encoding.offsets,
-                    &o_offsets,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                special_tokens_mask: get_current_part(
-                    &prev_encoding.special_tokens_mask,
-                    &o_spe_toks,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                attention_mask: get_current_part(
-                    &prev_encoding.attention_mask,
-                    &o_attent,
-                    part_size,
-                    part_id,
-                    stride,
-                ),
-                overflowing: vec![],
-            };
-
-            part_id += 1;
-            overflowing.push(o);
-            prev_encoding = &overflowing.last().unwrap();
         }
 
-        self.overflowing = overflowing;
+        let mut parts = parts_ranges.into_iter().map(|range| Encoding {
+            ids: self.ids[range.clone()].to_vec(),
+            type_ids: self.type_ids[range.clone()].to_vec(),
+            tokens: self.tokens[range.clone()].to_vec(),
+            words: self.words[range.clone()].to_vec(),
+            offsets: self.offsets[range.clone()].to_vec(),
+            special_tokens_mask: self.special_tokens_mask[range.clone()].to_vec(),
+            attention_mask: self.attention_mask[range.clone()].to_vec(),
+            overflowing: vec![],
+            sequence_ranges: self
+                .sequence_ranges
+                .iter()
+                .filter_map(|(sequence_id, seq_range)| {
+                    let start = seq_range.start.max(range.start);
+                    let end = seq_range.end.min(range.end);
+                    if start < end {
+                        Some((*sequence_id, start - range.start..end - range.start))
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+        });
+        let mut encoding = parts.next().unwrap();
+        encoding.overflowing = parts.collect();
+        *self = encoding;
     }
 
     /// Merge all Encodings together
@@ -340,6 +448,18 @@ impl Encoding {
         }
 
         // Finish by merging ourself with the other encoding
+        let original_len = self.len();
+        self.sequence_ranges
+            .extend(
+                pair.sequence_ranges
+                    .into_iter()
+                    .map(|(sequence_id, range)| {
+                        (
+                            sequence_id,
+                            original_len + range.start..original_len + range.end,
+                        )
+                    }),
+            );
         self.ids.extend(pair.ids);
         self.type_ids.extend(pair.type_ids);
         self.tokens.extend(pair.tokens);
@@ -381,8 +501,16 @@ impl Encoding {
         }
         let pad_length = target_length - self.ids.len();
 
+        // Make sure the padding tokens are not part of any sequence
+        if self.sequence_ranges.is_empty() {
+            self.sequence_ranges.insert(0, 0..self.len());
+        }
+
         match direction {
             PaddingDirection::Left => {
+                for range in self.sequence_ranges.values_mut() {
+                    *range = range.start + pad_length..range.end + pad_length;
+                }
                 self.ids = (0..pad_length)
                     .map(|_| pad_id)
                     .chain(self.ids.drain(..))
@@ -455,23 +583,6 @@ impl std::iter::FromIterator<(u32, String, (usize, usize), Option<u32>, u32)> fo
     }
 }
 
-#[inline]
-fn get_current_part<T: Clone>(
-    prev: &[T],
-    current: &[T],
-    size: usize,
-    idx: usize,
-    stride: usize,
-) -> Vec<T> {
-    let curr_slice = if (idx + 1) * size > current.len() {
-        &current[idx * size..]
-    } else {
-        &current[idx * size..(idx + 1) * size]
-    };
-    let prev_slice = &prev[prev.len() - stride..];
-    [prev_slice, curr_slice].concat()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -487,6 +598,7 @@ mod tests {
             special_tokens_mask: vec![0],
             attention_mask: vec![1],
             overflowing: vec![],
+            ..Default::default()
         };
         let b = Encoding {
             ids: vec![2],
@@ -497,6 +609,7 @@ mod tests {
             special_tokens_mask: vec![0],
             attention_mask: vec![1],
             overflowing: vec![],
+            ..Default::default()
         };
         a.merge_with(b, true);
 
@@ -511,6 +624,7 @@ mod tests {
                 special_tokens_mask: vec![0, 0],
                 attention_mask: vec![1, 1],
                 overflowing: vec![],
+                ..Default::default()
             }
         );
     }
@@ -530,8 +644,9 @@ mod tests {
             special_tokens_mask: vec![0, 0, 0],
             attention_mask: vec![1, 1, 1],
             overflowing: vec![],
+            ..Default::default()
         };
-        a.truncate(2, 0);
+        a.truncate(2, 0, TruncationDirection::Right);
 
         assert_eq!(
             a,
@@ -552,7 +667,9 @@ mod tests {
                     special_tokens_mask: vec![0],
                     attention_mask: vec![1],
                     overflowing: vec![],
-                }]
+                    ..Default::default()
+                }],
+                ..Default::default()
             }
         );
     }
@@ -572,8 +689,9 @@ mod tests {
             special_tokens_mask: vec![0, 0, 0],
             attention_mask: vec![1, 1, 1],
             overflowing: vec![],
+            ..Default::default()
         };
-        a.truncate(0, 0);
+        a.truncate(0, 0, TruncationDirection::Right);
 
         assert_eq!(
             a,
@@ -598,11 +716,132 @@ mod tests {
                     special_tokens_mask: vec![0, 0, 0],
                     attention_mask: vec![1, 1, 1],
                     overflowing: vec![],
-                }]
+                    ..Default::default()
+                }],
+                ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn truncate_with_stride() {
+        let encoding = Encoding {
+            ids: vec![1, 2, 3, 4, 5, 6],
+            type_ids: vec![0; 6],
+            tokens: vec![
+                "a".into(),
+                "b".into(),
+                "c".into(),
+                "d".into(),
+                "e".into(),
+                "f".into(),
+            ],
+            words: vec![Some(0), Some(1), Some(2), Some(3), Some(4), Some(5)],
+            offsets: vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6)],
+            special_tokens_mask: vec![0; 6],
+            attention_mask: vec![1; 6],
+            overflowing: vec![],
+            ..Default::default()
+        };
+        let windows = |e: &Encoding| {
+            std::iter::once(e)
+                .chain(e.get_overflowing())
+                .map(|o| o.get_ids().to_vec())
+                .collect::<Vec<_>>()
+        };
+
+        let mut right = encoding.clone();
+        right.truncate(3, 1, TruncationDirection::Right);
+        assert_eq!(
+            windows(&right),
+            vec![vec![1, 2, 3], vec![3, 4, 5], vec![5, 6]]
+        );
+
+        let mut left = encoding;
+        left.truncate(3, 1, TruncationDirection::Left);
+        assert_eq!(
+            windows(&left),
+            vec![vec![4, 5, 6], vec![2, 3, 4], vec![1, 2]]
+        );
+        assert_eq!(left.get_offsets(), &[(3, 4), (4, 5), (5, 6)]);
+        assert_eq!(left.get_words(), &[Some(3), Some(4), Some(5)]);
+        assert_eq!(left.get_overflowing()[1].get_tokens(), &["a", "b"]);
+        assert_eq!(left.get_overflowing()[1].get_offsets(), &[(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn sequence_ranges() {
+        let mut a = Encoding::from_tokens(
+            vec![
+                Token::new(1, "Hello".into(), (0, 5)),
+                Token::new(2, "World".into(), (6, 11)),
+            ],
+            0,
+        );
+        a.set_sequence_id(0);
+        let mut b = Encoding::from_tokens(vec![Token::new(3, "pair".into(), (0, 4))], 1);
+        b.set_sequence_id(1);
+        assert_eq!(a.token_to_sequence(1), Some(0));
+        assert_eq!(a.token_to_sequence(2), None);
+
+        a.merge_with(b, false);
+        assert_eq!(a.n_sequences(), 2);
+        assert_eq!(
+            (0..a.len())
+                .map(|i| a.token_to_sequence(i))
+                .collect::<Vec<_>>(),
+            vec![Some(0), Some(0), Some(1)]
+        );
+
+        a.truncate(2, 1, TruncationDirection::Right);
+        assert_eq!(a.n_sequences(), 1);
+        let overflowing = &a.get_overflowing()[0];
+        assert_eq!(overflowing.token_to_sequence(0), Some(0));
+        assert_eq!(overflowing.token_to_sequence(1), Some(1));
+
+        a.pad(4, 0, 0, "[PAD]", PaddingDirection::Left);
+        assert_eq!(a.token_to_sequence(1), None);
+        assert_eq!(a.token_to_sequence(2), Some(0));
+    }
+
+    #[test]
+    fn sequence_mappings() {
+        let mut a = Encoding::from_tokens(
+            vec![
+                Token::new(1, "Hello".into(), (0, 5)),
+                Token::new(2, "World".into(), (6, 11)),
+            ],
+            0,
+        );
+        a.words = vec![Some(0), Some(1)];
+        a.set_sequence_id(0);
+        let mut b = Encoding::from_tokens(
+            vec![
+                Token::new(3, "Hey".into(), (0, 3)),
+                Token::new(4, "there".into(), (4, 9)),
+            ],
+            1,
+        );
+        b.words = vec![Some(0), Some(1)];
+        b.set_sequence_id(1);
+        a.merge_with(b, false);
+        a.pad(5, 0, 0, "[PAD]", PaddingDirection::Right);
+
+        assert_eq!(
+            a.sequence_ids(),
+            vec![Some(0), Some(0), Some(1), Some(1), None]
+        );
+        assert_eq!(a.char_to_token(7), Some(1));
+        assert_eq!(a.char_to_token_in_sequence(7, 0), Some(1));
+        assert_eq!(a.char_to_token_in_sequence(7, 1), Some(3));
+        assert_eq!(a.char_to_token_in_sequence(7, 2), None);
+        assert_eq!(a.char_to_word_in_sequence(1, 1), Some(0));
+        assert_eq!(a.word_to_tokens_in_sequence(1, 0), Some((1, 2)));
+        assert_eq!(a.word_to_tokens_in_sequence(1, 1), Some((3, 4)));
+        assert_eq!(a.word_to_chars_in_sequence(1, 1), Some((4, 9)));
+        assert_eq!(a.word_to_chars_in_sequence(2, 1), None);
+    }
+
     #[test]
     fn mappings() {
         let encoding = Encoding {
@@ -10,7 +10,7 @@
 //!   ...).
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     fs::{read_to_string, File},
     io::prelude::*,
@@ -32,19 +32,32 @@ use crate::processors::PostProcessorWrapper;
 use crate::utils::parallelism::*;
 
 mod added_vocabulary;
+mod alignment;
+mod binary;
 mod encoding;
 pub mod normalizer;
 pub mod pattern;
 pub mod pre_tokenizer;
+mod prune;
+mod round_trip;
+pub mod sentencepiece;
 mod serialization;
 
 pub use crate::utils::iter::LinesWithEnding;
 pub use crate::utils::padding::{pad_encodings, PaddingDirection, PaddingParams, PaddingStrategy};
-pub use crate::utils::truncation::{truncate_encodings, TruncationParams, TruncationStrategy};
+pub use crate::utils::truncation::{
+    truncate_encodings, truncate_sequences, TruncationDirection, TruncationParams,
+    TruncationStrategy,
+};
 pub use added_vocabulary::*;
+pub use alignment::*;
+pub use binary::BinaryTokenizer;
+pub(crate) use binary::MappedTables;
 pub use encoding::*;
 pub use normalizer::{NormalizedString, OffsetReferential, SplitDelimiterBehavior};
 pub use pre_tokenizer::*;
+pub use prune::*;
+pub use round_trip::*;
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -77,6 +90,16 @@ pub trait Model {
     fn get_vocab(&self) -> &HashMap<String, u32>;
     /// Retrieve the size of the vocabulary
     fn get_vocab_size(&self) -> usize;
+    /// Retrieve the token used for the unknown pieces, if any
+    fn unk_token(&self) -> Option<&str> {
+        None
+    }
+    /// Remove the given ids from the vocabulary, along with the tokens that can't be produced
+    /// anymore, and re-number the remaining ones so that their ids stay contiguous. Returns the
+    /// mapping from the old ids to the new ones, for the tokens that were kept.
+    fn prune(&mut self, _ids: &HashSet<u32>) -> Result<HashMap<u32, u32>> {
+        Err("This model does not support pruning".into())
+    }
     /// Save the current `Model` in the given folder, using the given `prefix` for the various
     /// files that need to be saved.
     fn save(&self, folder: &Path, prefix: Option<&str>) -> Result<Vec<PathBuf>>;
@@ -87,6 +110,11 @@ pub trait Model {
 pub trait PostProcessor {
     /// Returns the number of tokens that will be added during the processing step
     fn added_tokens(&self, is_pair: bool) -> usize;
+    /// Returns the number of tokens that will be added when processing the given number
+    /// of sequences
+    fn added_tokens_for_sequences(&self, n_sequences: usize) -> usize {
+        self.added_tokens(n_sequences > 1)
+    }
     /// Process both encodings and returns a new merged one
     fn process(
         &self,
@@ -94,6 +122,22 @@ pub trait PostProcessor {
         pair_encoding: Option<Encoding>,
         add_special_tokens: bool,
     ) -> Result<Encoding>;
+    /// Process any number of encodings and returns a new merged one. By default, only
+    /// a single sequence or a pair of sequences are supported.
+    fn process_sequences(
+        &self,
+        mut encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        match encodings.len() {
+            1 => self.process(encodings.pop().unwrap(), None, add_special_tokens),
+            2 => {
+                let pair_encoding = encodings.pop();
+                self.process(encodings.pop().unwrap(), pair_encoding, add_special_tokens)
+            }
+            n => Err(format!("This PostProcessor cannot process {} sequences", n).into()),
+        }
+    }
 }
 impl dyn PostProcessor {
     pub fn default_process(
@@ -109,11 +153,28 @@ impl dyn PostProcessor {
             }
         }
     }
+
+    /// Merge any number of encodings, without adding anything
+    pub fn default_process_sequences(
+        encodings: Vec<Encoding>,
+        _add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        Ok(Encoding::merge(encodings, false))
+    }
 }
 
 /// A `Decoder` has the responsibility to merge the given `Vec<String>` in a `String`.
+///
+/// Each `Decoder` works on the list of tokens with `decode_chain`, so that several of them can
+/// be chained, and `decode` simply joins the resulting tokens.
 pub trait Decoder {
-    fn decode(&self, tokens: Vec<String>) -> Result<String>;
+    fn decode(&self, tokens: Vec<String>) -> Result<String> {
+        let tokens = self.decode_chain(tokens)?;
+        Ok(tokens.join(""))
+    }
+
+    /// Process the given tokens, and return the tokens to be given to the next `Decoder`
+    fn decode_chain(&self, tokens: Vec<String>) -> Result<Vec<String>>;
 }
 
 /// A `Trainer` has the responsibility to train a model. We feed it with lines/sentences
@@ -211,6 +272,18 @@ impl<'s> From<&'s [Cow<'s, str>]> for InputSequence<'s> {
 pub enum EncodeInput<'s> {
     Single(InputSequence<'s>),
     Dual(InputSequence<'s>, InputSequence<'s>),
+    Multiple(Vec<InputSequence<'s>>),
+}
+
+impl<'s> EncodeInput<'s> {
+    /// Extract all the sequences, in order
+    fn into_sequences(self) -> Vec<InputSequence<'s>> {
+        match self {
+            EncodeInput::Single(s1) => vec![s1],
+            EncodeInput::Dual(s1, s2) => vec![s1, s2],
+            EncodeInput::Multiple(sequences) => sequences,
+        }
+    }
 }
 
 impl<'s, I: Into<InputSequence<'s>>> From<I> for EncodeInput<'s> {
@@ -229,6 +302,23 @@ where
     }
 }
 
+impl<'s, I1, I2, I3> From<(I1, I2, I3)> for EncodeInput<'s>
+where
+    I1: Into<InputSequence<'s>>,
+    I2: Into<InputSequence<'s>>,
+    I3: Into<InputSequence<'s>>,
+{
+    fn from(input: (I1, I2, I3)) -> Self {
+        EncodeInput::Multiple(vec![input.0.into(), input.1.into(), input.2.into()])
+    }
+}
+
+impl<'s> From<Vec<InputSequence<'s>>> for EncodeInput<'s> {
+    fn from(input: Vec<InputSequence<'s>>) -> Self {
+        EncodeInput::Multiple(input)
+    }
+}
+
 #[derive(Debug)]
 pub struct BuilderError(String);
 
@@ -387,6 +477,24 @@ impl Tokenizer {
         let content = read_to_string(file)?;
         Ok(serde_json::from_str(&content)?)
     }
+
+    /// Load a tokenizer saved with `save_binary`. The file is memory-mapped, and the model
+    /// looks its vocabulary, merges and trie up in place, see
+    /// [`BinaryTokenizer`](struct.BinaryTokenizer.html).
+    pub fn from_binary_file<P: AsRef<Path>>(file: P) -> Result<Self> {
+        BinaryTokenizer::from_file(file)?.into_tokenizer()
+    }
+
+    /// Load a tokenizer serialized with `to_binary`
+    pub fn from_binary(bytes: Vec<u8>) -> Result<Self> {
+        BinaryTokenizer::from_bytes(bytes)?.into_tokenizer()
+    }
+
+    /// Build the whole pipeline described by a SentencePiece `.model` file. Like SentencePiece,
+    /// the beginning and end of sentence pieces are not added to the encodings.
+    pub fn from_sentencepiece_file<P: AsRef<Path>>(file: P) -> Result<Self> {
+        sentencepiece::SentencePieceModel::from_file(file)?.into_tokenizer()
+    }
 }
 
 impl<M, N, PT, PP, D> From<TokenizerImpl<M, N, PT, PP, D>> for Tokenizer
@@ -602,6 +710,7 @@ where
         &self,
         sequence: InputSequence,
         type_id: u32,
+        offset_ref: OffsetReferential,
         offsets_type: OffsetType,
     ) -> Result<Encoding> {
         let encode = |is_pre_tokenized, subseq_idx, subseq| -> Result<Encoding> {
@@ -617,6 +726,7 @@ where
                 } else {
                     None
                 },
+                offset_ref,
                 offsets_type,
             )?;
 
@@ -664,26 +774,20 @@ where
     ///
     /// // or even both types together:
     /// tokenizer.encode(("A complete sequence", &["And", "a", "tokenized"][..]), false);
+    ///
+    /// // More than two sequences:
+    /// tokenizer.encode(("The query", "A passage", "Its title"), false);
     /// ```
     pub fn encode<'s, E>(&self, input: E, add_special_tokens: bool) -> Result<Encoding>
     where
         E: Into<EncodeInput<'s>>,
     {
-        // Extract sequences from the EncodeInput
-        let (sequence, pair) = match input.into() {
-            EncodeInput::Single(s1) => (s1, None),
-            EncodeInput::Dual(s1, s2) => (s1, Some(s2)),
-        };
-
-        // Encode each sequence
-        let encoding = self.encode_single_sequence(sequence, 0, OffsetType::Byte)?;
-        let pair_encoding = match pair {
-            Some(sequence) => Some(self.encode_single_sequence(sequence, 1, OffsetType::Byte)?),
-            None => None,
-        };
-
-        // And finally post process
-        self.post_process(encoding, pair_encoding, add_special_tokens)
+        self.encode_with_offsets(
+            input,
+            add_special_tokens,
+            OffsetReferential::Original,
+            OffsetType::Byte,
+        )
     }
 
     /// Encode the given input, using offsets relative to chars instead of bytes.
@@ -713,21 +817,61 @@ where
     where
         E: Into<EncodeInput<'s>>,
     {
-        // Extract sequences from the EncodeInput
-        let (sequence, pair) = match input.into() {
-            EncodeInput::Single(s1) => (s1, None),
-            EncodeInput::Dual(s1, s2) => (s1, Some(s2)),
-        };
+        self.encode_with_offsets(
+            input,
+            add_special_tokens,
+            OffsetReferential::Original,
+            OffsetType::Char,
+        )
+    }
 
-        // Encode each sequence
-        let encoding = self.encode_single_sequence(sequence, 0, OffsetType::Char)?;
-        let pair_encoding = match pair {
-            Some(sequence) => Some(self.encode_single_sequence(sequence, 1, OffsetType::Char)?),
-            None => None,
-        };
+    /// Encode the given input, with offsets relative to the given referential and expressed in
+    /// the given unit. The offsets of the overflowing encodings are expressed the same way.
+    ///
+    /// ```
+    /// # use tokenizers::{OffsetReferential, OffsetType, Tokenizer};
+    /// # use tokenizers::models::bpe::BPE;
+    /// # let mut tokenizer = Tokenizer::new(BPE::default());
+    /// #
+    /// // Offsets in UTF-16 code units, as used by JavaScript:
+    /// tokenizer.encode_with_offsets(
+    ///     "Single sequence",
+    ///     false,
+    ///     OffsetReferential::Original,
+    ///     OffsetType::Utf16,
+    /// );
+    ///
+    /// // Offsets in bytes in the normalized string:
+    /// tokenizer.encode_with_offsets(
+    ///     "Single sequence",
+    ///     false,
+    ///     OffsetReferential::Normalized,
+    ///     OffsetType::Byte,
+    /// );
+    /// ```
+    pub fn encode_with_offsets<'s, E>(
+        &self,
+        input: E,
+        add_special_tokens: bool,
+        offset_ref: OffsetReferential,
+        offset_type: OffsetType,
+    ) -> Result<Encoding>
+    where
+        E: Into<EncodeInput<'s>>,
+    {
+        // Encode each sequence of the EncodeInput
+        let encodings = input
+            .into()
+            .into_sequences()
+            .into_iter()
+            .enumerate()
+            .map(|(i, sequence)| {
+                self.encode_single_sequence(sequence, i as u32, offset_ref, offset_type)
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         // And finally post process
-        self.post_process(encoding, pair_encoding, add_special_tokens)
+        self.post_process_sequences(encodings, add_special_tokens)
     }
 
     /// Decode the given ids, back to a String
@@ -750,6 +894,116 @@ where
             Ok(tokens.join(" "))
         }
     }
+
+    /// Get a `DecodeStream`, to decode ids one at a time while generating them:
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use tokenizers::Tokenizer;
+    /// # use tokenizers::models::wordpiece::WordPiece;
+    /// # use tokenizers::decoders::wordpiece::WordPiece as WordPieceDecoder;
+    /// # let vocab: HashMap<String, u32> = vec![("[UNK]", 0), ("Hey", 1), ("friend", 2), ("##s", 3)]
+    /// #     .into_iter().map(|(t, i)| (t.to_owned(), i)).collect();
+    /// # let mut tokenizer = Tokenizer::new(WordPiece::builder().vocab(vocab).build().unwrap());
+    /// # tokenizer.with_decoder(WordPieceDecoder::default());
+    /// let mut stream = tokenizer.decode_stream(false);
+    /// assert_eq!(stream.step(1).unwrap(), Some("Hey".to_owned()));
+    /// assert_eq!(stream.step(2).unwrap(), Some(" friend".to_owned()));
+    /// assert_eq!(stream.step(3).unwrap(), Some("s".to_owned()));
+    /// ```
+    pub fn decode_stream(&self, skip_special_tokens: bool) -> DecodeStream<'_, M, N, PT, PP, D> {
+        DecodeStream::new(self, skip_special_tokens)
+    }
+}
+
+/// Errors returned by [`DecodeStream::step`](struct.DecodeStream.html#method.step)
+#[derive(Debug)]
+pub enum DecodeStreamError {
+    /// The text decoded so far is not a prefix of the newly decoded text anymore. This happens
+    /// with decoders that change the previous text depending on the next tokens, in which case
+    /// the text can't be streamed.
+    InvalidPrefix,
+}
+
+impl std::error::Error for DecodeStreamError {}
+
+impl fmt::Display for DecodeStreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeStreamError::InvalidPrefix => write!(
+                f,
+                "DecodeStream error: the previously decoded text is not a prefix of the new one"
+            ),
+        }
+    }
+}
+
+/// Decodes ids one at a time, emitting only the newly finalized text at each step.
+///
+/// Decoding each id on its own is not enough, as most decoders need some context to produce
+/// the right output: `ByteLevel` tokens can hold partial UTF-8 characters, `Metaspace` strips
+/// the leading space of the first token, `WordPiece` needs the previous token to merge a
+/// `##` prefixed one, ... So we keep a small window of ids, always starting with the ids
+/// that produced the last emitted text, and we only emit the part that comes after it. This
+/// keeps each step cheap, instead of decoding the whole sequence again and again.
+pub struct DecodeStream<'tok, M, N, PT, PP, D> {
+    tokenizer: &'tok TokenizerImpl<M, N, PT, PP, D>,
+    skip_special_tokens: bool,
+    /// The ids currently in the window
+    ids: Vec<u32>,
+    /// The text decoded from `ids[..prefix_index]`, already emitted
+    prefix: String,
+    /// The number of ids in the window that were already emitted as `prefix`
+    prefix_index: usize,
+}
+
+impl<'tok, M, N, PT, PP, D> DecodeStream<'tok, M, N, PT, PP, D>
+where
+    M: Model,
+    N: Normalizer,
+    PT: PreTokenizer,
+    PP: PostProcessor,
+    D: Decoder,
+{
+    fn new(tokenizer: &'tok TokenizerImpl<M, N, PT, PP, D>, skip_special_tokens: bool) -> Self {
+        Self {
+            tokenizer,
+            skip_special_tokens,
+            ids: vec![],
+            prefix: String::new(),
+            prefix_index: 0,
+        }
+    }
+
+    /// Add the given id to the stream, and return any text that got finalized by it. This
+    /// returns `None` when the id does not produce any new text yet (an incomplete UTF-8
+    /// character, a skipped special token, ...).
+    pub fn step(&mut self, id: u32) -> Result<Option<String>> {
+        self.ids.push(id);
+        let string = self
+            .tokenizer
+            .decode(self.ids.clone(), self.skip_special_tokens)?;
+
+        if string.len() > self.prefix.len() && !string.ends_with('\u{FFFD}') {
+            if !string.starts_with(&self.prefix) {
+                return Err(Box::new(DecodeStreamError::InvalidPrefix));
+            }
+            let new_text = string[self.prefix.len()..].to_owned();
+
+            // Keep only the ids that were not part of the previous prefix, they become the new
+            // prefix, providing context for the next steps
+            let new_prefix_index = self.ids.len() - self.prefix_index;
+            self.ids.drain(..self.prefix_index);
+            self.prefix = self
+                .tokenizer
+                .decode(self.ids.clone(), self.skip_special_tokens)?;
+            self.prefix_index = new_prefix_index;
+
+            Ok(Some(new_text))
+        } else {
+            Ok(None)
+        }
+    }
 }
 
 impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
@@ -763,11 +1017,12 @@ where
         pretokenized: P,
         type_id: u32,
         word_idx: Option<u32>,
+        offset_ref: OffsetReferential,
         offsets_type: OffsetType,
     ) -> Result<Encoding> {
         let mut pretokenized: PreTokenizedString = pretokenized.into();
         pretokenized.tokenize(|normalized| self.model.tokenize(normalized.get()))?;
-        pretokenized.into_encoding(word_idx, type_id, offsets_type)
+        pretokenized.into_encoding(word_idx, type_id, offset_ref, offsets_type)
     }
 }
 
@@ -836,11 +1091,29 @@ where
         pair_encoding: Option<Encoding>,
         add_special_tokens: bool,
     ) -> Result<Encoding> {
+        self.post_process_sequences(
+            std::iter::once(encoding).chain(pair_encoding).collect(),
+            add_special_tokens,
+        )
+    }
+
+    /// Post processing logic for any number of sequences, handling the case where there is
+    /// no PostProcessor set
+    pub fn post_process_sequences(
+        &self,
+        encodings: Vec<Encoding>,
+        add_special_tokens: bool,
+    ) -> Result<Encoding> {
+        let mut encodings = encodings;
+        for (i, encoding) in encodings.iter_mut().enumerate() {
+            encoding.set_sequence_id(i);
+        }
+
         // 1. First we truncate if needed
-        let (encoding, pair_encoding) = {
+        let encodings = {
             if let Some(trunc) = &self.truncation {
                 let n_added_tokens = if let Some(processor) = &self.post_processor {
-                    processor.added_tokens(pair_encoding.is_some())
+                    processor.added_tokens_for_sequences(encodings.len())
                 } else {
                     0
                 };
@@ -850,20 +1123,20 @@ where
                         max_length: trunc.max_length - n_added_tokens,
                         ..*trunc
                     };
-                    truncate_encodings(encoding, pair_encoding, &params)?
+                    truncate_sequences(encodings, &params)?
                 } else {
-                    truncate_encodings(encoding, pair_encoding, &trunc)?
+                    truncate_sequences(encodings, &trunc)?
                 }
             } else {
-                (encoding, pair_encoding)
+                encodings
             }
         };
 
         // 2. Then We post process
         let final_encoding = if let Some(processor) = &self.post_processor {
-            processor.process(encoding, pair_encoding, add_special_tokens)?
+            processor.process_sequences(encodings, add_special_tokens)?
         } else {
-            PostProcessor::default_process(encoding, pair_encoding, add_special_tokens)?
+            PostProcessor::default_process_sequences(encodings, add_special_tokens)?
         };
 
         // 3. Then we pad if needed
@@ -896,17 +1169,12 @@ where
     where
         E: Into<EncodeInput<'s>> + Send,
     {
-        let mut encodings = inputs
-            .into_maybe_par_iter()
-            .map(|input| self.encode(input, add_special_tokens))
-            .collect::<Result<Vec<Encoding>>>()?;
-
-        if let Some(params) = &self.padding {
-            // We do the padding here to make sure we handle the batch padding
-            pad_encodings(&mut encodings, &params)?;
-        }
-
-        Ok(encodings)
+        self.encode_batch_with_offsets(
+            inputs,
+            add_special_tokens,
+            OffsetReferential::Original,
+            OffsetType::Byte,
+        )
     }
 
     /// Encode all the sentences in parallel, using multiple threads.
@@ -916,12 +1184,35 @@ where
         inputs: Vec<E>,
         add_special_tokens: bool,
     ) -> Result<Vec<Encoding>>
+    where
+        E: Into<EncodeInput<'s>> + Send,
+    {
+        self.encode_batch_with_offsets(
+            inputs,
+            add_special_tokens,
+            OffsetReferential::Original,
+            OffsetType::Char,
+        )
+    }
+
+    /// Encode all the sentences in parallel, using multiple threads.
+    /// The offsets on each `Encoding` will be relative to the given referential, and expressed
+    /// in the given unit.
+    pub fn encode_batch_with_offsets<'s, E>(
+        &self,
+        inputs: Vec<E>,
+        add_special_tokens: bool,
+        offset_ref: OffsetReferential,
+        offset_type: OffsetType,
+    ) -> Result<Vec<Encoding>>
     where
         E: Into<EncodeInput<'s>> + Send,
     {
         let mut encodings = inputs
             .into_maybe_par_iter()
-            .map(|input| self.encode_char_offsets(input, add_special_tokens))
+            .map(|input| {
+                self.encode_with_offsets(input, add_special_tokens, offset_ref, offset_type)
+            })
             .collect::<Result<Vec<Encoding>>>()?;
 
         if let Some(params) = &self.padding {
@@ -947,7 +1238,58 @@ where
             .collect()
     }
 
-    /// Train a model and replace our current Model, using the given Trainer
+    /// Count the words in the given sequences, going through the whole normalization and
+    /// pre-tokenization pipeline, and letting the `Trainer` process the resulting tokens.
+    fn count_words<MN, T, I, S>(
+        &self,
+        trainer: &T,
+        sequences: I,
+        progress: &Option<ProgressBar>,
+    ) -> Result<HashMap<String, u32>>
+    where
+        T: Trainer<Model = MN> + Sync,
+        MN: Model,
+        I: Iterator<Item = Result<S>> + Send,
+        S: AsRef<str> + Send,
+    {
+        sequences
+            .maybe_par_bridge()
+            .map_with(
+                progress,
+                |progress, sequence| -> Result<HashMap<String, u32>> {
+                    let sequence = sequence?;
+                    let sequence = sequence.as_ref();
+                    let mut words = HashMap::new();
+                    let normalized = self.do_normalize(sequence)?;
+                    let pre_tokenized = self.do_pre_tokenize(normalized)?;
+                    trainer.process_tokens(
+                        &mut words,
+                        pre_tokenized
+                            .get_splits(OffsetReferential::Original, OffsetType::Byte)
+                            .into_iter()
+                            .map(|(s, _, _)| s.to_owned())
+                            .collect(),
+                    );
+
+                    if let Some(pbar) = progress {
+                        pbar.inc(sequence.len() as u64);
+                    }
+                    Ok(words)
+                },
+            )
+            .reduce(
+                || Ok(HashMap::new()),
+                |acc, ws| {
+                    let mut acc = acc?;
+                    for (k, v) in ws? {
+                        acc.entry(k).and_modify(|c| *c += v).or_insert(v);
+                    }
+                    Ok(acc)
+                },
+            )
+    }
+
+    /// Count the words contained in the given files
     fn word_count<MN, T>(&self, trainer: &T, files: Vec<String>) -> Result<HashMap<String, u32>>
     where
         T: Trainer<Model = MN> + Sync,
@@ -981,41 +1323,12 @@ where
                 // We read new lines using this API instead of the Lines Iterator
                 // on purpose. We want to keep the `\n` and potential `\r` between each lines
                 // We use an iterator to be able to chain with par_bridge.
-                file.lines_with_ending()
-                    .maybe_par_bridge()
-                    .map_with(
-                        &progress,
-                        |progress, line| -> Result<HashMap<String, u32>> {
-                            let newline = line?;
-                            let b = newline.len();
-                            let mut words = HashMap::new();
-                            let normalized = self.do_normalize(newline)?;
-                            let pre_tokenized = self.do_pre_tokenize(normalized)?;
-                            trainer.process_tokens(
-                                &mut words,
-                                pre_tokenized
-                                    .get_splits(OffsetReferential::Original, OffsetType::Byte)
-                                    .into_iter()
-                                    .map(|(s, _, _)| s.to_owned())
-                                    .collect(),
-                            );
-
-                            if let Some(pbar) = progress {
-                                pbar.inc(b as u64);
-                            }
-                            Ok(words)
-                        },
-                    )
-                    .reduce(
-                        || Ok(HashMap::new()),
-                        |acc, ws| {
-                            let mut acc = acc?;
-                            for (k, v) in ws? {
-                                acc.entry(k).and_modify(|c| *c += v).or_insert(v);
-                            }
-                            Ok(acc)
-                        },
-                    )
+                self.count_words(
+                    trainer,
+                    file.lines_with_ending()
+                        .map(|line| line.map_err(Error::from)),
+                    &progress,
+                )
             })
             .try_fold(
                 HashMap::new(),
@@ -1032,18 +1345,48 @@ where
         Ok(words)
     }
 
-    /// Train a model and return a new Tokenizer, using the given Trainer
-    pub fn train<T, TM>(
+    /// Count the words contained in the given sequences. Since we can't know the total size
+    /// of an iterator up front, the progress only reports the amount of data processed so far.
+    fn word_count_from_iter<MN, T, I, S>(
+        &self,
+        trainer: &T,
+        sequences: I,
+    ) -> Result<HashMap<String, u32>>
+    where
+        T: Trainer<Model = MN> + Sync,
+        MN: Model,
+        I: Iterator<Item = S> + Send,
+        S: AsRef<str> + Send,
+    {
+        let progress = if trainer.should_show_progress() {
+            let progress = ProgressBar::new_spinner();
+            progress.set_style(
+                ProgressStyle::default_spinner()
+                    .template("[{elapsed_precise}] {msg:<40!} {spinner} {bytes:>19!}"),
+            );
+            progress.set_message("Reading sequences");
+            progress.set_draw_delta(1_000_000); // Redraw only every Mo
+            Some(progress)
+        } else {
+            None
+        };
+        let words = self.count_words(trainer, sequences.map(Ok), &progress)?;
+        if let Some(pbar) = progress {
+            pbar.finish();
+        }
+        Ok(words)
+    }
+
+    /// Build a new Tokenizer with a model trained on the given words
+    fn train_on_words<T, TM>(
         self,
         trainer: &T,
-        files: Vec<String>,
+        words: HashMap<String, u32>,
     ) -> Result<TokenizerImpl<TM, N, PT, PP, D>>
     where
         T: Trainer<Model = TM> + Sync,
         TM: Model,
     {
-        let words = self.word_count(trainer, files)?;
-
         let (model, special_tokens) = trainer.train(words)?;
         let mut new_tok = TokenizerImpl {
             normalizer: self.normalizer,
@@ -1061,6 +1404,52 @@ where
         Ok(new_tok)
     }
 
+    /// Train a model and return a new Tokenizer, using the given Trainer
+    pub fn train<T, TM>(
+        self,
+        trainer: &T,
+        files: Vec<String>,
+    ) -> Result<TokenizerImpl<TM, N, PT, PP, D>>
+    where
+        T: Trainer<Model = TM> + Sync,
+        TM: Model,
+    {
+        let words = self.word_count(trainer, files)?;
+        self.train_on_words(trainer, words)
+    }
+
+    /// Train a model and return a new Tokenizer, using the given Trainer and
+    /// the sequences provided by any iterator:
+    ///
+    /// ```
+    /// # use tokenizers::Tokenizer;
+    /// # use tokenizers::models::bpe::{BpeTrainer, BPE};
+    /// # use tokenizers::pre_tokenizers::whitespace::Whitespace;
+    /// let mut tokenizer = Tokenizer::new(BPE::default());
+    /// tokenizer.with_pre_tokenizer(Whitespace::default());
+    ///
+    /// let trainer = BpeTrainer::builder().show_progress(false).build();
+    /// let sequences = vec!["Hello there!", "General Kenobi!"];
+    /// let tokenizer = tokenizer
+    ///     .into_inner()
+    ///     .train_from_iter(&trainer, sequences.into_iter())
+    ///     .unwrap();
+    /// ```
+    pub fn train_from_iter<T, TM, I, S>(
+        self,
+        trainer: &T,
+        sequences: I,
+    ) -> Result<TokenizerImpl<TM, N, PT, PP, D>>
+    where
+        T: Trainer<Model = TM> + Sync,
+        TM: Model,
+        I: Iterator<Item = S> + Send,
+        S: AsRef<str> + Send,
+    {
+        let words = self.word_count_from_iter(trainer, sequences)?;
+        self.train_on_words(trainer, words)
+    }
+
     /// Train a model and replace our current Model, using the given Trainer
     pub fn train_and_replace<T>(&mut self, trainer: &T, files: Vec<String>) -> Result<()>
     where
@@ -1074,6 +1463,23 @@ where
 
         Ok(())
     }
+
+    /// Train a model and replace our current Model, using the given Trainer and
+    /// the sequences provided by any iterator
+    pub fn train_and_replace_from_iter<T, I, S>(&mut self, trainer: &T, sequences: I) -> Result<()>
+    where
+        T: Trainer<Model = M> + Sync,
+        I: Iterator<Item = S> + Send,
+        S: AsRef<str> + Send,
+    {
+        let words = self.word_count_from_iter(trainer, sequences)?;
+
+        let (model, special_tokens) = trainer.train(words)?;
+        self.model = model;
+        self.add_special_tokens(&special_tokens);
+
+        Ok(())
+    }
 }
 
 impl<M, N, PT, PP, D> std::str::FromStr for TokenizerImpl<M, N, PT, PP, D>
@@ -1132,4 +1538,62 @@ where
 
         Ok(())
     }
+
+    /// Serialize the current tokenizer in the binary format, see
+    /// [`BinaryTokenizer`](struct.BinaryTokenizer.html)
+    pub fn to_binary(&self) -> Result<Vec<u8>> {
+        binary::to_binary(self)
+    }
+
+    /// Save the current tokenizer in the binary format at the given path
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(&self.to_binary()?)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bpe::{BpeTrainer, BPE};
+    use crate::pre_tokenizers::whitespace::Whitespace;
+    use std::io::Write;
+
+    #[test]
+    fn train_from_iter_matches_files() {
+        let sequences = vec![
+            "Hello there, how are you?",
+            "General Kenobi, you are a bold one",
+            "There are so many of them",
+        ];
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for sequence in &sequences {
+            writeln!(file, "{}", sequence).unwrap();
+        }
+
+        let trainer = BpeTrainer::builder()
+            .show_progress(false)
+            .min_frequency(2)
+            .build();
+        let mut tokenizer = Tokenizer::new(BPE::default());
+        tokenizer.with_pre_tokenizer(Whitespace::default());
+
+        let from_files = tokenizer
+            .clone()
+            .train(&trainer, vec![file.path().to_str().unwrap().to_owned()])
+            .unwrap();
+        let from_iter = tokenizer
+            .into_inner()
+            .train_from_iter(&trainer, sequences.into_iter())
+            .unwrap();
+
+        assert!(from_iter.get_vocab_size(false) > 0);
+        assert_eq!(from_iter.get_vocab(false), from_files.get_vocab(false));
+        assert_eq!(
+            serde_json::to_string(from_iter.get_model()).unwrap(),
+            serde_json::to_string(from_files.get_model()).unwrap()
+        );
+    }
 }
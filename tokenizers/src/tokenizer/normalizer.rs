@@ -1,5 +1,6 @@
 use crate::pattern::Pattern;
-use crate::{Offsets, Result};
+use crate::{AlignmentReport, Offsets, Result};
+use serde::{Deserialize, Serialize};
 use std::ops::{Bound, RangeBounds};
 use unicode_normalization_alignments::UnicodeNormalization;
 
@@ -89,7 +90,7 @@ where
 ///  - MergedWithPrevious => `[ "the-", "final-", "-", "countdown" ]`
 ///  - MergedWithNext => `[ "the", "-final", "-", "-countdown" ]`
 ///  - Contiguous => `[ "the", "-", "final", "--", "countdown" ]`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SplitDelimiterBehavior {
     Removed,
     Isolated,
@@ -768,6 +769,51 @@ impl NormalizedString {
         self.normalized.is_empty()
     }
 
+    /// Returns the parts of the original string that were modified by the normalization, as
+    /// pairs of `(original_offsets, normalized_offsets)`, in bytes. Removed parts have empty
+    /// normalized offsets, and added parts have empty original offsets.
+    pub fn changes(&self) -> Vec<(Offsets, Offsets)> {
+        let mut changes: Vec<(Offsets, Offsets)> = vec![];
+        let mut add_change = |original: Offsets, normalized: Offsets| match changes.last_mut() {
+            // Merge with the previous change when they touch each other
+            Some((o, n)) if o.1 >= original.0 && n.1 == normalized.0 => {
+                o.1 = o.1.max(original.1);
+                n.1 = normalized.1;
+            }
+            _ => changes.push((original, normalized)),
+        };
+
+        let mut original_end = 0;
+        for (i, c) in self.normalized.char_indices() {
+            let end = i + c.len_utf8();
+            let original = (self.alignments[i].0, self.alignments[end - 1].1);
+            if original.0 > original_end {
+                add_change((original_end, original.0), (i, i));
+            }
+            if self.original.get(original.0..original.1) != Some(&self.normalized[i..end]) {
+                add_change(original, (i, end));
+            }
+            original_end = original_end.max(original.1);
+        }
+        if original_end < self.original.len() {
+            let len = self.normalized.len();
+            add_change((original_end, self.original.len()), (len, len));
+        }
+
+        changes
+    }
+
+    /// Returns a report of the alignments between the original and the normalized strings,
+    /// with one entry per normalized char, and one per removed part of the original string
+    pub fn alignment_report(&self) -> AlignmentReport {
+        let mut report = AlignmentReport::new(&self.original);
+        // A slice is aligned with its own original string, not the one it comes from
+        let mut normalized = self.clone();
+        normalized.original_shift = 0;
+        report.push_normalized(&normalized);
+        report.finish()
+    }
+
     /// Recalculate original alignments
     #[allow(dead_code)]
     pub(crate) fn alignments_original(&self) -> Vec<(usize, usize)> {
@@ -952,6 +998,27 @@ mod tests {
     use regex::Regex;
     use unicode_categories::UnicodeCategories;
 
+    #[test]
+    fn changes() {
+        let mut n = NormalizedString::from("Hello  WORLD");
+        assert_eq!(n.changes(), vec![]);
+
+        n.lowercase();
+        assert_eq!(n.changes(), vec![((0, 1), (0, 1)), ((7, 12), (7, 12))]);
+
+        let mut n = NormalizedString::from("élégant ");
+        n.nfd().filter(|c| !c.is_mark_nonspacing()).rstrip();
+        assert_eq!(
+            n.changes(),
+            vec![((0, 2), (0, 1)), ((3, 5), (2, 3)), ((9, 10), (7, 7))]
+        );
+
+        let mut n = NormalizedString::from("Hey");
+        n.prepend("▁");
+        // The prepended char is aligned with the first one
+        assert_eq!(n.changes(), vec![((0, 1), (0, 3))]);
+    }
+
     #[test]
     fn nfd_adds_new_chars() {
         let mut n = NormalizedString::from("élégant");
@@ -1,5 +1,6 @@
 use crate::{
-    normalizer::Range, Encoding, NormalizedString, OffsetReferential, Offsets, Result, Token,
+    normalizer::Range, AlignmentReport, Encoding, NormalizedString, OffsetReferential, Offsets,
+    Result, Token,
 };
 use std::collections::HashMap;
 
@@ -8,6 +9,8 @@ use std::collections::HashMap;
 pub enum OffsetType {
     Byte,
     Char,
+    /// UTF-16 code units, as used to index the strings in JavaScript or Java
+    Utf16,
 }
 
 /// Wrapper for a subpart of a `NormalizedString`.
@@ -131,11 +134,15 @@ impl PreTokenizedString {
     /// will be set to this value. This is generally used with pre-tokenized
     /// input, that do not need the `PreTokenizedString` to generate word ids.
     ///
+    /// The offsets of the tokens are relative to the given referential, the original string or
+    /// the normalized one (the concatenation of all the splits), and use the given unit.
+    ///
     /// This method will fail if some splits do not have associated `Token`.
     pub fn into_encoding(
         self,
         word_idx: Option<u32>,
         type_id: u32,
+        offset_ref: OffsetReferential,
         offset_type: OffsetType,
     ) -> Result<Encoding> {
         if self.splits.is_empty() {
@@ -143,28 +150,44 @@ impl PreTokenizedString {
         } else if !self.splits.iter().all(|split| split.tokens.is_some()) {
             Err("Split has not been tokenized, call `PreTokenizedString::tokenize` first".into())
         } else {
-            let offset_converter = match offset_type {
-                OffsetType::Char => Some(BytesToCharOffsetConverter::new(&self.original)),
-                OffsetType::Byte => None,
+            let offset_converter = match offset_ref {
+                OffsetReferential::Original => OffsetConverter::new(&self.original, offset_type),
+                OffsetReferential::Normalized => {
+                    OffsetConverter::new(&self.normalized(), offset_type)
+                }
             };
 
+            let mut normalized_offset = 0;
             Ok(self
                 .splits
                 .into_iter()
                 .enumerate()
                 .flat_map(|(idx, split)| {
                     let normalized = split.normalized;
-                    let offsets = normalized.offsets_original();
+                    let offsets = match offset_ref {
+                        OffsetReferential::Original => normalized.offsets_original(),
+                        OffsetReferential::Normalized => {
+                            normalized_offset += normalized.len();
+                            (normalized_offset - normalized.len(), normalized_offset)
+                        }
+                    };
                     let offset_converter = &offset_converter;
 
                     split.tokens.unwrap().into_iter().map(move |token| {
-                        let mut offsets = normalized
-                            .convert_offsets(Range::Normalized(token.offsets.0..token.offsets.1))
-                            .map_or(token.offsets, |range| {
-                                (offsets.0 + range.start, offsets.0 + range.end)
-                            });
+                        let mut offsets = match offset_ref {
+                            OffsetReferential::Original => normalized
+                                .convert_offsets(Range::Normalized(
+                                    token.offsets.0..token.offsets.1,
+                                ))
+                                .map_or(token.offsets, |range| {
+                                    (offsets.0 + range.start, offsets.0 + range.end)
+                                }),
+                            OffsetReferential::Normalized => {
+                                (offsets.0 + token.offsets.0, offsets.0 + token.offsets.1)
+                            }
+                        };
 
-                        // Convert to char offsets if relevant
+                        // Convert to another unit if relevant
                         if let Some(converter) = offset_converter {
                             offsets = converter.convert(offsets).unwrap_or(offsets);
                         }
@@ -194,9 +217,9 @@ impl PreTokenizedString {
         offset_ref: OffsetReferential,
         offset_type: OffsetType,
     ) -> Vec<(&str, Offsets, &Option<Vec<Token>>)> {
-        let offset_converter = match offset_type {
-            OffsetType::Char => Some(BytesToCharOffsetConverter::new(&self.original)),
-            OffsetType::Byte => None,
+        let offset_converter = match offset_ref {
+            OffsetReferential::Original => OffsetConverter::new(&self.original, offset_type),
+            OffsetReferential::Normalized => OffsetConverter::new(&self.normalized(), offset_type),
         };
 
         let mut offset = 0;
@@ -212,7 +235,7 @@ impl PreTokenizedString {
                     }
                 };
 
-                // Convert to char offsets if relevant
+                // Convert to another unit if relevant
                 if let Some(ref converter) = offset_converter {
                     offsets = converter.convert(offsets).unwrap_or(offsets);
                 }
@@ -221,6 +244,25 @@ impl PreTokenizedString {
             })
             .collect()
     }
+
+    /// The normalized string, made of all the splits
+    fn normalized(&self) -> String {
+        self.splits
+            .iter()
+            .map(|split| split.normalized.get())
+            .collect()
+    }
+
+    /// Returns a report of the alignments between the original string and the splits, along
+    /// with their tokens if any. The normalized string of the report is the concatenation of
+    /// all the splits.
+    pub fn alignment_report(&self) -> AlignmentReport {
+        let mut report = AlignmentReport::new(&self.original);
+        for split in &self.splits {
+            report.push_split(&split.normalized, split.tokens.as_deref());
+        }
+        report.finish()
+    }
 }
 
 impl From<NormalizedString> for PreTokenizedString {
@@ -249,39 +291,79 @@ impl From<String> for PreTokenizedString {
     }
 }
 
-struct BytesToCharOffsetConverter {
+/// Converts the byte offsets in a string to another unit
+struct OffsetConverter {
     map: HashMap<usize, usize>,
 }
 
-impl BytesToCharOffsetConverter {
-    pub fn new(sequence: &str) -> Self {
-        Self {
-            map: sequence
-                .char_indices()
-                .enumerate()
-                .flat_map(|(i, (b, c))| {
-                    let mut n = 0;
-                    std::iter::repeat_with(move || {
-                        let o = (b + n, i);
-                        n += 1;
-                        o
-                    })
-                    .take(c.len_utf8())
-                })
-                .collect(),
+impl OffsetConverter {
+    /// Returns `None` for bytes, as there is nothing to convert
+    pub fn new(sequence: &str, offset_type: OffsetType) -> Option<Self> {
+        let unit_len: fn(char) -> usize = match offset_type {
+            OffsetType::Byte => return None,
+            OffsetType::Char => |_| 1,
+            OffsetType::Utf16 => char::len_utf16,
+        };
+
+        // Each byte of a char is mapped to the beginning of this char, and the end of the
+        // sequence is mapped too
+        let mut map = HashMap::with_capacity(sequence.len() + 1);
+        let mut units = 0;
+        for (b, c) in sequence.char_indices() {
+            map.extend((b..b + c.len_utf8()).map(|b| (b, units)));
+            units += unit_len(c);
         }
+        map.insert(sequence.len(), units);
+        Some(Self { map })
     }
 
     pub fn convert(&self, offsets: Offsets) -> Option<Offsets> {
-        match (self.map.get(&offsets.0), self.map.get(&offsets.1)) {
-            (Some(start), Some(end)) => Some((*start, *end)),
-            // If we reached the end, `end` is not in the map
-            (Some(start), None) => {
-                // But the one just before should be
-                let last = self.map.get(&(offsets.1 - 1)).copied().unwrap_or(start + 1);
-                Some((*start, last + 1))
-            }
-            _ => None,
-        }
+        Some((*self.map.get(&offsets.0)?, *self.map.get(&offsets.1)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SplitDelimiterBehavior;
+
+    fn encode(offset_ref: OffsetReferential, offset_type: OffsetType) -> Encoding {
+        let mut pretokenized = PreTokenizedString::from("a 😀 b");
+        pretokenized
+            .split(|_, normalized| normalized.split(' ', SplitDelimiterBehavior::Removed))
+            .unwrap();
+        pretokenized
+            .tokenize(|normalized| {
+                Ok(vec![Token::new(
+                    0,
+                    normalized.get().to_owned(),
+                    (0, normalized.len()),
+                )])
+            })
+            .unwrap();
+        pretokenized
+            .into_encoding(None, 0, offset_ref, offset_type)
+            .unwrap()
+    }
+
+    #[test]
+    fn original_offsets() {
+        let encoding = encode(OffsetReferential::Original, OffsetType::Byte);
+        assert_eq!(encoding.get_offsets(), &[(0, 1), (2, 6), (7, 8)]);
+        let encoding = encode(OffsetReferential::Original, OffsetType::Char);
+        assert_eq!(encoding.get_offsets(), &[(0, 1), (2, 3), (4, 5)]);
+        let encoding = encode(OffsetReferential::Original, OffsetType::Utf16);
+        assert_eq!(encoding.get_offsets(), &[(0, 1), (2, 4), (5, 6)]);
+    }
+
+    #[test]
+    fn normalized_offsets() {
+        // The normalized string is "a😀b" once the spaces are removed
+        let encoding = encode(OffsetReferential::Normalized, OffsetType::Byte);
+        assert_eq!(encoding.get_offsets(), &[(0, 1), (1, 5), (5, 6)]);
+        let encoding = encode(OffsetReferential::Normalized, OffsetType::Char);
+        assert_eq!(encoding.get_offsets(), &[(0, 1), (1, 2), (2, 3)]);
+        let encoding = encode(OffsetReferential::Normalized, OffsetType::Utf16);
+        assert_eq!(encoding.get_offsets(), &[(0, 1), (1, 3), (3, 4)]);
     }
 }
@@ -0,0 +1,310 @@
+//! Shrinks the vocabulary of a trained `Tokenizer`.
+//!
+//! The tokens to remove can be given explicitly, or selected using their frequencies on some
+//! corpus, as counted by `TokenizerImpl::token_frequencies`.
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crate::utils::parallelism::*;
+use crate::{
+    Decoder, InputSequence, Model, Normalizer, OffsetReferential, OffsetType, PostProcessor,
+    PreTokenizer, Result, TokenizerImpl,
+};
+
+/// The tokens to remove when pruning the vocabulary of a `Tokenizer`
+#[derive(Debug, Clone, Default)]
+pub struct PruneParams {
+    /// Some tokens to remove
+    pub tokens: Vec<String>,
+    /// The number of occurrences of each token id on some corpus. The ids missing from it are
+    /// considered as never seen.
+    pub frequencies: Option<HashMap<u32, u64>>,
+    /// Remove the tokens seen less than this number of times. Requires `frequencies`.
+    pub min_frequency: u64,
+    /// The maximum size of the vocabulary of the model. The most frequent tokens are kept when
+    /// `frequencies` are given, and the ones with the lowest ids otherwise.
+    pub vocab_size: Option<usize>,
+}
+
+impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
+where
+    M: Model + Send + Sync,
+    N: Normalizer + Send + Sync,
+    PT: PreTokenizer + Send + Sync,
+    PP: PostProcessor + Send + Sync,
+    D: Decoder + Send + Sync,
+{
+    /// Count, in parallel, how many times each token is used to encode the given sequences.
+    /// Neither the special tokens of the `PostProcessor`, nor the padding are counted.
+    pub fn token_frequencies<S>(&self, sequences: Vec<S>) -> Result<HashMap<u32, u64>>
+    where
+        S: AsRef<str> + Send,
+    {
+        sequences
+            .into_maybe_par_iter()
+            .map(|sequence| {
+                let encoding = self.encode_single_sequence(
+                    InputSequence::from(sequence.as_ref()),
+                    0,
+                    OffsetReferential::Original,
+                    OffsetType::Byte,
+                )?;
+                let mut frequencies = HashMap::new();
+                for id in encoding.get_ids() {
+                    *frequencies.entry(*id).or_insert(0) += 1;
+                }
+                Ok(frequencies)
+            })
+            .reduce(
+                || Ok(HashMap::new()),
+                |acc, frequencies| {
+                    let mut acc = acc?;
+                    for (id, n) in frequencies? {
+                        *acc.entry(id).or_insert(0) += n;
+                    }
+                    Ok(acc)
+                },
+            )
+    }
+}
+
+impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
+where
+    M: Model,
+    N: Normalizer,
+{
+    /// Remove some tokens from the vocabulary of the `Model`, and re-number the remaining ones
+    /// along with the added tokens, so that all the ids stay contiguous. Returns the mapping from
+    /// the old ids to the new ones, for all the tokens that were kept.
+    ///
+    /// The unknown token and the added tokens are never removed. Removing a token can also
+    /// remove the ones that can't be produced without it, like the result of a `BPE` merge.
+    /// The `PostProcessor` is left untouched, and must be updated if it uses some of the ids
+    /// that changed.
+    pub fn prune(&mut self, params: &PruneParams) -> Result<HashMap<u32, u32>> {
+        let protected = self
+            .model
+            .unk_token()
+            .and_then(|unk| self.model.token_to_id(unk))
+            .into_iter()
+            .chain(self.added_vocabulary.ids())
+            .collect::<HashSet<_>>();
+
+        let mut ids = HashSet::new();
+        for token in &params.tokens {
+            let id = self.model.token_to_id(token).ok_or_else(|| {
+                format!(
+                    "Token `{}` is not part of the vocabulary of the model",
+                    token
+                )
+            })?;
+            if protected.contains(&id) {
+                return Err(format!(
+                    "Cannot remove `{}`, the unknown token and the added tokens are kept",
+                    token
+                )
+                .into());
+            }
+            ids.insert(id);
+        }
+
+        let mut candidates = self
+            .model
+            .get_vocab()
+            .values()
+            .copied()
+            .filter(|id| !protected.contains(id) && !ids.contains(id))
+            .collect::<Vec<_>>();
+        let frequency = |id: &u32| {
+            params
+                .frequencies
+                .as_ref()
+                .and_then(|frequencies| frequencies.get(id))
+                .copied()
+                .unwrap_or(0)
+        };
+        if params.min_frequency > 0 {
+            if params.frequencies.is_none() {
+                return Err("Pruning with `min_frequency` requires some `frequencies`".into());
+            }
+            let (kept, removed) = candidates
+                .into_iter()
+                .partition(|id| frequency(id) >= params.min_frequency);
+            candidates = kept;
+            ids.extend::<Vec<_>>(removed);
+        }
+        if let Some(vocab_size) = params.vocab_size {
+            if params.frequencies.is_some() {
+                candidates.sort_unstable_by_key(|id| (Reverse(frequency(id)), *id));
+            } else {
+                candidates.sort_unstable();
+            }
+            let kept = self.model.get_vocab_size() - ids.len() - candidates.len();
+            let keep = vocab_size.saturating_sub(kept).min(candidates.len());
+            ids.extend(candidates.drain(keep..));
+        }
+
+        let mut mapping = self.model.prune(&ids)?;
+        let added =
+            self.added_vocabulary
+                .remap_ids(&mapping, &self.model, self.normalizer.as_ref());
+        mapping.extend(added);
+
+        Ok(mapping)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bpe::BPE;
+    use crate::models::wordlevel::WordLevel;
+    use crate::pre_tokenizers::whitespace::WhitespaceSplit;
+    use crate::{AddedToken, Tokenizer};
+
+    fn build_tokenizer() -> Tokenizer {
+        let vocab = ["[UNK]", "the", "cat", "sat", "on", "mat", "dog"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.to_string(), i as u32))
+            .collect();
+        let mut tokenizer = Tokenizer::new(
+            WordLevel::builder()
+                .vocab(vocab)
+                .unk_token("[UNK]".into())
+                .build(),
+        );
+        tokenizer.with_pre_tokenizer(WhitespaceSplit);
+        tokenizer.add_special_tokens(&[AddedToken::from("[CLS]", true)]);
+        tokenizer.add_tokens(&[AddedToken::from("cat", false)]);
+        tokenizer
+    }
+
+    fn sorted_vocab(tokenizer: &Tokenizer) -> Vec<(String, u32)> {
+        let mut vocab = tokenizer.get_vocab(true).into_iter().collect::<Vec<_>>();
+        vocab.sort_unstable_by_key(|(_, id)| *id);
+        vocab
+    }
+
+    #[test]
+    fn prune_tokens() {
+        let mut tokenizer = build_tokenizer();
+        let mapping = tokenizer
+            .prune(&PruneParams {
+                tokens: vec!["the".into(), "on".into()],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut mapping = mapping.into_iter().collect::<Vec<_>>();
+        mapping.sort_unstable();
+        assert_eq!(
+            mapping,
+            vec![(0, 0), (2, 1), (3, 2), (5, 3), (6, 4), (7, 5)]
+        );
+        assert_eq!(
+            sorted_vocab(&tokenizer),
+            vec![
+                ("[UNK]".into(), 0),
+                ("cat".into(), 1),
+                ("sat".into(), 2),
+                ("mat".into(), 3),
+                ("dog".into(), 4),
+                ("[CLS]".into(), 5),
+            ]
+        );
+        let encoding = tokenizer.encode("[CLS] the cat", false).unwrap();
+        assert_eq!(encoding.get_ids(), &[5, 0, 1]);
+
+        // The unknown token and the added tokens can't be removed
+        for token in &["[UNK]", "cat"] {
+            assert!(tokenizer
+                .prune(&PruneParams {
+                    tokens: vec![token.to_string()],
+                    ..Default::default()
+                })
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn prune_frequencies() {
+        let mut tokenizer = build_tokenizer();
+        let frequencies = tokenizer
+            .token_frequencies(vec!["the cat sat on the mat", "the dog [CLS]"])
+            .unwrap();
+        assert_eq!(frequencies[&1], 3);
+        assert_eq!(frequencies[&7], 1);
+        assert_eq!(frequencies.get(&0), None);
+
+        let params = PruneParams {
+            frequencies: Some(frequencies),
+            min_frequency: 1,
+            vocab_size: Some(4),
+            ..Default::default()
+        };
+        tokenizer.prune(&params).unwrap();
+        assert_eq!(
+            sorted_vocab(&tokenizer),
+            vec![
+                ("[UNK]".into(), 0),
+                ("the".into(), 1),
+                ("cat".into(), 2),
+                ("sat".into(), 3),
+                ("[CLS]".into(), 4),
+            ]
+        );
+
+        let mut tokenizer = build_tokenizer();
+        assert!(tokenizer
+            .prune(&PruneParams {
+                min_frequency: 1,
+                ..Default::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn prune_bpe_merges() {
+        let vocab = [
+            "<unk>", "a", "b", "c", "d", "ab", "cd", "abc", "abcd", "bc", "bcd",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (t.to_string(), i as u32))
+        .collect();
+        let merges = vec![
+            ("a".into(), "b".into()),
+            ("c".into(), "d".into()),
+            ("ab".into(), "c".into()),
+            ("abc".into(), "d".into()),
+            ("b".into(), "c".into()),
+            ("bc".into(), "d".into()),
+            ("ab".into(), "cd".into()),
+        ];
+        let bpe = BPE::builder()
+            .vocab_and_merges(vocab, merges)
+            .unk_token("<unk>".into())
+            .build()
+            .unwrap();
+        let mut tokenizer = Tokenizer::new(bpe);
+
+        // Removing `ab` also removes `abc` and `abcd`, that can't be produced anymore
+        tokenizer
+            .prune(&PruneParams {
+                tokens: vec!["ab".into()],
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            sorted_vocab(&tokenizer)
+                .into_iter()
+                .map(|(t, _)| t)
+                .collect::<Vec<_>>(),
+            vec!["<unk>", "a", "b", "c", "d", "cd", "bc", "bcd"]
+        );
+        let encoding = tokenizer.encode("abcd", false).unwrap();
+        assert_eq!(encoding.get_tokens(), &["a", "b", "cd"]);
+    }
+}
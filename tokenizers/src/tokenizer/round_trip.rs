@@ -0,0 +1,649 @@
+//! Checks whether a `Tokenizer` is able to decode what it encoded.
+//!
+//! Running a corpus through `TokenizerImpl::validate_round_trip` reports each input for which
+//! `decode(encode(input)) != input`, with the step of the pipeline responsible for the
+//! difference, and some statistics about the tokenization of the corpus.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::normalizers::NormalizerWrapper;
+use crate::pre_tokenizers::PreTokenizerWrapper;
+use crate::tokenizer::normalizer::Range;
+use crate::utils::parallelism::*;
+use crate::{
+    Decoder, Encoding, Model, NormalizedString, Normalizer, OffsetReferential, OffsetType, Offsets,
+    PostProcessor, PreTokenizedString, PreTokenizer, Result, TokenizerImpl,
+};
+
+/// The step of the pipeline responsible for a lossy round trip
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum RoundTripStep {
+    /// The `Normalizer` modified the input
+    Normalizer,
+    /// The `PreTokenizer` removed a part of the input, that the `Decoder` can't restore
+    PreTokenizer,
+    /// The `Model` produced some unknown tokens
+    Model,
+    /// The `Decoder` didn't restore the input, or there is no `Decoder`
+    Decoder,
+}
+
+impl fmt::Display for RoundTripStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// An input that couldn't be decoded back
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoundTripMismatch {
+    /// The index of the input in the corpus
+    pub index: usize,
+    /// The input
+    pub original: String,
+    /// The result of `decode(encode(original))`
+    pub decoded: String,
+    /// The first step of the pipeline responsible for the difference
+    pub step: RoundTripStep,
+    /// The component of the `Normalizer` or `PreTokenizer` responsible for the difference,
+    /// like `Lowercase`, or `Sequence[1]: Lowercase` for the second element of a `Sequence`
+    pub component: Option<String>,
+    /// The offsets (in bytes) of the part of `original` modified by `step`
+    pub offsets: Offsets,
+    /// What `step` made of this part of `original`
+    pub replacement: String,
+}
+
+/// The result of the round trip of a whole corpus
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RoundTripReport {
+    /// The number of inputs
+    pub samples: usize,
+    /// The number of inputs that were decoded back to their original form
+    pub lossless: usize,
+    /// The total number of tokens
+    pub tokens: usize,
+    /// The total number of unknown tokens
+    pub unk_tokens: usize,
+    /// The total number of words, as produced by the `PreTokenizer`
+    pub words: usize,
+    /// Each input that couldn't be decoded back, ordered by index
+    pub mismatches: Vec<RoundTripMismatch>,
+}
+
+impl RoundTripReport {
+    /// The fraction of inputs that were decoded back to their original form
+    pub fn lossless_rate(&self) -> f64 {
+        ratio(self.lossless, self.samples)
+    }
+
+    /// The fraction of tokens that are unknown
+    pub fn unk_rate(&self) -> f64 {
+        ratio(self.unk_tokens, self.tokens)
+    }
+
+    /// The mean number of tokens per word
+    pub fn tokens_per_word(&self) -> f64 {
+        ratio(self.tokens, self.words)
+    }
+
+    /// The number of mismatches caused by each step
+    pub fn mismatches_by_step(&self) -> HashMap<RoundTripStep, usize> {
+        let mut steps = HashMap::new();
+        for mismatch in &self.mismatches {
+            *steps.entry(mismatch.step).or_insert(0) += 1;
+        }
+        steps
+    }
+}
+
+fn ratio(a: usize, b: usize) -> f64 {
+    if b == 0 {
+        0.0
+    } else {
+        a as f64 / b as f64
+    }
+}
+
+/// The round trip of a single input
+struct Sample {
+    tokens: usize,
+    unk_tokens: usize,
+    words: usize,
+    mismatch: Option<RoundTripMismatch>,
+}
+
+/// A step of the pipeline responsible for a mismatch, with the name of its component, the
+/// offsets of its modification and what it replaced them with
+type ResponsibleStep = (RoundTripStep, Option<String>, Offsets, String);
+
+/// The name of a `Normalizer` or `PreTokenizer`, as given by its serialized `type`
+fn type_name<T: Serialize>(component: &T) -> String {
+    serde_json::to_value(component)
+        .ok()
+        .and_then(|value| value["type"].as_str().map(|name| name.to_owned()))
+        .unwrap_or_else(|| "Unknown".into())
+}
+
+/// List the normalizers that are applied one after the other, with their name. The elements
+/// of a `Sequence` are listed recursively, and named after their position in it.
+fn normalizer_components<'a>(
+    normalizer: &'a NormalizerWrapper,
+    prefix: &str,
+    components: &mut Vec<(String, &'a NormalizerWrapper)>,
+) {
+    match normalizer {
+        NormalizerWrapper::Sequence(sequence) => {
+            for (i, normalizer) in sequence.get_normalizers().iter().enumerate() {
+                let prefix = format!("{}Sequence[{}]: ", prefix, i);
+                normalizer_components(normalizer, &prefix, components);
+            }
+        }
+        _ => components.push((format!("{}{}", prefix, type_name(normalizer)), normalizer)),
+    }
+}
+
+/// List the pre-tokenizers that are applied one after the other, with their name. The
+/// elements of a `Sequence` are listed recursively, and named after their position in it.
+fn pre_tokenizer_components<'a>(
+    pre_tokenizer: &'a PreTokenizerWrapper,
+    prefix: &str,
+    components: &mut Vec<(String, &'a PreTokenizerWrapper)>,
+) {
+    match pre_tokenizer {
+        PreTokenizerWrapper::Sequence(sequence) => {
+            for (i, pre_tokenizer) in sequence.get_pre_tokenizers().iter().enumerate() {
+                let prefix = format!("{}Sequence[{}]: ", prefix, i);
+                pre_tokenizer_components(pre_tokenizer, &prefix, components);
+            }
+        }
+        _ => components.push((
+            format!("{}{}", prefix, type_name(pre_tokenizer)),
+            pre_tokenizer,
+        )),
+    }
+}
+
+/// Whether two parts of `original` overlap. An empty part, like the position of an insertion,
+/// overlaps the parts around it.
+fn overlap(a: Offsets, b: Offsets) -> bool {
+    if a.0 == a.1 || b.0 == b.1 {
+        a.0 <= b.1 && b.0 <= a.1
+    } else {
+        a.0 < b.1 && b.0 < a.1
+    }
+}
+
+/// Find the first change of the normalization overlapping `span`, with its normalized content
+fn changed_part(normalized: &NormalizedString, span: Offsets) -> Option<(Offsets, String)> {
+    normalized
+        .changes()
+        .into_iter()
+        .find(|(original_offsets, _)| overlap(*original_offsets, span))
+        .map(|(original_offsets, normalized_offsets)| {
+            (
+                original_offsets,
+                normalized.get()[normalized_offsets.0..normalized_offsets.1].to_owned(),
+            )
+        })
+}
+
+/// Find the first part of `original` overlapping `span` removed by the pre-tokenization, except
+/// for the single spaces between two words, which are expected to be restored by the `Decoder`
+fn removed_part(
+    pretokenized: &PreTokenizedString,
+    original: &str,
+    span: Offsets,
+) -> Option<Offsets> {
+    let mut end = 0;
+    let mut removed = vec![];
+    for (_, offsets, _) in pretokenized.get_splits(OffsetReferential::Original, OffsetType::Byte) {
+        if offsets.0 > end {
+            removed.push((end, offsets.0));
+        }
+        end = end.max(offsets.1);
+    }
+    if end < original.len() {
+        removed.push((end, original.len()));
+    }
+    removed.into_iter().find(|(start, end)| {
+        overlap((*start, *end), span)
+            && (*start == 0 || *end == original.len() || &original[*start..*end] != " ")
+    })
+}
+
+/// Find the part of `original` that differs from `decoded`, after removing their common prefix
+/// and suffix.
+fn diff(original: &str, decoded: &str) -> (Offsets, String) {
+    let prefix = original
+        .char_indices()
+        .zip(decoded.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((i, _), _)| i)
+        .unwrap_or_else(|| original.len().min(decoded.len()));
+    let (original_rest, decoded_rest) = (&original[prefix..], &decoded[prefix..]);
+    let suffix = original_rest
+        .chars()
+        .rev()
+        .zip(decoded_rest.chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum::<usize>()
+        .min(original_rest.len())
+        .min(decoded_rest.len());
+
+    (
+        (prefix, original.len() - suffix),
+        decoded_rest[..decoded_rest.len() - suffix].to_owned(),
+    )
+}
+
+impl<M, N, PT, PP, D> TokenizerImpl<M, N, PT, PP, D>
+where
+    M: Model + Send + Sync,
+    N: Normalizer + Send + Sync + 'static,
+    PT: PreTokenizer + Send + Sync + 'static,
+    PP: PostProcessor + Send + Sync,
+    D: Decoder + Send + Sync,
+{
+    /// Encode and decode each of the given inputs, in parallel, and report those that are not
+    /// decoded back to their original form, along with some statistics about the tokenization.
+    ///
+    /// The special tokens are neither added nor skipped. Each mismatch is attributed to the
+    /// first step of the pipeline that can explain it:
+    ///   - the `Model` when the input is decoded back once its unknown tokens are replaced by
+    ///     what they stand for,
+    ///   - the `Normalizer` when one of its changes overlaps the part of the input that is not
+    ///     decoded back (so changes restored by the `Decoder`, like spaces replaced by `▁`,
+    ///     are not blamed),
+    ///   - the `PreTokenizer` when it removed a part overlapping it, other than a single space
+    ///     between two words (which is expected to be restored by the `Decoder`),
+    ///   - the `Decoder` otherwise.
+    ///
+    /// When a `NormalizerWrapper` or a `PreTokenizerWrapper` is responsible, the mismatch also
+    /// names the component at fault, looking into the elements of a `Sequence`.
+    pub fn validate_round_trip<S>(&self, inputs: Vec<S>) -> Result<RoundTripReport>
+    where
+        S: AsRef<str> + Send,
+    {
+        let samples = inputs
+            .into_iter()
+            .enumerate()
+            .collect::<Vec<_>>()
+            .into_maybe_par_iter()
+            .map(|(index, input)| self.round_trip(index, input.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut report = RoundTripReport {
+            samples: samples.len(),
+            ..Default::default()
+        };
+        for sample in samples {
+            report.tokens += sample.tokens;
+            report.unk_tokens += sample.unk_tokens;
+            report.words += sample.words;
+            match sample.mismatch {
+                Some(mismatch) => report.mismatches.push(mismatch),
+                None => report.lossless += 1,
+            }
+        }
+        Ok(report)
+    }
+
+    fn round_trip(&self, index: usize, original: &str) -> Result<Sample> {
+        let encoding = self.encode(original, false)?;
+        let decoded = self.decode(encoding.get_ids().to_vec(), false)?;
+
+        let unk_token = self.model.unk_token();
+        let unk_id = unk_token.and_then(|t| self.model.token_to_id(t));
+        let unk_tokens = encoding
+            .get_ids()
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| Some(**id) == unk_id)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let mut words = encoding.get_words().iter().flatten().collect::<Vec<_>>();
+        words.dedup();
+
+        let mismatch = if decoded == original {
+            None
+        } else {
+            let normalized = self.do_normalize(original)?;
+            let known = self.decode_known(&encoding, &unk_tokens, &normalized)?;
+            let (step, component, offsets, replacement) = if known == original {
+                (
+                    RoundTripStep::Model,
+                    None,
+                    encoding.get_offsets()[unk_tokens[0]],
+                    unk_token.unwrap_or_default().to_owned(),
+                )
+            } else {
+                let (span, _) = diff(original, &known);
+                match self.responsible_step(original, normalized, span)? {
+                    Some(step) => step,
+                    None => {
+                        let (offsets, replacement) = diff(original, &decoded);
+                        (RoundTripStep::Decoder, None, offsets, replacement)
+                    }
+                }
+            };
+            Some(RoundTripMismatch {
+                index,
+                original: original.to_owned(),
+                decoded,
+                step,
+                component,
+                offsets,
+                replacement,
+            })
+        };
+
+        Ok(Sample {
+            tokens: encoding.len(),
+            unk_tokens: unk_tokens.len(),
+            words: words.len(),
+            mismatch,
+        })
+    }
+
+    /// Decode the tokens of `encoding`, with each unknown token replaced by the normalized part
+    /// of the input it stands for
+    fn decode_known(
+        &self,
+        encoding: &Encoding,
+        unk_tokens: &[usize],
+        normalized: &NormalizedString,
+    ) -> Result<String> {
+        let mut tokens = encoding.get_tokens().to_vec();
+        for i in unk_tokens {
+            let (start, end) = encoding.get_offsets()[*i];
+            if let Some(part) = normalized.slice(Range::Original(start..end)) {
+                tokens[*i] = part.get().to_owned();
+            }
+        }
+
+        if let Some(decoder) = &self.decoder {
+            decoder.decode(tokens)
+        } else {
+            Ok(tokens.join(" "))
+        }
+    }
+
+    /// Look for a modification made by the `Normalizer` or the `PreTokenizer` overlapping
+    /// `span`, the part of `original` that is not decoded back, along with the component
+    /// responsible, the offsets of the modification and what it was replaced with.
+    fn responsible_step(
+        &self,
+        original: &str,
+        normalized: NormalizedString,
+        span: Offsets,
+    ) -> Result<Option<ResponsibleStep>> {
+        if let Some((offsets, replacement)) = changed_part(&normalized, span) {
+            return Ok(Some((
+                RoundTripStep::Normalizer,
+                self.responsible_normalizer(original, span)?,
+                offsets,
+                replacement,
+            )));
+        }
+
+        let pretokenized = self.do_pre_tokenize(PreTokenizedString::from(normalized.clone()))?;
+        if let Some(offsets) = removed_part(&pretokenized, original, span) {
+            return Ok(Some((
+                RoundTripStep::PreTokenizer,
+                self.responsible_pre_tokenizer(normalized, original, span)?,
+                offsets,
+                "".into(),
+            )));
+        }
+
+        Ok(None)
+    }
+
+    /// Apply the components of the `Normalizer` one by one, to find the first one that
+    /// modifies `span` in `original`
+    fn responsible_normalizer(&self, original: &str, span: Offsets) -> Result<Option<String>> {
+        let normalizer = match self
+            .normalizer
+            .as_ref()
+            .and_then(|n| (n as &dyn Any).downcast_ref::<NormalizerWrapper>())
+        {
+            Some(normalizer) => normalizer,
+            None => return Ok(None),
+        };
+        let mut components = vec![];
+        normalizer_components(normalizer, "", &mut components);
+
+        let mut normalized = NormalizedString::from(original);
+        for (name, normalizer) in components {
+            normalizer.normalize(&mut normalized)?;
+            if changed_part(&normalized, span).is_some() {
+                return Ok(Some(name));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Apply the components of the `PreTokenizer` one by one, to find the first one that
+    /// removes a part of `span` in `original`
+    fn responsible_pre_tokenizer(
+        &self,
+        normalized: NormalizedString,
+        original: &str,
+        span: Offsets,
+    ) -> Result<Option<String>> {
+        let pre_tokenizer = match self
+            .pre_tokenizer
+            .as_ref()
+            .and_then(|p| (p as &dyn Any).downcast_ref::<PreTokenizerWrapper>())
+        {
+            Some(pre_tokenizer) => pre_tokenizer,
+            None => return Ok(None),
+        };
+        let mut components = vec![];
+        pre_tokenizer_components(pre_tokenizer, "", &mut components);
+
+        let mut pretokenized = PreTokenizedString::from(normalized);
+        for (name, pre_tokenizer) in components {
+            pre_tokenizer.pre_tokenize(&mut pretokenized)?;
+            if removed_part(&pretokenized, original, span).is_some() {
+                return Ok(Some(name));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoders::wordpiece::WordPiece as WordPieceDecoder;
+    use crate::models::wordlevel::WordLevel;
+    use crate::normalizers::utils::Lowercase;
+    use crate::pre_tokenizers::whitespace::WhitespaceSplit;
+    use crate::Tokenizer;
+
+    fn build_tokenizer() -> Tokenizer {
+        let vocab = ["[UNK]", "hello", "world", "Hello", "!"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.to_string(), i as u32))
+            .collect();
+        let mut tokenizer = Tokenizer::new(
+            WordLevel::builder()
+                .vocab(vocab)
+                .unk_token("[UNK]".into())
+                .build(),
+        );
+        tokenizer
+            .with_pre_tokenizer(WhitespaceSplit)
+            .with_decoder(WordPieceDecoder::new("##".into(), false));
+        tokenizer
+    }
+
+    #[test]
+    fn diff_parts() {
+        assert_eq!(diff("Hello world", "Hello world"), ((11, 11), "".into()));
+        assert_eq!(diff("Hello world", "Hello  world"), ((6, 6), " ".into()));
+        assert_eq!(diff("Héllo", "Hello"), ((1, 3), "e".into()));
+        assert_eq!(diff("Hello", "Hel"), ((3, 5), "".into()));
+    }
+
+    #[test]
+    fn report() {
+        let tokenizer = build_tokenizer();
+        let report = tokenizer
+            .validate_round_trip(vec!["Hello world !", "hello  world", "hello you", " hello"])
+            .unwrap();
+
+        assert_eq!(report.samples, 4);
+        assert_eq!(report.lossless, 1);
+        assert_eq!(report.tokens, 8);
+        assert_eq!(report.words, 8);
+        assert_eq!(report.unk_tokens, 1);
+        assert_eq!(report.lossless_rate(), 0.25);
+        assert_eq!(report.tokens_per_word(), 1.0);
+        assert_eq!(
+            report.mismatches,
+            vec![
+                RoundTripMismatch {
+                    index: 1,
+                    original: "hello  world".into(),
+                    decoded: "hello world".into(),
+                    step: RoundTripStep::PreTokenizer,
+                    component: Some("WhitespaceSplit".into()),
+                    offsets: (5, 7),
+                    replacement: "".into(),
+                },
+                RoundTripMismatch {
+                    index: 2,
+                    original: "hello you".into(),
+                    decoded: "hello [UNK]".into(),
+                    step: RoundTripStep::Model,
+                    component: None,
+                    offsets: (6, 9),
+                    replacement: "[UNK]".into(),
+                },
+                RoundTripMismatch {
+                    index: 3,
+                    original: " hello".into(),
+                    decoded: "hello".into(),
+                    step: RoundTripStep::PreTokenizer,
+                    component: Some("WhitespaceSplit".into()),
+                    offsets: (0, 1),
+                    replacement: "".into(),
+                },
+            ]
+        );
+        assert_eq!(report.mismatches_by_step()[&RoundTripStep::PreTokenizer], 2);
+    }
+
+    #[test]
+    fn normalizer_and_decoder() {
+        let mut tokenizer = build_tokenizer();
+        tokenizer.with_normalizer(Lowercase);
+        let report = tokenizer.validate_round_trip(vec!["Hello World"]).unwrap();
+        assert_eq!(report.mismatches[0].step, RoundTripStep::Normalizer);
+        assert_eq!(report.mismatches[0].offsets, (0, 1));
+        assert_eq!(report.mismatches[0].replacement, "h");
+
+        let mut tokenizer = build_tokenizer();
+        tokenizer.with_decoder(crate::decoders::fuse::Fuse);
+        let report = tokenizer.validate_round_trip(vec!["hello world"]).unwrap();
+        assert_eq!(report.mismatches[0].step, RoundTripStep::Decoder);
+        assert_eq!(report.mismatches[0].decoded, "helloworld");
+        assert_eq!(report.mismatches[0].offsets, (5, 6));
+        assert_eq!(report.mismatches[0].replacement, "");
+    }
+
+    #[test]
+    fn reversible_normalizer() {
+        use crate::decoders::DecoderWrapper;
+        use crate::normalizers::replace::Replace;
+        use crate::pre_tokenizers::metaspace::Metaspace;
+
+        let vocab = ["[UNK]", "hello", "▁world"]
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.to_string(), i as u32))
+            .collect();
+        let mut tokenizer = Tokenizer::new(
+            WordLevel::builder()
+                .vocab(vocab)
+                .unk_token("[UNK]".into())
+                .build(),
+        );
+        tokenizer
+            .with_normalizer(Replace::new(" ", "▁").unwrap())
+            .with_pre_tokenizer(Metaspace::new('▁', false))
+            .with_decoder(DecoderWrapper::Metaspace(Metaspace::new('▁', false)));
+
+        // The spaces replaced by the normalizer are restored by the decoder
+        let report = tokenizer
+            .validate_round_trip(vec!["hello world", "hello you", "Hello world"])
+            .unwrap();
+        assert_eq!(report.lossless, 1);
+        assert_eq!(
+            report.mismatches[0],
+            RoundTripMismatch {
+                index: 1,
+                original: "hello you".into(),
+                decoded: "hello[UNK]".into(),
+                step: RoundTripStep::Model,
+                component: None,
+                offsets: (5, 9),
+                replacement: "[UNK]".into(),
+            }
+        );
+        assert_eq!(report.mismatches[1].step, RoundTripStep::Model);
+        assert_eq!(report.mismatches[1].offsets, (0, 5));
+
+        // With a decoder that doesn't restore them, the normalizer is responsible
+        tokenizer.with_decoder(crate::decoders::fuse::Fuse);
+        let report = tokenizer.validate_round_trip(vec!["hello world"]).unwrap();
+        assert_eq!(report.mismatches[0].step, RoundTripStep::Normalizer);
+        assert_eq!(report.mismatches[0].component.as_deref(), Some("Replace"));
+        assert_eq!(report.mismatches[0].offsets, (5, 6));
+        assert_eq!(report.mismatches[0].replacement, "▁");
+    }
+
+    #[test]
+    fn sequence_components() {
+        use crate::normalizers::{unicode::NFC, utils::Sequence as NormalizerSequence};
+        use crate::pre_tokenizers::{
+            punctuation::Punctuation, sequence::Sequence as PreTokenizerSequence,
+        };
+
+        let mut tokenizer = build_tokenizer();
+        tokenizer.with_normalizer(NormalizerSequence::new(vec![
+            NFC.into(),
+            NormalizerSequence::new(vec![NFC.into(), Lowercase.into()]).into(),
+        ]));
+        let report = tokenizer.validate_round_trip(vec!["Hello"]).unwrap();
+        assert_eq!(report.mismatches[0].step, RoundTripStep::Normalizer);
+        assert_eq!(
+            report.mismatches[0].component.as_deref(),
+            Some("Sequence[1]: Sequence[1]: Lowercase")
+        );
+
+        let mut tokenizer = build_tokenizer();
+        tokenizer.with_pre_tokenizer(PreTokenizerSequence::new(vec![
+            Punctuation.into(),
+            WhitespaceSplit.into(),
+        ]));
+        let report = tokenizer
+            .validate_round_trip(vec!["hello  world!"])
+            .unwrap();
+        assert_eq!(report.mismatches[0].step, RoundTripStep::PreTokenizer);
+        assert_eq!(report.mismatches[0].offsets, (5, 7));
+        assert_eq!(
+            report.mismatches[0].component.as_deref(),
+            Some("Sequence[1]: WhitespaceSplit")
+        );
+    }
+}
@@ -0,0 +1,826 @@
+//! Loading of the `.model` files produced by [SentencePiece](https://github.com/google/sentencepiece).
+//!
+//! These files contain a serialized `ModelProto` message. We only need a handful of its fields,
+//! so instead of depending on a complete protobuf implementation, we decode the wire format
+//! directly and skip everything else.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::decoders::byte_fallback::ByteFallback;
+use crate::decoders::sequence::Sequence as DecoderSequence;
+use crate::decoders::DecoderWrapper;
+use crate::models::bpe::{Merges, BPE};
+use crate::models::unigram::Unigram;
+use crate::models::ModelWrapper;
+use crate::normalizers::{
+    precompiled::Precompiled,
+    replace::{Replace, ReplacePattern},
+    strip::Strip,
+    utils::Sequence,
+    NormalizerWrapper,
+};
+use crate::pre_tokenizers::metaspace::Metaspace;
+use crate::processors::template::TemplateProcessing;
+use crate::tokenizer::{AddedToken, Result, Tokenizer};
+use crate::utils::padding::PaddingParams;
+
+#[derive(Debug)]
+pub enum SentencePieceError {
+    /// The data is not a valid `ModelProto`
+    InvalidProto(&'static str),
+    /// The model uses some options that we can't reproduce
+    Unsupported(Vec<String>),
+    /// The `unk_id` doesn't point to a piece of the vocabulary
+    InvalidUnkId(i32),
+    /// The beginning or end of sentence piece has been requested, but the model has none
+    MissingPiece(&'static str),
+}
+
+impl fmt::Display for SentencePieceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SentencePieceError::InvalidProto(e) => {
+                write!(f, "Invalid SentencePiece model: {}", e)
+            }
+            SentencePieceError::Unsupported(options) => write!(
+                f,
+                "Unsupported SentencePiece options: {}",
+                options.join(", ")
+            ),
+            SentencePieceError::InvalidUnkId(id) => {
+                write!(f, "The unk_id {} is not part of the vocabulary", id)
+            }
+            SentencePieceError::MissingPiece(piece) => {
+                write!(f, "The model has no {} piece", piece)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SentencePieceError {}
+
+/// The type of a piece in the vocabulary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PieceType {
+    Normal,
+    Unknown,
+    Control,
+    UserDefined,
+    Unused,
+    Byte,
+}
+
+impl PieceType {
+    fn from_proto(value: u64) -> std::result::Result<Self, SentencePieceError> {
+        match value {
+            1 => Ok(PieceType::Normal),
+            2 => Ok(PieceType::Unknown),
+            3 => Ok(PieceType::Control),
+            4 => Ok(PieceType::UserDefined),
+            5 => Ok(PieceType::Unused),
+            6 => Ok(PieceType::Byte),
+            _ => Err(SentencePieceError::InvalidProto("unknown piece type")),
+        }
+    }
+}
+
+/// The algorithm used by the model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelType {
+    Unigram,
+    Bpe,
+    Word,
+    Char,
+}
+
+impl ModelType {
+    fn from_proto(value: u64) -> std::result::Result<Self, SentencePieceError> {
+        match value {
+            1 => Ok(ModelType::Unigram),
+            2 => Ok(ModelType::Bpe),
+            3 => Ok(ModelType::Word),
+            4 => Ok(ModelType::Char),
+            _ => Err(SentencePieceError::InvalidProto("unknown model type")),
+        }
+    }
+}
+
+/// A piece of the vocabulary, with its score
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentencePiece {
+    pub piece: String,
+    pub score: f32,
+    pub kind: PieceType,
+}
+
+/// The subset of the `TrainerSpec` that is relevant to encode/decode
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainerSpec {
+    pub model_type: ModelType,
+    pub byte_fallback: bool,
+    pub split_by_whitespace: bool,
+    pub treat_whitespace_as_suffix: bool,
+    pub unk_id: i32,
+    pub bos_id: i32,
+    pub eos_id: i32,
+    pub pad_id: i32,
+}
+
+impl Default for TrainerSpec {
+    fn default() -> Self {
+        Self {
+            model_type: ModelType::Unigram,
+            byte_fallback: false,
+            split_by_whitespace: true,
+            treat_whitespace_as_suffix: false,
+            unk_id: 0,
+            bos_id: 1,
+            eos_id: 2,
+            pad_id: -1,
+        }
+    }
+}
+
+/// The `NormalizerSpec`, describing how the input is normalized
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizerSpec {
+    pub name: String,
+    pub precompiled_charsmap: Vec<u8>,
+    pub add_dummy_prefix: bool,
+    pub remove_extra_whitespaces: bool,
+    pub escape_whitespaces: bool,
+    pub normalization_rule_tsv: String,
+}
+
+impl Default for NormalizerSpec {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            precompiled_charsmap: vec![],
+            add_dummy_prefix: true,
+            remove_extra_whitespaces: true,
+            escape_whitespaces: true,
+            normalization_rule_tsv: String::new(),
+        }
+    }
+}
+
+/// A SentencePiece model, as parsed from its `ModelProto`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SentencePieceModel {
+    pub pieces: Vec<SentencePiece>,
+    pub trainer_spec: TrainerSpec,
+    pub normalizer_spec: NormalizerSpec,
+    pub denormalizer_spec: Option<NormalizerSpec>,
+}
+
+/// A minimal reader for the protobuf wire format
+struct ProtoReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+type ProtoResult<T> = std::result::Result<T, SentencePieceError>;
+
+impl<'a> ProtoReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn varint(&mut self) -> ProtoResult<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or(SentencePieceError::InvalidProto("truncated varint"))?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(SentencePieceError::InvalidProto("varint too long"))
+    }
+
+    fn take(&mut self, n: usize) -> ProtoResult<&'a [u8]> {
+        if self.data.len() - self.pos < n {
+            return Err(SentencePieceError::InvalidProto("truncated field"));
+        }
+        let bytes = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    /// Read the key of the next field, as `(field_number, wire_type)`
+    fn key(&mut self) -> ProtoResult<(u64, u64)> {
+        let key = self.varint()?;
+        Ok((key >> 3, key & 0x7))
+    }
+
+    fn bytes(&mut self) -> ProtoResult<&'a [u8]> {
+        let len = self.varint()? as usize;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> ProtoResult<String> {
+        String::from_utf8(self.bytes()?.to_vec())
+            .map_err(|_| SentencePieceError::InvalidProto("invalid utf-8 string"))
+    }
+
+    fn float(&mut self) -> ProtoResult<f32> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(f32::from_le_bytes(bytes))
+    }
+
+    /// `int32` values are sign-extended to 64 bits on the wire
+    fn int32(&mut self) -> ProtoResult<i32> {
+        Ok(self.varint()? as i64 as i32)
+    }
+
+    fn bool(&mut self) -> ProtoResult<bool> {
+        Ok(self.varint()? != 0)
+    }
+
+    fn skip(&mut self, wire_type: u64) -> ProtoResult<()> {
+        match wire_type {
+            0 => self.varint().map(|_| ()),
+            1 => self.take(8).map(|_| ()),
+            2 => self.bytes().map(|_| ()),
+            5 => self.take(4).map(|_| ()),
+            _ => Err(SentencePieceError::InvalidProto("unsupported wire type")),
+        }
+    }
+}
+
+fn parse_piece(data: &[u8]) -> ProtoResult<SentencePiece> {
+    let mut reader = ProtoReader::new(data);
+    let mut piece = SentencePiece {
+        piece: String::new(),
+        score: 0.0,
+        kind: PieceType::Normal,
+    };
+    while !reader.is_empty() {
+        match reader.key()? {
+            (1, 2) => piece.piece = reader.string()?,
+            (2, 5) => piece.score = reader.float()?,
+            (3, 0) => piece.kind = PieceType::from_proto(reader.varint()?)?,
+            (_, wire_type) => reader.skip(wire_type)?,
+        }
+    }
+    Ok(piece)
+}
+
+fn parse_trainer_spec(data: &[u8]) -> ProtoResult<TrainerSpec> {
+    let mut reader = ProtoReader::new(data);
+    let mut spec = TrainerSpec::default();
+    while !reader.is_empty() {
+        match reader.key()? {
+            (3, 0) => spec.model_type = ModelType::from_proto(reader.varint()?)?,
+            (22, 0) => spec.split_by_whitespace = reader.bool()?,
+            (24, 0) => spec.treat_whitespace_as_suffix = reader.bool()?,
+            (35, 0) => spec.byte_fallback = reader.bool()?,
+            (40, 0) => spec.unk_id = reader.int32()?,
+            (41, 0) => spec.bos_id = reader.int32()?,
+            (42, 0) => spec.eos_id = reader.int32()?,
+            (43, 0) => spec.pad_id = reader.int32()?,
+            (_, wire_type) => reader.skip(wire_type)?,
+        }
+    }
+    Ok(spec)
+}
+
+fn parse_normalizer_spec(data: &[u8]) -> ProtoResult<NormalizerSpec> {
+    let mut reader = ProtoReader::new(data);
+    let mut spec = NormalizerSpec::default();
+    while !reader.is_empty() {
+        match reader.key()? {
+            (1, 2) => spec.name = reader.string()?,
+            (2, 2) => spec.precompiled_charsmap = reader.bytes()?.to_vec(),
+            (3, 0) => spec.add_dummy_prefix = reader.bool()?,
+            (4, 0) => spec.remove_extra_whitespaces = reader.bool()?,
+            (5, 0) => spec.escape_whitespaces = reader.bool()?,
+            (6, 2) => spec.normalization_rule_tsv = reader.string()?,
+            (_, wire_type) => reader.skip(wire_type)?,
+        }
+    }
+    Ok(spec)
+}
+
+impl SentencePieceModel {
+    /// Parse a serialized `ModelProto`
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let mut reader = ProtoReader::new(data);
+        let mut model = SentencePieceModel::default();
+        while !reader.is_empty() {
+            match reader.key()? {
+                (1, 2) => model.pieces.push(parse_piece(reader.bytes()?)?),
+                (2, 2) => model.trainer_spec = parse_trainer_spec(reader.bytes()?)?,
+                (3, 2) => model.normalizer_spec = parse_normalizer_spec(reader.bytes()?)?,
+                (5, 2) => model.denormalizer_spec = Some(parse_normalizer_spec(reader.bytes()?)?),
+                (_, wire_type) => reader.skip(wire_type)?,
+            }
+        }
+        if model.pieces.is_empty() {
+            return Err(Box::new(SentencePieceError::InvalidProto(
+                "the model has no pieces",
+            )));
+        }
+        Ok(model)
+    }
+
+    /// Load a SentencePiece `.model` file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    fn special_id(&self, id: i32) -> Option<u32> {
+        if id >= 0 && (id as usize) < self.pieces.len() {
+            Some(id as u32)
+        } else {
+            None
+        }
+    }
+
+    /// The id of the unknown piece
+    pub fn unk_id(&self) -> Option<u32> {
+        self.special_id(self.trainer_spec.unk_id)
+    }
+
+    /// The id of the beginning of sentence piece, if any
+    pub fn bos_id(&self) -> Option<u32> {
+        self.special_id(self.trainer_spec.bos_id)
+    }
+
+    /// The id of the end of sentence piece, if any
+    pub fn eos_id(&self) -> Option<u32> {
+        self.special_id(self.trainer_spec.eos_id)
+    }
+
+    /// The id of the padding piece, if any
+    pub fn pad_id(&self) -> Option<u32> {
+        self.special_id(self.trainer_spec.pad_id)
+    }
+
+    /// List the options used by this model that we are not able to reproduce
+    fn unsupported_options(&self) -> Vec<String> {
+        let mut options = vec![];
+        match self.trainer_spec.model_type {
+            ModelType::Word => options.push("model_type=WORD".into()),
+            ModelType::Char => options.push("model_type=CHAR".into()),
+            _ => (),
+        }
+        if self.trainer_spec.treat_whitespace_as_suffix {
+            options.push("treat_whitespace_as_suffix=true".into());
+        }
+        if !self.trainer_spec.split_by_whitespace {
+            options.push("split_by_whitespace=false".into());
+        }
+        let normalizer = &self.normalizer_spec;
+        if !normalizer.escape_whitespaces {
+            options.push("escape_whitespaces=false".into());
+        }
+        if normalizer.precompiled_charsmap.is_empty()
+            && !normalizer.normalization_rule_tsv.is_empty()
+        {
+            options.push("normalization_rule_tsv without precompiled_charsmap".into());
+        }
+        if let Some(denormalizer) = &self.denormalizer_spec {
+            if !denormalizer.precompiled_charsmap.is_empty() {
+                options.push("denormalizer_spec".into());
+            }
+        }
+        // SentencePiece never outputs these pieces, while our models would
+        let unused = self
+            .pieces
+            .iter()
+            .filter(|p| p.kind == PieceType::Unused)
+            .count();
+        if unused > 0 {
+            options.push(format!("{} UNUSED pieces", unused));
+        }
+        options
+    }
+
+    fn unigram(&self) -> Result<Unigram> {
+        let unk_id = self
+            .unk_id()
+            .ok_or(SentencePieceError::InvalidUnkId(self.trainer_spec.unk_id))?;
+        let vocab = self
+            .pieces
+            .iter()
+            .map(|p| (p.piece.clone(), f64::from(p.score)))
+            .collect();
+        let mut model = Unigram::from(vocab, unk_id as usize)?;
+        model.set_byte_fallback(self.trainer_spec.byte_fallback);
+        Ok(model)
+    }
+
+    /// SentencePiece doesn't store the merges of its BPE models, but the pieces are ordered
+    /// by merge priority, so we can rebuild them from the vocabulary.
+    fn bpe(&self) -> Result<BPE> {
+        let unk_id = self
+            .unk_id()
+            .ok_or(SentencePieceError::InvalidUnkId(self.trainer_spec.unk_id))?;
+        let vocab: HashMap<String, u32> = self
+            .pieces
+            .iter()
+            .enumerate()
+            .map(|(id, p)| (p.piece.clone(), id as u32))
+            .collect();
+
+        let mut merges = vec![];
+        for (id, piece) in self.pieces.iter().enumerate() {
+            if piece.kind != PieceType::Normal {
+                continue;
+            }
+            for (i, _) in piece.piece.char_indices().skip(1) {
+                let (left, right) = piece.piece.split_at(i);
+                if let (Some(l), Some(r)) = (vocab.get(left), vocab.get(right)) {
+                    merges.push(((id, *l, *r), (left.to_owned(), right.to_owned())));
+                }
+            }
+        }
+        merges.sort();
+        let merges: Merges = merges.into_iter().map(|(_, merge)| merge).collect();
+
+        BPE::builder()
+            .vocab_and_merges(vocab, merges)
+            .unk_token(self.pieces[unk_id as usize].piece.clone())
+            .fuse_unk(true)
+            .byte_fallback(self.trainer_spec.byte_fallback)
+            .build()
+    }
+
+    fn normalizer(&self) -> Result<Option<NormalizerWrapper>> {
+        let spec = &self.normalizer_spec;
+        let mut normalizers: Vec<NormalizerWrapper> = vec![];
+        if !spec.precompiled_charsmap.is_empty() {
+            let precompiled = Precompiled::from(&spec.precompiled_charsmap)
+                .map_err(|_| SentencePieceError::InvalidProto("invalid precompiled_charsmap"))?;
+            normalizers.push(precompiled.into());
+        }
+        if spec.remove_extra_whitespaces {
+            normalizers.push(Strip::new(true, true).into());
+            normalizers.push(Replace::new(ReplacePattern::Regex(" {2,}".into()), " ")?.into());
+        }
+        Ok(match normalizers.len() {
+            0 => None,
+            1 => normalizers.pop(),
+            _ => Some(Sequence::new(normalizers).into()),
+        })
+    }
+
+    /// A `TemplateProcessing` surrounding each sequence with the beginning and/or end of
+    /// sentence pieces, like SentencePiece does with its `add_bos` and `add_eos` options.
+    /// `None` if neither of them is added.
+    fn post_processor(&self, add_bos: bool, add_eos: bool) -> Result<Option<TemplateProcessing>> {
+        let bos = if add_bos {
+            Some(
+                self.bos_id()
+                    .ok_or(SentencePieceError::MissingPiece("bos"))?,
+            )
+        } else {
+            None
+        };
+        let eos = if add_eos {
+            Some(
+                self.eos_id()
+                    .ok_or(SentencePieceError::MissingPiece("eos"))?,
+            )
+        } else {
+            None
+        };
+        let specials = [bos, eos]
+            .iter()
+            .flatten()
+            .map(|&id| (self.pieces[id as usize].piece.clone(), id))
+            .collect::<Vec<_>>();
+        if specials.is_empty() {
+            return Ok(None);
+        }
+
+        let template = |sequence: &str, type_id: u32| {
+            let mut pieces = vec![];
+            if let Some(id) = bos {
+                pieces.push(format!("{}:{}", self.pieces[id as usize].piece, type_id));
+            }
+            pieces.push(format!("{}:{}", sequence, type_id));
+            if let Some(id) = eos {
+                pieces.push(format!("{}:{}", self.pieces[id as usize].piece, type_id));
+            }
+            pieces
+        };
+        let mut pair = template("$A", 0);
+        pair.extend(template("$B", 1));
+        Ok(Some(
+            TemplateProcessing::builder()
+                .try_single(template("$A", 0))?
+                .try_pair(pair)?
+                .special_tokens(specials)
+                .build()?,
+        ))
+    }
+
+    /// Build the complete `Tokenizer` described by this model: the `Unigram` or `BPE` model,
+    /// the normalization, and `Metaspace` as both pre-tokenizer and decoder, preceded by
+    /// `ByteFallback` when the model falls back on bytes. The unknown and
+    /// control pieces are added as special tokens, and the user defined ones as added tokens.
+    /// The padding piece, if any, is used for padding.
+    ///
+    /// Like SentencePiece by default, the beginning and end of sentence pieces are not added
+    /// to the encodings, see [`into_tokenizer_with`](#method.into_tokenizer_with) for this.
+    ///
+    /// Options that would make the result differ from SentencePiece are reported as an error.
+    pub fn into_tokenizer(self) -> Result<Tokenizer> {
+        self.into_tokenizer_with(false, false)
+    }
+
+    /// Build the complete `Tokenizer` like [`into_tokenizer`](#method.into_tokenizer), adding
+    /// the beginning of sentence piece to each sequence with `add_bos`, and the end of sentence
+    /// piece with `add_eos`, as SentencePiece does with the same options. For example, Llama
+    /// models only add the former, and T5 models the latter.
+    pub fn into_tokenizer_with(self, add_bos: bool, add_eos: bool) -> Result<Tokenizer> {
+        let unsupported = self.unsupported_options();
+        if !unsupported.is_empty() {
+            return Err(Box::new(SentencePieceError::Unsupported(unsupported)));
+        }
+
+        let model: ModelWrapper = match self.trainer_spec.model_type {
+            ModelType::Bpe => self.bpe()?.into(),
+            _ => self.unigram()?.into(),
+        };
+        let mut tokenizer = Tokenizer::new(model);
+        if let Some(normalizer) = self.normalizer()? {
+            tokenizer.with_normalizer(normalizer);
+        }
+        let metaspace = Metaspace::new('▁', self.normalizer_spec.add_dummy_prefix);
+        tokenizer.with_pre_tokenizer(metaspace.clone());
+        if self.trainer_spec.byte_fallback {
+            tokenizer.with_decoder(DecoderWrapper::from(DecoderSequence::new(vec![
+                ByteFallback.into(),
+                metaspace.into(),
+            ])));
+        } else {
+            tokenizer.with_decoder(DecoderWrapper::from(metaspace));
+        }
+        if let Some(post_processor) = self.post_processor(add_bos, add_eos)? {
+            tokenizer.with_post_processor(post_processor);
+        }
+        if let Some(pad_id) = self.pad_id() {
+            tokenizer.with_padding(Some(PaddingParams {
+                pad_id,
+                pad_token: self.pieces[pad_id as usize].piece.clone(),
+                ..PaddingParams::default()
+            }));
+        }
+
+        let special_tokens = self
+            .pieces
+            .iter()
+            .filter(|p| p.kind == PieceType::Unknown || p.kind == PieceType::Control)
+            .map(|p| AddedToken::from(p.piece.clone(), true))
+            .collect::<Vec<_>>();
+        tokenizer.add_special_tokens(&special_tokens);
+        let user_defined = self
+            .pieces
+            .iter()
+            .filter(|p| p.kind == PieceType::UserDefined)
+            .map(|p| AddedToken::from(p.piece.clone(), false).normalized(false))
+            .collect::<Vec<_>>();
+        tokenizer.add_tokens(&user_defined);
+
+        Ok(tokenizer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Decoder;
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut bytes = vec![];
+        while value >= 0x80 {
+            bytes.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        bytes.push(value as u8);
+        bytes
+    }
+
+    fn varint_field(number: u64, value: u64) -> Vec<u8> {
+        let mut bytes = varint(number << 3);
+        bytes.extend(varint(value));
+        bytes
+    }
+
+    fn bytes_field(number: u64, value: &[u8]) -> Vec<u8> {
+        let mut bytes = varint(number << 3 | 2);
+        bytes.extend(varint(value.len() as u64));
+        bytes.extend(value);
+        bytes
+    }
+
+    fn piece(piece: &str, score: f32, kind: u64) -> Vec<u8> {
+        let mut bytes = bytes_field(1, piece.as_bytes());
+        bytes.extend(varint(2 << 3 | 5));
+        bytes.extend(&score.to_le_bytes());
+        bytes.extend(varint_field(3, kind));
+        bytes_field(1, &bytes)
+    }
+
+    fn model_proto(model_type: u64, trainer_spec: &[u8]) -> Vec<u8> {
+        let mut data = vec![];
+        for (p, score, kind) in &[
+            ("<unk>", 0.0, 2),
+            ("<s>", 0.0, 3),
+            ("</s>", 0.0, 3),
+            ("<sep>", 0.0, 4),
+            ("▁ab", -1.0, 1),
+            ("ab", -2.0, 1),
+            ("▁", -3.0, 1),
+            ("a", -4.0, 1),
+            ("b", -5.0, 1),
+            ("<pad>", 0.0, 3),
+        ] {
+            data.extend(piece(p, *score, *kind));
+        }
+        let mut trainer = varint_field(3, model_type);
+        trainer.extend(trainer_spec);
+        data.extend(bytes_field(2, &trainer));
+        // An unknown field, that should be skipped
+        data.extend(bytes_field(4, b"self test data"));
+        data
+    }
+
+    #[test]
+    fn parse() {
+        // pad_id = -1 is encoded as a sign-extended varint
+        let model =
+            SentencePieceModel::from_bytes(&model_proto(1, &varint_field(43, (-1i64) as u64)))
+                .unwrap();
+        assert_eq!(model.pieces.len(), 10);
+        assert_eq!(
+            model.pieces[4],
+            SentencePiece {
+                piece: "▁ab".into(),
+                score: -1.0,
+                kind: PieceType::Normal
+            }
+        );
+        assert_eq!(model.trainer_spec.model_type, ModelType::Unigram);
+        assert_eq!(model.unk_id(), Some(0));
+        assert_eq!(model.bos_id(), Some(1));
+        assert_eq!(model.eos_id(), Some(2));
+        assert_eq!(model.pad_id(), None);
+        assert!(model.normalizer_spec.add_dummy_prefix);
+        assert!(model.normalizer_spec.remove_extra_whitespaces);
+
+        let data = model_proto(1, &[]);
+        assert!(SentencePieceModel::from_bytes(&data[..data.len() - 3]).is_err());
+        assert!(SentencePieceModel::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn unigram_tokenizer() {
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(1, &[]))
+            .unwrap()
+            .into_tokenizer()
+            .unwrap();
+
+        let encoding = tokenizer.encode("  ab  ab<sep></s>", false).unwrap();
+        assert_eq!(encoding.get_tokens(), &["▁ab", "▁ab", "<sep>", "</s>"]);
+        assert_eq!(encoding.get_ids(), &[4, 4, 3, 2]);
+        assert_eq!(tokenizer.decode(vec![4, 6, 7], true).unwrap(), "ab a");
+    }
+
+    #[test]
+    fn special_tokens() {
+        // Like SentencePiece, nothing is added by default
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(1, &[]))
+            .unwrap()
+            .into_tokenizer()
+            .unwrap();
+        assert!(tokenizer.get_padding().is_none());
+        let encoding = tokenizer.encode("ab", true).unwrap();
+        assert_eq!(encoding.get_tokens(), &["▁ab"]);
+
+        // Only the beginning of sentence, like Llama
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(1, &[]))
+            .unwrap()
+            .into_tokenizer_with(true, false)
+            .unwrap();
+        let encoding = tokenizer.encode("ab", true).unwrap();
+        assert_eq!(encoding.get_tokens(), &["<s>", "▁ab"]);
+        assert_eq!(encoding.get_special_tokens_mask(), &[1, 0]);
+
+        // Only the end of sentence, like T5
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(1, &[]))
+            .unwrap()
+            .into_tokenizer_with(false, true)
+            .unwrap();
+        let encoding = tokenizer.encode("ab", true).unwrap();
+        assert_eq!(encoding.get_tokens(), &["▁ab", "</s>"]);
+
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(1, &[]))
+            .unwrap()
+            .into_tokenizer_with(true, true)
+            .unwrap();
+        let encoding = tokenizer.encode("ab", true).unwrap();
+        assert_eq!(encoding.get_tokens(), &["<s>", "▁ab", "</s>"]);
+        assert_eq!(encoding.get_ids(), &[1, 4, 2]);
+        assert_eq!(encoding.get_special_tokens_mask(), &[1, 0, 1]);
+        let encoding = tokenizer.encode(("ab", "ab"), true).unwrap();
+        assert_eq!(
+            encoding.get_tokens(),
+            &["<s>", "▁ab", "</s>", "<s>", "▁ab", "</s>"]
+        );
+        assert_eq!(encoding.get_type_ids(), &[0, 0, 0, 1, 1, 1]);
+        assert_eq!(
+            tokenizer.decode(encoding.get_ids().to_vec(), true).unwrap(),
+            "ab ab"
+        );
+
+        // No beginning of sentence piece to add
+        let err =
+            SentencePieceModel::from_bytes(&model_proto(1, &varint_field(41, (-1i64) as u64)))
+                .unwrap()
+                .into_tokenizer_with(true, false)
+                .unwrap_err();
+        assert_eq!(err.to_string(), "The model has no bos piece");
+    }
+
+    #[test]
+    fn padding() {
+        // No beginning of sentence, and a padding piece
+        let mut spec = varint_field(41, (-1i64) as u64);
+        spec.extend(varint_field(43, 9));
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(1, &spec))
+            .unwrap()
+            .into_tokenizer_with(false, true)
+            .unwrap();
+
+        let encodings = tokenizer.encode_batch(vec!["ab", "ab ab"], true).unwrap();
+        assert_eq!(encodings[0].get_tokens(), &["▁ab", "</s>", "<pad>"]);
+        assert_eq!(encodings[0].get_ids(), &[4, 2, 9]);
+        assert_eq!(encodings[0].get_attention_mask(), &[1, 1, 0]);
+        assert_eq!(encodings[1].get_tokens(), &["▁ab", "▁ab", "</s>"]);
+    }
+
+    #[test]
+    fn bpe_tokenizer() {
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(2, &[]))
+            .unwrap()
+            .into_tokenizer()
+            .unwrap();
+
+        let encoding = tokenizer.encode("ab ba", false).unwrap();
+        assert_eq!(encoding.get_tokens(), &["▁ab", "▁", "b", "a"]);
+    }
+
+    #[test]
+    fn byte_fallback_decoder() {
+        let tokenizer = SentencePieceModel::from_bytes(&model_proto(2, &varint_field(35, 1)))
+            .unwrap()
+            .into_tokenizer()
+            .unwrap();
+
+        assert!(matches!(
+            tokenizer.get_decoder(),
+            Some(DecoderWrapper::Sequence(_))
+        ));
+        assert_eq!(
+            tokenizer
+                .get_decoder()
+                .unwrap()
+                .decode(vec!["▁ab".into(), "<0x61>".into(), "▁b".into()])
+                .unwrap(),
+            "aba b"
+        );
+    }
+
+    #[test]
+    fn unsupported_options() {
+        let mut spec = varint_field(24, 1);
+        spec.extend(varint_field(22, 0));
+        let mut data = model_proto(3, &spec);
+        data.extend(piece("c", -6.0, 5));
+        let err = SentencePieceModel::from_bytes(&data)
+            .unwrap()
+            .into_tokenizer()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported SentencePiece options: model_type=WORD, \
+             treat_whitespace_as_suffix=true, split_by_whitespace=false, 1 UNUSED pieces"
+        );
+    }
+}
@@ -6,11 +6,43 @@ use serde::{
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
+use serde_json::Value;
 
 use super::{added_vocabulary::AddedTokenWithId, TokenizerImpl};
+use crate::utils::tagged::with_context;
 use crate::{Decoder, Model, Normalizer, PostProcessor, PreTokenizer, TokenizerBuilder};
 
-static SERIALIZATION_VERSION: &str = "1.0";
+static SERIALIZATION_VERSION: &str = "1.1";
+
+/// Migrate the model of a tokenizer serialized with the version 1.0, where the models had no
+/// `type` field. It is inferred from the other fields.
+fn migrate_from_1_0(model: &mut Value) {
+    if let Value::Object(model) = model {
+        if !model.contains_key("type") {
+            let model_type = if model.contains_key("merges") {
+                "BPE"
+            } else if model.contains_key("unk_id") {
+                "Unigram"
+            } else if model.contains_key("continuing_subword_prefix")
+                || model.contains_key("max_input_chars_per_word")
+            {
+                "WordPiece"
+            } else {
+                "WordLevel"
+            };
+            model.insert("type".into(), model_type.into());
+        }
+    }
+}
+
+/// Deserialize the value of a field of the tokenizer, adding its name to the error if any
+fn next_field<'de, T, A>(map: &mut A, name: &str) -> Result<T, A::Error>
+where
+    T: Deserialize<'de>,
+    A: MapAccess<'de>,
+{
+    map.next_value().map_err(|e| with_context(name, e))
+}
 
 impl<M, N, PT, PP, D> Serialize for TokenizerImpl<M, N, PT, PP, D>
 where
@@ -59,37 +91,48 @@ where
     where
         De: Deserializer<'de>,
     {
-        deserializer.deserialize_struct(
-            "Tokenizer",
-            &[
-                "version",
-                "truncation",
-                "padding",
-                "added_tokens",
-                "normalizer",
-                "pre_tokenizer",
-                "post_processor",
-                "decoder",
-                "model",
-            ],
-            TokenizerVisitor(
-                PhantomData,
-                PhantomData,
-                PhantomData,
-                PhantomData,
-                PhantomData,
-            ),
-        )
+        deserialize_tokenizer(deserializer, None)
     }
 }
 
-struct TokenizerVisitor<M, N, PT, PP, D>(
-    PhantomData<M>,
-    PhantomData<N>,
-    PhantomData<PT>,
-    PhantomData<PP>,
-    PhantomData<D>,
-);
+/// Deserialize a tokenizer. The model can be provided directly, in which case the `model`
+/// field is skipped.
+pub(crate) fn deserialize_tokenizer<'de, De, M, N, PT, PP, D>(
+    deserializer: De,
+    model: Option<M>,
+) -> Result<TokenizerImpl<M, N, PT, PP, D>, De::Error>
+where
+    De: Deserializer<'de>,
+    M: Deserialize<'de> + Model,
+    N: Deserialize<'de> + Normalizer,
+    PT: Deserialize<'de> + PreTokenizer,
+    PP: Deserialize<'de> + PostProcessor,
+    D: Deserialize<'de> + Decoder,
+{
+    deserializer.deserialize_struct(
+        "Tokenizer",
+        &[
+            "version",
+            "truncation",
+            "padding",
+            "added_tokens",
+            "normalizer",
+            "pre_tokenizer",
+            "post_processor",
+            "decoder",
+            "model",
+        ],
+        TokenizerVisitor {
+            model,
+            _marker: PhantomData,
+        },
+    )
+}
+
+struct TokenizerVisitor<M, N, PT, PP, D> {
+    model: Option<M>,
+    _marker: PhantomData<(N, PT, PP, D)>,
+}
 
 impl<'de, M, N, PT, PP, D> Visitor<'de> for TokenizerVisitor<M, N, PT, PP, D>
 where
@@ -109,43 +152,70 @@ where
     where
         V: MapAccess<'de>,
     {
+        let has_model = self.model.is_some();
         let mut builder = TokenizerBuilder::new();
+        if let Some(model) = self.model {
+            builder = builder.with_model(model);
+        }
+        let mut version: Option<String> = None;
+        // The model of a file written with the version 1.0 may need to be migrated, so it is
+        // buffered when it doesn't come after the current version
+        let mut buffered_model: Option<Value> = None;
         let mut tokens: Vec<AddedTokenWithId> = vec![];
+
         while let Some(key) = map.next_key::<String>()? {
             match key.as_ref() {
                 "version" => {
-                    let v: String = map.next_value()?;
-                    if &v != "1.0" {
-                        return Err(Error::custom(format!("Unknown tokenizer version '{}'", v)));
+                    version = next_field(&mut map, "version")?;
+                    // The files written before the version was introduced use the format of
+                    // the version 1.0
+                    match version.as_deref() {
+                        None | Some("1.0") => {}
+                        Some(v) if v == SERIALIZATION_VERSION => {}
+                        Some(v) => {
+                            return Err(Error::custom(format!(
+                                "Unknown tokenizer version '{}', expected '1.0' or '{}'",
+                                v, SERIALIZATION_VERSION
+                            )));
+                        }
                     }
                 }
                 "truncation" => {
-                    builder = builder.with_truncation(map.next_value()?);
-                }
-                "padding" => {
-                    builder = builder.with_padding(map.next_value()?);
-                }
-                "added_tokens" => {
-                    tokens = map.next_value()?;
+                    builder = builder.with_truncation(next_field(&mut map, "truncation")?);
                 }
+                "padding" => builder = builder.with_padding(next_field(&mut map, "padding")?),
+                "added_tokens" => tokens = next_field(&mut map, "added_tokens")?,
                 "normalizer" => {
-                    builder = builder.with_normalizer(map.next_value()?);
+                    builder = builder.with_normalizer(next_field(&mut map, "normalizer")?);
                 }
                 "pre_tokenizer" => {
-                    builder = builder.with_pre_tokenizer(map.next_value()?);
+                    builder = builder.with_pre_tokenizer(next_field(&mut map, "pre_tokenizer")?);
                 }
-                "model" => {
-                    builder = builder.with_model(map.next_value()?);
+                "post_processor" => {
+                    builder = builder.with_post_processor(next_field(&mut map, "post_processor")?);
                 }
-                "decoder" => {
-                    builder = builder.with_decoder(map.next_value()?);
+                "decoder" => builder = builder.with_decoder(next_field(&mut map, "decoder")?),
+                "model" if !has_model => {
+                    if version.as_deref() == Some(SERIALIZATION_VERSION) {
+                        builder = builder.with_model(next_field(&mut map, "model")?);
+                    } else {
+                        buffered_model = Some(map.next_value()?);
+                    }
                 }
-                "post_processor" => {
-                    builder = builder.with_post_processor(map.next_value()?);
+                _ => {
+                    map.next_value::<serde::de::IgnoredAny>()?;
                 }
-                _ => {}
             };
         }
+
+        if let Some(mut model) = buffered_model {
+            if version.as_deref() != Some(SERIALIZATION_VERSION) {
+                migrate_from_1_0(&mut model);
+            }
+            let model = M::deserialize(model).map_err(|e| with_context("model", e))?;
+            builder = builder.with_model(model);
+        }
+
         let mut tokenizer = builder
             .build()
             .map_err(|e| V::Error::custom(e.to_string()))?;
@@ -1,25 +1,195 @@
 use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
-use std::hash::Hash;
-use std::sync::RwLock;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 
 /// The default capacity for a `BPE`'s internal cache.
 pub static DEFAULT_CACHE_CAPACITY: usize = 10_000;
+/// The maximum number of shards of a `Cache`.
+static MAX_SHARDS: usize = 16;
+/// The minimum capacity of each shard, to avoid splitting small caches too much.
+static MIN_SHARD_CAPACITY: usize = 64;
 
-/// Provides a simple multithread cache to speed up BPE tokenization that will try to read values
-/// concurrently but won't block if another thread is writing.
-/// The goal is clearly not the accuracy of the content, both get and set
-/// are not guaranteed to actually get or set.
-#[derive(Debug)]
+/// Statistics about the usage of a cache, since its creation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of lookups that found their value
+    pub hits: u64,
+    /// Number of lookups that didn't find their value
+    pub misses: u64,
+    /// Number of entries that have been evicted to make room for new ones
+    pub evictions: u64,
+    /// Number of entries currently in the cache
+    pub size: usize,
+    /// Maximum number of entries in the cache
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// The ratio of lookups that found their value, or 0 if there was no lookup yet.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Marks the absence of a link in a `Shard` list.
+const NIL: usize = usize::MAX;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    prev: usize,
+    next: usize,
+}
+
+/// A shard of the cache, with its own LRU policy. The entries form a doubly linked list,
+/// from the most recently used (`head`) to the least recently used (`tail`).
+struct Shard<K, V> {
+    map: HashMap<K, usize>,
+    entries: Vec<Entry<K, V>>,
+    head: usize,
+    tail: usize,
+    capacity: usize,
+}
+
+impl<K, V> Shard<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn new(capacity: usize) -> Self {
+        Self {
+            map: HashMap::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+            head: NIL,
+            tail: NIL,
+            capacity,
+        }
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let (prev, next) = (self.entries[i].prev, self.entries[i].next);
+        if prev == NIL {
+            self.head = next;
+        } else {
+            self.entries[prev].next = next;
+        }
+        if next == NIL {
+            self.tail = prev;
+        } else {
+            self.entries[next].prev = prev;
+        }
+    }
+
+    fn push_front(&mut self, i: usize) {
+        self.entries[i].prev = NIL;
+        self.entries[i].next = self.head;
+        if self.head != NIL {
+            self.entries[self.head].prev = i;
+        }
+        self.head = i;
+        if self.tail == NIL {
+            self.tail = i;
+        }
+    }
+
+    fn touch(&mut self, i: usize) {
+        if self.head != i {
+            self.unlink(i);
+            self.push_front(i);
+        }
+    }
+
+    fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = *self.map.get(key)?;
+        self.touch(i);
+        Some(self.entries[i].value.clone())
+    }
+
+    /// Insert the given entry, and return whether another one had to be evicted.
+    fn insert(&mut self, key: K, value: V) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+        if let Some(&i) = self.map.get(&key) {
+            self.entries[i].value = value;
+            self.touch(i);
+            return false;
+        }
+
+        let entry = Entry {
+            key: key.clone(),
+            value,
+            prev: NIL,
+            next: NIL,
+        };
+        let (i, evicted) = if self.entries.len() < self.capacity {
+            self.entries.push(entry);
+            (self.entries.len() - 1, false)
+        } else {
+            // Reuse the slot of the least recently used entry
+            let i = self.tail;
+            self.unlink(i);
+            let old = std::mem::replace(&mut self.entries[i], entry);
+            self.map.remove(&old.key);
+            (i, true)
+        };
+        self.map.insert(key, i);
+        self.push_front(i);
+        evicted
+    }
+
+    fn clear(&mut self) {
+        self.map.clear();
+        self.entries.clear();
+        self.head = NIL;
+        self.tail = NIL;
+    }
+}
+
+/// Provides a simple multithread cache to speed up BPE tokenization. The entries are split
+/// between several shards, each with its own lock, and each shard evicts its least recently
+/// used entries once it is full.
+/// Lookups and insertions never block: if a shard is being used by another thread, a lookup
+/// is simply a miss and an insertion is skipped. The goal is clearly not the accuracy of the
+/// content, both get and set are not guaranteed to actually get or set.
 pub(crate) struct Cache<K, V>
 where
     K: Eq + Hash + Clone,
     V: Clone,
 {
-    map: RwLock<HashMap<K, V>>,
+    shards: Vec<Mutex<Shard<K, V>>>,
+    hasher: RandomState,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
     pub capacity: usize,
 }
 
+impl<K, V> std::fmt::Debug for Cache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Cache")
+            .field("capacity", &self.capacity)
+            .field("shards", &self.shards.len())
+            .finish()
+    }
+}
+
 // We dont really care about Cache comparison, so let's make them always equal
 impl<K, V> PartialEq for Cache<K, V>
 where
@@ -48,8 +218,22 @@ where
 {
     /// Create new `Cache` with the given capacity.
     pub(crate) fn new(capacity: usize) -> Self {
-        let map = RwLock::new(HashMap::with_capacity(capacity));
-        Cache { map, capacity }
+        let n_shards = (capacity / MIN_SHARD_CAPACITY).max(1).min(MAX_SHARDS);
+        let shards = (0..n_shards)
+            .map(|i| {
+                // Spread the capacity as evenly as possible
+                let shard_capacity = capacity / n_shards + usize::from(i < capacity % n_shards);
+                Mutex::new(Shard::new(shard_capacity))
+            })
+            .collect();
+        Cache {
+            shards,
+            hasher: RandomState::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            capacity,
+        }
     }
 
     /// Create a fresh `Cache` with the same configuration.
@@ -57,9 +241,37 @@ where
         Self::new(self.capacity)
     }
 
-    /// Clear the cache.
+    /// Clear the cache. The statistics are kept.
     pub(crate) fn clear(&self) {
-        self.map.write().unwrap().clear();
+        for shard in &self.shards {
+            shard.lock().unwrap().clear();
+        }
+    }
+
+    /// Get the statistics of this cache.
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            size: self
+                .shards
+                .iter()
+                .map(|shard| shard.lock().unwrap().map.len())
+                .sum(),
+            capacity: self.capacity,
+        }
+    }
+
+    // `BuildHasher::hash_one` is only available since Rust 1.71
+    #[allow(clippy::manual_hash_one)]
+    fn shard<Q>(&self, key: &Q) -> &Mutex<Shard<K, V>>
+    where
+        Q: Hash + ?Sized,
+    {
+        let mut hasher = self.hasher.build_hasher();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
     }
 
     #[allow(dead_code)]
@@ -69,11 +281,7 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized + 'a,
     {
-        if let Ok(ref mut cache) = self.map.try_read() {
-            Some(keys_iter.map(|k| cache.get(k).cloned()).collect())
-        } else {
-            None
-        }
+        Some(keys_iter.map(|k| self.get(k)).collect())
     }
 
     pub(crate) fn get<Q>(&self, key: &Q) -> Option<V>
@@ -81,38 +289,101 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        if let Ok(ref mut cache) = self.map.try_read() {
-            cache.get(key).cloned()
+        let value = self
+            .shard(key)
+            .try_lock()
+            .ok()
+            .and_then(|mut shard| shard.get(key));
+        let counter = if value.is_some() {
+            &self.hits
         } else {
-            None
-        }
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
     }
 
+    #[allow(dead_code)]
     pub(crate) fn set_values<I>(&self, entries: I)
     where
         I: IntoIterator<Item = (K, V)>,
     {
-        // Before trying to acquire a write lock, we check if we are already at
-        // capacity with a read handler.
-        if let Ok(cache) = self.map.try_read() {
-            if cache.len() >= self.capacity {
-                // At capacity, so do nothing.
-                return;
+        for (key, value) in entries {
+            self.set(key, value);
+        }
+    }
+
+    pub(crate) fn set(&self, key: K, value: V) {
+        // If we can't acquire the lock, another thread is using this shard and we just skip
+        if let Ok(mut shard) = self.shard(&key).try_lock() {
+            if shard.insert(key, value) {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
             }
-        } else {
-            // If we couldn't acquire a read handle then we probably won't be able to acquire
-            // a write handle one quadrillionth of a second later.
-            return;
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut shard = Shard::new(2);
+        assert!(!shard.insert("a", 1));
+        assert!(!shard.insert("b", 2));
+        // Using "a" makes "b" the least recently used
+        assert_eq!(shard.get("a"), Some(1));
+        assert!(shard.insert("c", 3));
+        assert_eq!(shard.get("b"), None);
+        assert_eq!(shard.get("a"), Some(1));
+        assert_eq!(shard.get("c"), Some(3));
+        // Updating an entry doesn't evict anything
+        assert!(!shard.insert("a", 4));
+        assert_eq!(shard.get("a"), Some(4));
+        assert_eq!(shard.map.len(), 2);
+
+        let mut empty = Shard::new(0);
+        assert!(!empty.insert("a", 1));
+        assert_eq!(empty.get("a"), None);
+    }
 
-        // Not at capacity, so try acquiring a write handle.
-        if let Ok(mut cache) = self.map.try_write() {
-            let free = self.capacity - cache.len();
-            cache.extend(entries.into_iter().take(free));
+    #[test]
+    fn stats() {
+        let cache = Cache::new(100);
+        for i in 0..200 {
+            cache.set(i, i);
         }
+        for i in 0..200 {
+            cache.get(&i);
+        }
+
+        let stats = cache.stats();
+        assert_eq!(stats.capacity, 100);
+        assert_eq!(stats.size, 100);
+        assert_eq!(stats.evictions, 100);
+        assert_eq!(stats.hits + stats.misses, 200);
+        assert!(stats.hits > 0 && stats.hits <= 100);
+        assert_eq!(stats.hit_rate(), stats.hits as f64 / 200.0);
+
+        cache.clear();
+        assert_eq!(cache.stats().size, 0);
+        assert_eq!(cache.stats().evictions, 100);
     }
 
-    pub(crate) fn set(&self, key: K, value: V) {
-        self.set_values(std::iter::once((key, value)))
+    #[test]
+    fn capacity_is_spread_over_shards() {
+        let cache: Cache<u32, u32> = Cache::new(DEFAULT_CACHE_CAPACITY);
+        assert_eq!(cache.shards.len(), MAX_SHARDS);
+        let total: usize = cache
+            .shards
+            .iter()
+            .map(|s| s.lock().unwrap().capacity)
+            .sum();
+        assert_eq!(total, DEFAULT_CACHE_CAPACITY);
+
+        let cache: Cache<u32, u32> = Cache::new(10);
+        assert_eq!(cache.shards.len(), 1);
+        assert_eq!(cache.stats().hit_rate(), 0.0);
     }
 }
@@ -2,6 +2,8 @@ pub mod cache;
 pub mod iter;
 pub mod padding;
 pub mod parallelism;
+pub mod registry;
+pub(crate) mod tagged;
 pub mod truncation;
 
 #[macro_use]
@@ -57,3 +59,73 @@ macro_rules! impl_serde_unit_struct (
         }
     }
 );
+
+/// Implement `Deserialize` for a wrapper enum, using the `type` field of the serialized component
+/// to select the variant. Unlike `#[serde(untagged)]`, which only reports that the data did not
+/// match any variant, this tells which component and which field could not be deserialized.
+/// The component is deserialized directly once its `type` is read, without being buffered.
+/// The types that are not built-in are looked up in the given registry.
+macro_rules! impl_serde_tagged_wrapper (
+    ($enum:ident, $component:expr, $custom:ident($registry:expr), {
+        $($type_str:expr => $variant:ident($inner:ty)),* $(,)?
+    }) => {
+        impl $enum {
+            /// The `type` of the built-in components
+            pub(crate) const BUILTIN_TYPES: &'static [&'static str] = &[$($type_str),*];
+        }
+
+        impl<'de> serde::Deserialize<'de> for $enum {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error> where
+                D: serde::de::Deserializer<'de> {
+                struct TaggedVisitor;
+                impl<'de> serde::de::Visitor<'de> for TaggedVisitor {
+                    type Value = $enum;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        write!(formatter, "a {}", $component)
+                    }
+
+                    fn visit_map<A>(self, map: A) -> std::result::Result<$enum, A::Error> where
+                        A: serde::de::MapAccess<'de> {
+                        use serde::de::Error;
+                        let (type_str, map) = $crate::utils::tagged::read_type(map, $component)?;
+                        let component = serde::de::value::MapAccessDeserializer::new(map);
+                        match type_str.as_str() {
+                            $($type_str => <$inner as serde::Deserialize>::deserialize(component)
+                                .map($enum::$variant),)*
+                            _ if $registry.contains(&type_str) => {
+                                let value = <serde_json::Value as serde::Deserialize>::deserialize(
+                                    component
+                                )?;
+                                // The registered components are never removed
+                                $registry.load(&type_str, value)
+                                    .unwrap()
+                                    .map($enum::$custom)
+                                    .map_err(A::Error::custom)
+                            }
+                            _ => {
+                                let mut expected = $enum::BUILTIN_TYPES
+                                    .iter()
+                                    .map(|t| t.to_string())
+                                    .collect::<Vec<_>>();
+                                expected.extend($registry.names());
+                                return Err(A::Error::custom(format!(
+                                    "Unknown {} type `{}`, expected one of: {}",
+                                    $component,
+                                    type_str,
+                                    expected.join(", ")
+                                )));
+                            }
+                        }
+                        .map_err(|e| $crate::utils::tagged::with_context(
+                            format!("Invalid {} `{}`", $component, type_str),
+                            e,
+                        ))
+                    }
+                }
+
+                deserializer.deserialize_map(TaggedVisitor)
+            }
+        }
+    }
+);
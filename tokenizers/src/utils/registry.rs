@@ -0,0 +1,265 @@
+//! Registry of the user-defined components.
+//!
+//! The components provided by this crate are serialized with their `type`, and loaded back
+//! through the `*Wrapper` enums. A component defined outside of this crate can take part in the
+//! same process: once its type has been registered under a name, for example with
+//! [`normalizers::REGISTRY`](../../normalizers/struct.REGISTRY.html), it can be wrapped in a
+//! [`Custom`](struct.Custom.html) and is then saved in the tokenizer.json with this name as its
+//! `type`, along with its own fields. Loading the tokenizer.json creates it again using the
+//! registry.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use tokenizers::normalizers::{CustomNormalizer, NormalizerWrapper, REGISTRY};
+//! use tokenizers::{NormalizedString, Normalizer, Result};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Prefix {
+//!     prefix: String,
+//! }
+//!
+//! impl Normalizer for Prefix {
+//!     fn normalize(&self, normalized: &mut NormalizedString) -> Result<()> {
+//!         normalized.prepend(&self.prefix);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! REGISTRY.register::<Prefix>("Prefix").unwrap();
+//!
+//! let normalizer: NormalizerWrapper = CustomNormalizer::new(
+//!     "Prefix",
+//!     Prefix { prefix: "_".into() },
+//! ).into();
+//! let serialized = serde_json::to_string(&normalizer).unwrap();
+//! assert_eq!(serialized, r#"{"type":"Prefix","prefix":"_"}"#);
+//!
+//! let normalizer: NormalizerWrapper = serde_json::from_str(&serialized).unwrap();
+//! let mut normalized = NormalizedString::from("hello");
+//! normalizer.normalize(&mut normalized).unwrap();
+//! assert_eq!(normalized.get(), "_hello");
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, RwLock};
+
+use serde::de::DeserializeOwned;
+use serde::ser::{Error, SerializeMap};
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::{Decoder, Model, Normalizer, PostProcessor, PreTokenizer, Result};
+
+/// A type that can be used as the component `T`, usually a trait object like
+/// `dyn Normalizer + Send + Sync`.
+pub trait Component<T: ?Sized> {
+    fn into_shared(this: Arc<Self>) -> Arc<T>;
+}
+
+macro_rules! impl_component (
+    ($trait:ident) => {
+        impl<C: $trait + Send + Sync + 'static> Component<dyn $trait + Send + Sync> for C {
+            fn into_shared(this: Arc<Self>) -> Arc<dyn $trait + Send + Sync> {
+                this
+            }
+        }
+    }
+);
+
+impl_component!(Normalizer);
+impl_component!(PreTokenizer);
+impl_component!(Model);
+impl_component!(PostProcessor);
+impl_component!(Decoder);
+
+type Config = Arc<dyn Fn() -> serde_json::Result<Value> + Send + Sync>;
+
+/// A user-defined component, along with the name it has been registered with.
+pub struct Custom<T: ?Sized> {
+    name: String,
+    component: Arc<T>,
+    config: Config,
+}
+
+impl<T: ?Sized> Custom<T> {
+    /// Wrap the given component. It is serialized with `name` as its `type`, and the same
+    /// name must be registered to load it back.
+    pub fn new<C>(name: impl Into<String>, component: C) -> Self
+    where
+        C: Component<T> + Serialize + Send + Sync + 'static,
+    {
+        let component = Arc::new(component);
+        let serialized = component.clone();
+        Self {
+            name: name.into(),
+            component: C::into_shared(component),
+            config: Arc::new(move || serde_json::to_value(serialized.as_ref())),
+        }
+    }
+
+    /// The name this component has been registered with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<T: ?Sized> Deref for Custom<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.component
+    }
+}
+
+impl<T: ?Sized> Clone for Custom<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            component: self.component.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Custom<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Custom")
+            .field("name", &self.name)
+            .field("config", &(self.config)().ok())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> PartialEq for Custom<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && (self.config)().ok() == (other.config)().ok()
+    }
+}
+
+impl<T: ?Sized> Serialize for Custom<T> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let fields = match (self.config)().map_err(S::Error::custom)? {
+            Value::Object(fields) => fields,
+            Value::Null => Map::new(),
+            _ => {
+                return Err(S::Error::custom(format!(
+                    "`{}` must be serialized as a map",
+                    self.name
+                )))
+            }
+        };
+
+        let mut map = serializer.serialize_map(Some(fields.len() + 1))?;
+        map.serialize_entry("type", &self.name)?;
+        for (key, value) in fields.iter().filter(|(key, _)| *key != "type") {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+type Loader<T> = Box<dyn Fn(Value) -> serde_json::Result<Custom<T>> + Send + Sync>;
+
+/// The user-defined components of one kind, by name.
+pub struct Registry<T: ?Sized> {
+    component: &'static str,
+    builtins: &'static [&'static str],
+    loaders: RwLock<HashMap<String, Loader<T>>>,
+}
+
+impl<T: ?Sized> Registry<T> {
+    pub(crate) fn new(component: &'static str, builtins: &'static [&'static str]) -> Self {
+        Self {
+            component,
+            builtins,
+            loaders: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Register the type `C` under the given name, so that the components serialized with this
+    /// `type` can be loaded. The name can't be the one of a built-in component, nor be
+    /// registered twice.
+    pub fn register<C>(&self, name: &str) -> Result<()>
+    where
+        C: Component<T> + Serialize + DeserializeOwned + Send + Sync + 'static,
+    {
+        self.register_loader(name, |config| Ok(from_config::<C>(config)?))
+    }
+
+    /// Register the function creating the components serialized with the given `type`, from
+    /// their fields. This is useful when these components don't implement `Deserialize`, like
+    /// the ones defined in the bindings. The same rules as [`register`](#method.register) apply
+    /// to the name.
+    pub fn register_loader<C, F>(&self, name: &str, loader: F) -> Result<()>
+    where
+        C: Component<T> + Serialize + Send + Sync + 'static,
+        F: Fn(Value) -> Result<C> + Send + Sync + 'static,
+    {
+        if self.builtins.contains(&name) {
+            return Err(format!("`{}` is already a built-in {}", name, self.component).into());
+        }
+        let mut loaders = self.loaders.write().unwrap();
+        if loaders.contains_key(name) {
+            return Err(format!("A {} is already registered as `{}`", self.component, name).into());
+        }
+
+        let owned_name = name.to_owned();
+        let loader: Loader<T> = Box::new(move |mut config| {
+            if let Value::Object(fields) = &mut config {
+                fields.remove("type");
+            }
+            let component = loader(config).map_err(serde::de::Error::custom)?;
+            Ok(Custom::new(owned_name.clone(), component))
+        });
+        loaders.insert(name.to_owned(), loader);
+        Ok(())
+    }
+
+    /// Whether a component is registered under the given name
+    pub fn contains(&self, name: &str) -> bool {
+        self.loaders.read().unwrap().contains_key(name)
+    }
+
+    /// The registered names, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self
+            .loaders
+            .read()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Load the component registered under `name`, from its serialized form. Returns `None` if
+    /// no component is registered under this name.
+    pub(crate) fn load(&self, name: &str, config: Value) -> Option<serde_json::Result<Custom<T>>> {
+        self.loaders
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|loader| loader(config))
+    }
+}
+
+/// Deserialize a component from its fields, without its `type`. The components that are
+/// serialized as `null`, like unit structs, have no field at all.
+fn from_config<C: DeserializeOwned>(config: Value) -> serde_json::Result<C> {
+    let fields = match config {
+        Value::Object(fields) => fields,
+        other => return serde_json::from_value(other),
+    };
+    if fields.is_empty() {
+        if let Ok(component) = serde_json::from_value(Value::Null) {
+            return Ok(component);
+        }
+    }
+    serde_json::from_value(Value::Object(fields))
+}
@@ -0,0 +1,114 @@
+//! Helpers to deserialize the components tagged with their `type`, without buffering them.
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::vec;
+
+use serde::de::{DeserializeSeed, Error, IntoDeserializer, MapAccess};
+use serde_json::Value;
+
+/// Read the `type` of a tagged component from its map. The entries before `type`, usually
+/// none as it is serialized first, are buffered.
+///
+/// Returns this `type`, along with a map giving back every entry, `type` first, to deserialize
+/// the component itself.
+pub(crate) fn read_type<'de, A>(
+    mut map: A,
+    component: &str,
+) -> Result<(String, TaggedMap<'de, A>), A::Error>
+where
+    A: MapAccess<'de>,
+{
+    let mut before = vec![];
+    while let Some(key) = map.next_key::<String>()? {
+        if key == "type" {
+            let type_str = map.next_value::<String>().map_err(|_| {
+                A::Error::custom(format!(
+                    "Invalid {}: the field `type` must be a string",
+                    component
+                ))
+            })?;
+            let tagged = TaggedMap {
+                type_str: Some(type_str.clone()),
+                before: before.into_iter(),
+                value: None,
+                map,
+                _marker: PhantomData,
+            };
+            return Ok((type_str, tagged));
+        }
+        before.push((key, map.next_value::<Value>()?));
+    }
+    Err(A::Error::custom(format!(
+        "Invalid {}: missing field `type`",
+        component
+    )))
+}
+
+/// Wrap an error with some context. As `serde_json` appends the position of an error to its
+/// message, and appends it again once wrapped, only the outermost position is kept.
+pub(crate) fn with_context<E: Error>(context: impl Display, error: impl Display) -> E {
+    let message = error.to_string();
+    let message = match message.rfind(" at line ") {
+        Some(i) if is_position(&message[i + " at line ".len()..]) => &message[..i],
+        _ => &message,
+    };
+    E::custom(format!("{}: {}", context, message))
+}
+
+/// Whether `s` looks like the `N column M` ending the position of a `serde_json` error
+fn is_position(s: &str) -> bool {
+    let number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    match s.find(" column ") {
+        Some(i) => number(&s[..i]) && number(&s[i + " column ".len()..]),
+        None => false,
+    }
+}
+
+/// The entries of a tagged component, with its `type` first, as returned by `read_type`
+pub(crate) struct TaggedMap<'de, A> {
+    type_str: Option<String>,
+    before: vec::IntoIter<(String, Value)>,
+    value: Option<Entry>,
+    map: A,
+    _marker: PhantomData<&'de ()>,
+}
+
+/// The value of the last key given by a `TaggedMap`, when it doesn't come from the map itself
+enum Entry {
+    Type(String),
+    Buffered(Value),
+}
+
+impl<'de, A> MapAccess<'de> for TaggedMap<'de, A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if let Some(type_str) = self.type_str.take() {
+            self.value = Some(Entry::Type(type_str));
+            return seed.deserialize("type".into_deserializer()).map(Some);
+        }
+        if let Some((key, value)) = self.before.next() {
+            self.value = Some(Entry::Buffered(value));
+            return seed.deserialize(key.into_deserializer()).map(Some);
+        }
+        self.map.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(Entry::Type(type_str)) => seed.deserialize(type_str.into_deserializer()),
+            Some(Entry::Buffered(value)) => seed.deserialize(value).map_err(A::Error::custom),
+            None => self.map.next_value_seed(seed),
+        }
+    }
+}
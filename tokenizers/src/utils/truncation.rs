@@ -1,13 +1,37 @@
 use crate::tokenizer::{Encoding, Result};
 use serde::{Deserialize, Serialize};
-use std::cmp;
-use std::mem;
+
+/// The side from which the tokens are removed when truncating.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TruncationDirection {
+    Left,
+    Right,
+}
+
+// `#[default]` on enum variants is only available since Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for TruncationDirection {
+    fn default() -> Self {
+        TruncationDirection::Right
+    }
+}
+
+impl std::convert::AsRef<str> for TruncationDirection {
+    fn as_ref(&self) -> &str {
+        match self {
+            TruncationDirection::Left => "left",
+            TruncationDirection::Right => "right",
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TruncationParams {
     pub max_length: usize,
     pub strategy: TruncationStrategy,
     pub stride: usize,
+    #[serde(default)]
+    pub direction: TruncationDirection,
 }
 
 impl Default for TruncationParams {
@@ -16,6 +40,7 @@ impl Default for TruncationParams {
             max_length: 512,
             strategy: TruncationStrategy::LongestFirst,
             stride: 0,
+            direction: TruncationDirection::Right,
         }
     }
 }
@@ -24,6 +49,8 @@ impl Default for TruncationParams {
 pub enum TruncationError {
     /// We are supposed to truncate the pair sequence, but it has not been provided.
     SecondSequenceNotProvided,
+    /// We are supposed to truncate the sequence at the given index, but it has not been provided.
+    SequenceNotProvided(usize),
     /// We cannot truncate the target sequence enough to respect the provided max length.
     SequenceTooShort,
     /// We cannot truncate with the given constraints.
@@ -37,6 +64,9 @@ impl std::fmt::Display for TruncationError {
             SecondSequenceNotProvided => {
                 write!(fmt, "Truncation error: Second sequence not provided")
             }
+            SequenceNotProvided(index) => {
+                write!(fmt, "Truncation error: Sequence {} not provided", index)
+            }
             SequenceTooShort => write!(
                 fmt,
                 "Truncation error: Sequence to truncate too short to respect the provided max_length"
@@ -52,9 +82,26 @@ impl std::error::Error for TruncationError {}
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TruncationStrategy {
+    /// Remove tokens from the longest sequences first, until all of them fit
     LongestFirst,
+    /// Only truncate the first sequence
     OnlyFirst,
+    /// Only truncate the second sequence
     OnlySecond,
+    /// Only truncate the sequence at the given index
+    OnlySequence(usize),
+}
+
+impl TruncationStrategy {
+    /// The index of the only sequence to truncate, if any
+    fn target_sequence(&self) -> Option<usize> {
+        match self {
+            TruncationStrategy::LongestFirst => None,
+            TruncationStrategy::OnlyFirst => Some(0),
+            TruncationStrategy::OnlySecond => Some(1),
+            TruncationStrategy::OnlySequence(index) => Some(*index),
+        }
+    }
 }
 
 impl std::convert::AsRef<str> for TruncationStrategy {
@@ -63,104 +110,107 @@ impl std::convert::AsRef<str> for TruncationStrategy {
             TruncationStrategy::LongestFirst => "longest_first",
             TruncationStrategy::OnlyFirst => "only_first",
             TruncationStrategy::OnlySecond => "only_second",
+            TruncationStrategy::OnlySequence(_) => "only_sequence",
         }
     }
 }
 
 pub fn truncate_encodings(
-    mut encoding: Encoding,
-    mut pair_encoding: Option<Encoding>,
+    encoding: Encoding,
+    pair_encoding: Option<Encoding>,
     params: &TruncationParams,
 ) -> Result<(Encoding, Option<Encoding>)> {
+    let has_pair = pair_encoding.is_some();
+    let mut encodings = truncate_sequences(
+        std::iter::once(encoding).chain(pair_encoding).collect(),
+        params,
+    )?;
+
+    let pair_encoding = if has_pair { encodings.pop() } else { None };
+    Ok((encodings.pop().unwrap(), pair_encoding))
+}
+
+/// Truncate any number of sequences so that their total length fits in `max_length`.
+///
+/// With `LongestFirst`, the longest sequences are truncated first, so that all the sequences
+/// end up with the same length, or their original one if shorter. With two sequences, this is
+/// the same as iteratively removing one token from the longest sequence.
+pub fn truncate_sequences(
+    mut encodings: Vec<Encoding>,
+    params: &TruncationParams,
+) -> Result<Vec<Encoding>> {
     if params.max_length == 0 {
-        encoding.truncate(0, params.stride);
-        if let Some(other_encoding) = pair_encoding.as_mut() {
-            other_encoding.truncate(0, params.stride);
+        for encoding in encodings.iter_mut() {
+            encoding.truncate(0, params.stride, params.direction);
         }
-        return Ok((encoding, pair_encoding));
+        return Ok(encodings);
     }
 
-    let total_length = encoding.get_ids().len()
-        + pair_encoding
-            .as_ref()
-            .map(|e| e.get_ids().len())
-            .unwrap_or(0);
+    let total_length = encodings.iter().map(|e| e.get_ids().len()).sum::<usize>();
     let to_remove = if total_length > params.max_length {
         total_length - params.max_length
     } else {
-        return Ok((encoding, pair_encoding));
+        return Ok(encodings);
     };
 
-    match params.strategy {
-        TruncationStrategy::LongestFirst => {
-            if let Some(other_encoding) = pair_encoding.as_mut() {
-                // Assuming n1 <= n2, there are 3 cases
-                // Case 1:
-                //   No truncation needs to be performed.
-                //   This scenario is handled before the match.
-                // Case 2:
-                //   Only the longer input needs to be truncated.
-                //   n1 = n1
-                //   n2 = max_length - n1
-                // Case 3:
-                //   Both inputs must be truncated.
-                //   n1 = max_length / 2
-                //   n2 = n1 + max_length % 2
-
-                let mut n1 = encoding.get_ids().len();
-                let mut n2 = other_encoding.get_ids().len();
-                let mut swap = false;
-
-                // Ensure n1 is the length of the shortest input
-                if n1 > n2 {
-                    swap = true;
-                    mem::swap(&mut n1, &mut n2);
-                }
-
-                if n1 > params.max_length {
-                    // This needs to be a special case
-                    // to avoid max_length - n1 < 0
-                    // since n1 and n2 are unsigned
-                    n2 = n1;
-                } else {
-                    n2 = cmp::max(n1, params.max_length - n1);
-                }
-
-                if n1 + n2 > params.max_length {
-                    n1 = params.max_length / 2;
-                    n2 = n1 + params.max_length % 2;
-                }
-
-                // Swap lengths if we swapped previosuly
-                if swap {
-                    mem::swap(&mut n1, &mut n2);
-                }
-                encoding.truncate(n1, params.stride);
-                other_encoding.truncate(n2, params.stride);
-            } else {
-                encoding.truncate(total_length - to_remove, params.stride);
+    match params.strategy.target_sequence() {
+        None => {
+            let lengths = encodings
+                .iter()
+                .map(|e| e.get_ids().len())
+                .collect::<Vec<_>>();
+            let targets = longest_first_lengths(&lengths, params.max_length);
+            for (encoding, length) in encodings.iter_mut().zip(targets) {
+                encoding.truncate(length, params.stride, params.direction);
             }
         }
-        TruncationStrategy::OnlyFirst | TruncationStrategy::OnlySecond => {
-            let target = if params.strategy == TruncationStrategy::OnlyFirst {
-                Ok(&mut encoding)
-            } else if let Some(encoding) = pair_encoding.as_mut() {
-                Ok(encoding)
-            } else {
-                Err(Box::new(TruncationError::SecondSequenceNotProvided))
-            }?;
+        Some(index) => {
+            let target = encodings.get_mut(index).ok_or_else(|| {
+                Box::new(if index == 1 {
+                    TruncationError::SecondSequenceNotProvided
+                } else {
+                    TruncationError::SequenceNotProvided(index)
+                })
+            })?;
 
             let target_len = target.get_ids().len();
             if target_len > to_remove {
-                target.truncate(target_len - to_remove, params.stride);
+                target.truncate(target_len - to_remove, params.stride, params.direction);
             } else {
                 return Err(Box::new(TruncationError::SequenceTooShort));
             }
         }
     }
-    Ok((encoding, pair_encoding))
+    Ok(encodings)
 }
 
+/// Compute the length of each sequence once truncated with `LongestFirst`.
+///
+/// We look for the largest length that we can give to all the sequences longer than it. The
+/// tokens that remain are then given to the longest sequences, the last ones first on ties.
+fn longest_first_lengths(lengths: &[usize], max_length: usize) -> Vec<usize> {
+    let mut order = (0..lengths.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| (lengths[*i], *i));
+
+    let mut targets = lengths.to_vec();
+    let mut remaining = max_length;
+    for (position, i) in order.iter().enumerate() {
+        let left = order.len() - position;
+        let share = remaining / left;
+        if lengths[*i] <= share {
+            remaining -= lengths[*i];
+            continue;
+        }
+
+        // All the sequences left are longer than their share
+        let extra = remaining - share * left;
+        for (rank, j) in order[position..].iter().enumerate() {
+            targets[*j] = share + usize::from(rank >= left - extra);
+        }
+        break;
+    }
+    targets
+}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,6 +322,7 @@ mod tests {
             max_length: 7,
             strategy: TruncationStrategy::LongestFirst,
             stride: 0,
+            direction: TruncationDirection::Right,
         };
 
         truncate_and_assert(get_empty(), get_empty(), &params, 0, 0);
@@ -301,10 +352,97 @@ mod tests {
             max_length: 0,
             strategy: TruncationStrategy::LongestFirst,
             stride: 0,
+            direction: TruncationDirection::Right,
         };
 
         truncate_and_assert(get_empty(), get_short(), &params, 0, 0);
         truncate_and_assert(get_medium(), get_medium(), &params, 0, 0);
         truncate_and_assert(get_long(), get_long(), &params, 0, 0);
     }
+
+    #[test]
+    fn truncate_encodings_left() {
+        let params = TruncationParams {
+            max_length: 3,
+            strategy: TruncationStrategy::OnlyFirst,
+            stride: 0,
+            direction: TruncationDirection::Left,
+        };
+
+        let (e1, e2) = truncate_encodings(get_medium(), Some(get_empty()), &params).unwrap();
+        assert_eq!(e1.get_ids(), &[4, 5, 6]);
+        assert_eq!(e1.get_overflowing()[0].get_ids(), &[3]);
+        assert!(e2.unwrap().get_ids().is_empty());
+
+        let params = TruncationParams {
+            max_length: 4,
+            strategy: TruncationStrategy::LongestFirst,
+            ..params
+        };
+        let (e1, e2) = truncate_encodings(get_medium(), Some(get_short()), &params).unwrap();
+        assert_eq!(e1.get_ids(), &[5, 6]);
+        assert_eq!(e2.unwrap().get_ids(), &[1, 2]);
+    }
+
+    #[test]
+    fn truncation_params_direction_defaults_to_right() {
+        let params: TruncationParams =
+            serde_json::from_str(r#"{"max_length":5,"strategy":"OnlyFirst","stride":1}"#).unwrap();
+        assert_eq!(params.direction, TruncationDirection::Right);
+        assert_eq!(
+            serde_json::to_string(&TruncationParams {
+                direction: TruncationDirection::Left,
+                ..params
+            })
+            .unwrap(),
+            r#"{"max_length":5,"strategy":"OnlyFirst","stride":1,"direction":"Left"}"#
+        );
+    }
+
+    #[test]
+    fn truncate_sequences_longest_first() {
+        let params = TruncationParams {
+            max_length: 9,
+            ..Default::default()
+        };
+        let lengths = |encodings: Vec<Encoding>| {
+            truncate_sequences(encodings, &params)
+                .unwrap()
+                .iter()
+                .map(|e| e.get_ids().len())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            lengths(vec![get_short(), get_medium(), get_long()]),
+            vec![2, 3, 4]
+        );
+        assert_eq!(
+            lengths(vec![get_long(), get_long(), get_short()]),
+            vec![3, 4, 2]
+        );
+        assert_eq!(
+            lengths(vec![get_medium(), get_medium(), get_medium()]),
+            vec![3, 3, 3]
+        );
+        assert_eq!(
+            lengths(vec![get_short(), get_empty(), get_medium()]),
+            vec![2, 0, 4]
+        );
+    }
+
+    #[test]
+    fn truncate_sequences_only_sequence() {
+        let params = TruncationParams {
+            max_length: 9,
+            strategy: TruncationStrategy::OnlySequence(2),
+            ..Default::default()
+        };
+        let encodings =
+            truncate_sequences(vec![get_short(), get_medium(), get_long()], &params).unwrap();
+        assert_eq!(encodings[2].get_ids().len(), 3);
+
+        let err = truncate_sequences(vec![get_long(), get_long()], &params).unwrap_err();
+        assert_eq!(err.to_string(), "Truncation error: Sequence 2 not provided");
+    }
 }
@@ -116,5 +116,7 @@ fn overlapping_tokens() {
 
     let output = tokenizer.encode(input, false).unwrap();
 
-    assert_eq!(output.get_tokens(), &["I", "Ġl", "ike", "Ġda", "nci", "ng"]);
+    // The insertion order doesn't matter: `danc` starts before `nci`, so it is kept, as with the
+    // leftmost-longest semantics of the matcher
+    assert_eq!(output.get_tokens(), &["I", "Ġl", "ike", "Ġ", "danc", "ing"]);
 }
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use tokenizers::decoders::byte_level::ByteLevel;
+use tokenizers::decoders::metaspace::Metaspace;
+use tokenizers::decoders::wordpiece::WordPiece as WordPieceDecoder;
+use tokenizers::models::wordlevel::WordLevel;
+use tokenizers::models::wordpiece::WordPiece;
+use tokenizers::{AddedToken, Tokenizer};
+
+fn vocab(tokens: &[&str]) -> HashMap<String, u32> {
+    tokens
+        .iter()
+        .enumerate()
+        .map(|(i, t)| ((*t).to_owned(), i as u32))
+        .collect()
+}
+
+fn stream_all(
+    tokenizer: &Tokenizer,
+    ids: &[u32],
+    skip_special_tokens: bool,
+) -> Vec<Option<String>> {
+    let mut stream = tokenizer.decode_stream(skip_special_tokens);
+    ids.iter().map(|id| stream.step(*id).unwrap()).collect()
+}
+
+#[test]
+fn byte_level_split_chars() {
+    // "é" is encoded as the two bytes 0xC3 0xA9, represented by "Ã" and "©" at the byte-level
+    let model = WordLevel::builder()
+        .vocab(vocab(&["<unk>", "Ã", "©", "Ġcaf", "Ġnaïve", "Ġ"]))
+        .build();
+    let mut tokenizer = Tokenizer::new(model);
+    tokenizer.with_decoder(ByteLevel::default());
+
+    assert_eq!(
+        stream_all(&tokenizer, &[3, 1, 2, 5, 1, 2], false),
+        vec![
+            Some(" caf".to_owned()),
+            None,
+            Some("é".to_owned()),
+            Some(" ".to_owned()),
+            None,
+            Some("é".to_owned()),
+        ]
+    );
+    assert_eq!(
+        tokenizer.decode(vec![3, 1, 2, 5, 1, 2], false).unwrap(),
+        " café é"
+    );
+}
+
+#[test]
+fn metaspace_leading_spaces() {
+    let model = WordLevel::builder()
+        .vocab(vocab(&["<unk>", "▁Hey", "▁friend", "!", "▁"]))
+        .build();
+    let mut tokenizer = Tokenizer::new(model);
+    tokenizer.with_decoder(Metaspace::default());
+
+    let ids = [1, 2, 3, 4, 1];
+    let outputs = stream_all(&tokenizer, &ids, false);
+    assert_eq!(
+        outputs,
+        vec![
+            Some("Hey".to_owned()),
+            Some(" friend".to_owned()),
+            Some("!".to_owned()),
+            Some(" ".to_owned()),
+            Some(" Hey".to_owned()),
+        ]
+    );
+    assert_eq!(
+        outputs.into_iter().flatten().collect::<String>(),
+        tokenizer.decode(ids.to_vec(), false).unwrap()
+    );
+}
+
+#[test]
+fn wordpiece_continuing_subwords() {
+    let model = WordPiece::builder()
+        .vocab(vocab(&[
+            "[UNK]", "[SEP]", "un", "##believ", "##able", "story",
+        ]))
+        .build()
+        .unwrap();
+    let mut tokenizer = Tokenizer::new(model);
+    tokenizer.with_decoder(WordPieceDecoder::default());
+    tokenizer.add_special_tokens(&[AddedToken::from("[SEP]", true)]);
+
+    assert_eq!(
+        stream_all(&tokenizer, &[2, 3, 4, 5, 1], false),
+        vec![
+            Some("un".to_owned()),
+            Some("believ".to_owned()),
+            Some("able".to_owned()),
+            Some(" story".to_owned()),
+            Some(" [SEP]".to_owned()),
+        ]
+    );
+
+    // Skipped special tokens don't produce anything, and don't break the context
+    assert_eq!(
+        stream_all(&tokenizer, &[2, 1, 3, 4, 1, 5], true),
+        vec![
+            Some("un".to_owned()),
+            None,
+            Some("believ".to_owned()),
+            Some("able".to_owned()),
+            None,
+            Some(" story".to_owned()),
+        ]
+    );
+}
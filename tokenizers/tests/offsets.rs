@@ -1,7 +1,11 @@
 mod common;
 
 use common::*;
-use tokenizers::tokenizer::AddedToken;
+use tokenizers::models::wordlevel::WordLevel;
+use tokenizers::pre_tokenizers::whitespace::Whitespace;
+use tokenizers::tokenizer::{
+    AddedToken, OffsetReferential, OffsetType, Tokenizer, TruncationParams,
+};
 
 macro_rules! check_offsets {
     ($input: expr, $output:expr, $offset:expr, $result:expr) => {
@@ -191,3 +195,45 @@ fn split_on_added_tokens_bert() {
         ]
     );
 }
+
+#[test]
+fn utf16_offsets_with_overflowing() {
+    let vocab = [("[UNK]", 0), ("a", 1), ("😀", 2), ("b", 3)]
+        .iter()
+        .map(|(token, id)| (token.to_string(), *id))
+        .collect();
+    let model = WordLevel::builder()
+        .vocab(vocab)
+        .unk_token("[UNK]".into())
+        .build();
+    let mut tokenizer = Tokenizer::new(model);
+    tokenizer
+        .with_pre_tokenizer(Whitespace::default())
+        .with_truncation(Some(TruncationParams {
+            max_length: 2,
+            ..Default::default()
+        }));
+
+    let input = "a 😀 b 😀";
+    let output = tokenizer
+        .encode_with_offsets(input, false, OffsetReferential::Original, OffsetType::Utf16)
+        .unwrap();
+    assert_eq!(output.get_tokens(), &["a", "😀"]);
+    assert_eq!(output.get_offsets(), &[(0, 1), (2, 4)]);
+    let overflowing = &output.get_overflowing()[0];
+    assert_eq!(overflowing.get_tokens(), &["b", "😀"]);
+    assert_eq!(overflowing.get_offsets(), &[(5, 6), (7, 9)]);
+
+    // The same goes for the normalized referential, where the whitespaces have been removed
+    let output = tokenizer
+        .encode_batch_with_offsets(
+            vec![input],
+            false,
+            OffsetReferential::Normalized,
+            OffsetType::Utf16,
+        )
+        .unwrap()
+        .remove(0);
+    assert_eq!(output.get_offsets(), &[(0, 1), (1, 3)]);
+    assert_eq!(output.get_overflowing()[0].get_offsets(), &[(3, 4), (4, 6)]);
+}
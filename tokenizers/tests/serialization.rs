@@ -1,22 +1,29 @@
 mod common;
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
 use common::*;
+use serde::{Deserialize, Serialize};
 use tokenizers::decoders::byte_level::ByteLevel;
 use tokenizers::decoders::DecoderWrapper;
 use tokenizers::models::bpe::BPE;
 use tokenizers::models::wordlevel::WordLevel;
 use tokenizers::models::wordpiece::WordPiece;
-use tokenizers::models::ModelWrapper;
+use tokenizers::models::{CustomModel, ModelWrapper};
 use tokenizers::normalizers::bert::BertNormalizer;
 use tokenizers::normalizers::unicode::{NFC, NFKC};
-use tokenizers::normalizers::NormalizerWrapper;
+use tokenizers::normalizers::{CustomNormalizer, NormalizerWrapper};
 use tokenizers::pre_tokenizers::bert::BertPreTokenizer;
 use tokenizers::pre_tokenizers::delimiter::CharDelimiterSplit;
+use tokenizers::pre_tokenizers::split::{Split, SplitPattern};
 use tokenizers::pre_tokenizers::whitespace::Whitespace;
 use tokenizers::pre_tokenizers::PreTokenizerWrapper;
 use tokenizers::processors::bert::BertProcessing;
 use tokenizers::processors::PostProcessorWrapper;
-use tokenizers::{Tokenizer, TokenizerImpl};
+use tokenizers::{
+    Model, NormalizedString, Normalizer, SplitDelimiterBehavior, Token, Tokenizer, TokenizerImpl,
+};
 
 #[test]
 fn bpe_serde() {
@@ -134,6 +141,22 @@ fn pretoks() {
         err.is_err(),
         "BertPreTokenizer shouldn't be deserializable from Whitespace"
     );
+
+    let split = Split::new(
+        SplitPattern::Regex(r"\w+".into()),
+        SplitDelimiterBehavior::Isolated,
+        true,
+    )
+    .unwrap();
+    let split_ser = serde_json::to_string(&split).unwrap();
+    serde_json::from_str::<Split>(&split_ser).unwrap();
+    let split_wrapped: PreTokenizerWrapper = serde_json::from_str(&split_ser).unwrap();
+    match &split_wrapped {
+        PreTokenizerWrapper::Split(_) => (),
+        _ => panic!("Split wrapped with incorrect variant"),
+    }
+    let ser_wrapped = serde_json::to_string(&split_wrapped).unwrap();
+    assert_eq!(ser_wrapped, split_ser);
 }
 
 #[test]
@@ -162,6 +185,317 @@ fn models() {
     }
     let ser_wrapped = serde_json::to_string(&bpe_wrapper).unwrap();
     assert_eq!(ser_wrapped, bpe_ser);
+
+    // The `type` is used to select the model, even when its other fields would match another one
+    let wordlevel = WordLevel::default();
+    let wordlevel_ser = serde_json::to_string(&wordlevel).unwrap();
+    let wordlevel_wrapper: ModelWrapper = serde_json::from_str(&wordlevel_ser).unwrap();
+    match &wordlevel_wrapper {
+        ModelWrapper::WordLevel(_) => (),
+        _ => panic!("WordLevel wrapped with incorrect variant"),
+    }
+    let err: Result<WordPiece, _> = serde_json::from_str(&wordlevel_ser);
+    assert!(
+        err.is_err(),
+        "WordPiece shouldn't be deserializable from WordLevel"
+    );
+}
+
+#[test]
+fn wrapper_errors() {
+    let err = serde_json::from_str::<NormalizerWrapper>(r#"{"type":"Foo"}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.starts_with("Unknown normalizer type `Foo`, expected one of: BertNormalizer, "),
+        "{}",
+        err
+    );
+
+    let err = serde_json::from_str::<NormalizerWrapper>(r#"{"lowercase":true}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("Invalid normalizer: missing field `type`"));
+
+    let err = serde_json::from_str::<ModelWrapper>(r#"{"type":"BPE","vocab":{}}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("Invalid model `BPE`: missing field `merges`"));
+
+    // The errors of the nested components are kept
+    let err = serde_json::from_str::<PreTokenizerWrapper>(
+        r#"{"type":"Sequence","pretokenizers":[{"type":"Digits"}]}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert_eq!(
+        err,
+        "Invalid pre-tokenizer `Sequence`: Invalid pre-tokenizer `Digits`: \
+         missing field `individual_digits` at line 1 column 55"
+    );
+
+    // The `type` doesn't need to come first
+    let pre_tokenizer = serde_json::from_str::<PreTokenizerWrapper>(
+        r#"{"individual_digits":true,"type":"Digits"}"#,
+    )
+    .unwrap();
+    assert!(matches!(pre_tokenizer, PreTokenizerWrapper::Digits(_)));
+
+    // And the tokenizer tells in which of its fields the error happened
+    let err = serde_json::from_str::<Tokenizer>(
+        r#"{"version":"1.1","decoder":{"type":"Foo"},"model":{"type":"BPE","vocab":{},"merges":[]}}"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        err.starts_with("decoder: Unknown decoder type `Foo`"),
+        "{}",
+        err
+    );
+
+    let err = serde_json::from_str::<Tokenizer>(r#"{"version":"0.1"}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("Unknown tokenizer version '0.1'"));
+}
+
+#[derive(Serialize, Deserialize)]
+struct Uppercase;
+
+impl Normalizer for Uppercase {
+    fn normalize(&self, normalized: &mut NormalizedString) -> tokenizers::Result<()> {
+        normalized.uppercase();
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Chars {
+    vocab: HashMap<String, u32>,
+}
+
+impl Model for Chars {
+    fn tokenize(&self, sequence: &str) -> tokenizers::Result<Vec<Token>> {
+        sequence
+            .char_indices()
+            .map(|(start, c)| {
+                let id = self.token_to_id(&c.to_string()).ok_or("Unknown char")?;
+                Ok(Token::new(id, c.to_string(), (start, start + c.len_utf8())))
+            })
+            .collect()
+    }
+
+    fn token_to_id(&self, token: &str) -> Option<u32> {
+        self.vocab.get(token).copied()
+    }
+
+    fn id_to_token(&self, id: u32) -> Option<&str> {
+        self.vocab
+            .iter()
+            .find(|(_, i)| **i == id)
+            .map(|(token, _)| token.as_str())
+    }
+
+    fn get_vocab(&self) -> &HashMap<String, u32> {
+        &self.vocab
+    }
+
+    fn get_vocab_size(&self) -> usize {
+        self.vocab.len()
+    }
+
+    fn save(&self, _folder: &Path, _prefix: Option<&str>) -> tokenizers::Result<Vec<PathBuf>> {
+        Ok(vec![])
+    }
+}
+
+#[test]
+fn custom_components() {
+    tokenizers::normalizers::REGISTRY
+        .register::<Uppercase>("Uppercase")
+        .unwrap();
+    tokenizers::models::REGISTRY
+        .register::<Chars>("Chars")
+        .unwrap();
+
+    let vocab = [("A", 0), ("B", 1)]
+        .iter()
+        .map(|(token, id)| (token.to_string(), *id))
+        .collect();
+    let mut tokenizer = Tokenizer::new(CustomModel::new("Chars", Chars { vocab }));
+    tokenizer.with_normalizer(CustomNormalizer::new("Uppercase", Uppercase));
+
+    let ser = serde_json::to_string(&tokenizer).unwrap();
+    assert!(
+        ser.contains(r#""normalizer":{"type":"Uppercase"}"#),
+        "{}",
+        ser
+    );
+    assert!(
+        ser.contains(r#""model":{"type":"Chars","vocab":{"#),
+        "{}",
+        ser
+    );
+
+    let de: Tokenizer = serde_json::from_str(&ser).unwrap();
+    assert_eq!(serde_json::to_string(&de).unwrap(), ser);
+    let encoding = de.encode("ab", false).unwrap();
+    assert_eq!(encoding.get_ids(), &[0, 1]);
+    assert_eq!(encoding.get_tokens(), &["A", "B"]);
+
+    // The registered names are listed when the type is unknown
+    let err = serde_json::from_str::<ModelWrapper>(r#"{"type":"Foo"}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.ends_with(
+            "expected one of: WordPiece, BPE, WordLevel, Unigram, Chars at line 1 column 14"
+        ),
+        "{}",
+        err
+    );
+    let err = serde_json::from_str::<ModelWrapper>(r#"{"type":"Chars","vocab":[]}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.starts_with("Invalid model `Chars`: invalid type"),
+        "{}",
+        err
+    );
+
+    // A name can only be registered once, and can't replace a built-in component
+    let err = tokenizers::models::REGISTRY
+        .register::<Chars>("Chars")
+        .unwrap_err();
+    assert_eq!(err.to_string(), "A model is already registered as `Chars`");
+    let err = tokenizers::normalizers::REGISTRY
+        .register::<Uppercase>("Lowercase")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Lowercase` is already a built-in normalizer"
+    );
+}
+
+/// Only implements `Serialize`, like the components defined in the bindings
+#[derive(Serialize)]
+struct Prefix {
+    prefix: String,
+}
+
+impl Normalizer for Prefix {
+    fn normalize(&self, normalized: &mut NormalizedString) -> tokenizers::Result<()> {
+        normalized.prepend(&self.prefix);
+        Ok(())
+    }
+}
+
+#[test]
+fn custom_loader() {
+    tokenizers::normalizers::REGISTRY
+        .register_loader("Prefix", |config| {
+            let prefix = config["prefix"].as_str().ok_or("Missing prefix")?;
+            Ok(Prefix {
+                prefix: prefix.into(),
+            })
+        })
+        .unwrap();
+
+    let normalizer: NormalizerWrapper =
+        CustomNormalizer::new("Prefix", Prefix { prefix: "_".into() }).into();
+    let ser = serde_json::to_string(&normalizer).unwrap();
+    assert_eq!(ser, r#"{"type":"Prefix","prefix":"_"}"#);
+
+    let de: NormalizerWrapper = serde_json::from_str(&ser).unwrap();
+    assert_eq!(serde_json::to_string(&de).unwrap(), ser);
+    let mut normalized = NormalizedString::from("a");
+    de.normalize(&mut normalized).unwrap();
+    assert_eq!(normalized.get(), "_a");
+
+    let err = serde_json::from_str::<NormalizerWrapper>(r#"{"type":"Prefix"}"#)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        err,
+        "Invalid normalizer `Prefix`: Missing prefix at line 1 column 17"
+    );
+}
+
+#[test]
+fn tokenizer_version_1_0() {
+    // Before 1.1, the models had no `type`
+    let tokenizer: Tokenizer = serde_json::from_str(
+        r#"{
+            "version": "1.0",
+            "truncation": null,
+            "padding": null,
+            "added_tokens": [],
+            "normalizer": {"type": "NFC"},
+            "pre_tokenizer": {"type": "Whitespace"},
+            "post_processor": null,
+            "decoder": null,
+            "model": {"vocab": {"<unk>": 0, "hello": 1}, "unk_token": "<unk>"}
+        }"#,
+    )
+    .unwrap();
+    match tokenizer.get_model() {
+        ModelWrapper::WordLevel(_) => (),
+        _ => panic!("WordLevel migrated to an incorrect model"),
+    }
+    assert_eq!(
+        tokenizer.encode("hello world", false).unwrap().get_ids(),
+        &[1, 0]
+    );
+
+    let ser = serde_json::to_value(&tokenizer).unwrap();
+    assert_eq!(ser["version"], "1.1");
+    assert_eq!(ser["model"]["type"], "WordLevel");
+}
+
+#[test]
+fn tokenizer_version_after_model() {
+    // The model is only migrated once the version is known
+    let tokenizer: Tokenizer = serde_json::from_str(
+        r#"{"model": {"type": "WordPiece", "vocab": {"[UNK]": 0}}, "version": "1.1"}"#,
+    )
+    .unwrap();
+    assert!(matches!(tokenizer.get_model(), ModelWrapper::WordPiece(_)));
+
+    let err = serde_json::from_str::<Tokenizer>(r#"{"model": {"vocab": {}}, "version": "1.1"}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.starts_with("model: Invalid model: missing field `type`"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn tokenizer_without_version() {
+    // The legacy files without any version are migrated like the 1.0 ones
+    let tokenizer: Tokenizer = serde_json::from_str(
+        r#"{
+            "added_tokens": [],
+            "normalizer": null,
+            "pre_tokenizer": {"type": "Whitespace"},
+            "model": {
+                "vocab": {"<unk>": 0, "h": 1, "e": 2, "he": 3},
+                "merges": ["h e"],
+                "unk_token": "<unk>"
+            }
+        }"#,
+    )
+    .unwrap();
+    match tokenizer.get_model() {
+        ModelWrapper::BPE(_) => (),
+        _ => panic!("BPE migrated to an incorrect model"),
+    }
+    assert_eq!(tokenizer.encode("he", false).unwrap().get_ids(), &[3]);
+
+    let ser = serde_json::to_value(&tokenizer).unwrap();
+    assert_eq!(ser["version"], "1.1");
+    assert_eq!(ser["model"]["type"], "BPE");
 }
 
 #[test]